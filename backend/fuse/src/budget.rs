@@ -0,0 +1,108 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Number of items scanned between two checks of a [`SearchBudget`].
+pub const CHECK_INTERVAL: usize = 256;
+
+/// Limits on how long a scan over a list may run.
+///
+/// A scan checks its budget every [`CHECK_INTERVAL`] items and stops once the
+/// deadline has passed or the cancel flag has been set, keeping the results
+/// found so far. The default budget is unlimited.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use fuse_lib::budget::SearchBudget;
+/// # use std::sync::Arc;
+/// # use std::sync::atomic::AtomicBool;
+/// # use std::time::Duration;
+/// let cancel = Arc::new(AtomicBool::new(false));
+/// let budget = SearchBudget::unlimited()
+///     .with_timeout(Duration::from_millis(500))
+///     .with_cancel_flag(cancel.clone());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SearchBudget {
+    deadline: Option<Instant>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+/// Results of a scan that may have been cut short by its budget.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanResult<T> {
+    /// Results of the items scanned before the budget ran out.
+    pub results: Vec<T>,
+    /// `true` if the budget ran out before every item was scanned.
+    pub partial: bool,
+}
+
+impl SearchBudget {
+    /// A budget that never runs out.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Stops scans at `deadline`.
+    pub const fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Stops scans `timeout` from now. A timeout too large to represent is unlimited.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.with_deadline(deadline),
+            None => self,
+        }
+    }
+
+    /// Stops scans once `flag` is set, e.g. when nobody waits for the results anymore.
+    pub fn with_cancel_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(flag);
+        self
+    }
+
+    /// Whether the deadline has passed or the cancel flag has been set.
+    pub fn is_exhausted(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Applies `f` to each item until the items or the budget run out.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The items to scan, in order
+    /// * `f` - Returns the result for an item, or `None` to skip it
+    ///
+    /// # Returns
+    ///
+    /// The results of the scanned items in scan order, and whether the scan
+    /// stopped early.
+    pub fn scan<I, T, F>(&self, items: I, mut f: F) -> ScanResult<T>
+    where
+        I: IntoIterator,
+        F: FnMut(I::Item) -> Option<T>,
+    {
+        let mut results = Vec::new();
+        for (scanned, item) in items.into_iter().enumerate() {
+            if scanned % CHECK_INTERVAL == 0 && self.is_exhausted() {
+                return ScanResult {
+                    results,
+                    partial: true,
+                };
+            }
+            results.extend(f(item));
+        }
+        ScanResult {
+            results,
+            partial: false,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::budget::{ScanResult, SearchBudget};
 use super::config::Fuse;
 use super::types::{FuseProperty, FuseableSearchResult};
 use crate::types::{Pattern, ScoreResult};
@@ -134,21 +135,74 @@ impl Fuse {
         text: &str,
         list: &[impl Fuseable],
     ) -> Vec<FuseableSearchResult> {
+        self.search_text_in_fuse_list_within(text, list, &SearchBudget::unlimited())
+            .results
+    }
+
+    /// Searches like [`Fuse::search_text_in_fuse_list`], stopping early once
+    /// `budget` runs out.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The search pattern to look for
+    /// * `list` - A slice of objects implementing the `Fuseable` trait
+    /// * `budget` - Deadline and cancel flag checked while scanning
+    ///
+    /// # Returns
+    ///
+    /// The matches among the objects scanned before the budget ran out, sorted
+    /// by relevance, with `partial` set if the scan stopped early.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use fuse_lib::budget::SearchBudget;
+    /// # use fuse_lib::config::Fuse;
+    /// # use fuse_lib::fuseable::Fuseable;
+    /// # use fuse_lib::types::FuseProperty;
+    /// # use std::time::Duration;
+    /// #
+    /// # struct Book<'a> {
+    /// #    title: &'a str,
+    /// # }
+    /// #
+    /// # impl Fuseable for Book<'_> {
+    /// #     fn properties(&self) -> Vec<FuseProperty> {
+    /// #         vec![FuseProperty { value: String::from("title"), weight: 1.0 }]
+    /// #     }
+    /// #
+    /// #     fn lookup(&self, key: &str) -> Option<&str> {
+    /// #         (key == "title").then_some(self.title)
+    /// #     }
+    /// # }
+    /// let books = [Book { title: "Old Man's War fiction" }];
+    ///
+    /// let fuse = Fuse::default();
+    /// let budget = SearchBudget::unlimited().with_timeout(Duration::from_millis(200));
+    /// let scan = fuse.search_text_in_fuse_list_within("man", &books, &budget);
+    /// if scan.partial {
+    ///     // Only some of the books were searched
+    /// }
+    /// ```
+    pub fn search_text_in_fuse_list_within(
+        &self,
+        text: &str,
+        list: &[impl Fuseable],
+        budget: &SearchBudget,
+    ) -> ScanResult<FuseableSearchResult> {
         let pattern = self.create_pattern(text);
 
-        let mut result: Vec<FuseableSearchResult> = list
-            .iter()
-            .enumerate()
-            .filter_map(|(index, item)| self.search_fuseable_item(pattern.as_ref(), index, item))
-            .collect();
+        let mut scan = budget.scan(list.iter().enumerate(), |(index, item)| {
+            self.search_fuseable_item(pattern.as_ref(), index, item)
+        });
 
-        result.sort_unstable_by(|a, b| {
+        scan.results.sort_unstable_by(|a, b| {
             a.score
                 .partial_cmp(&b.score)
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
 
-        result
+        scan
     }
 
     /// Searches for a pattern in the given string.
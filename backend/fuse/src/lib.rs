@@ -3,6 +3,7 @@
 #![deny(clippy::panic)]
 
 pub mod algorithm;
+pub mod budget;
 pub mod config;
 pub mod fuseable;
 pub mod types;
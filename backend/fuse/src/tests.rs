@@ -14,3 +14,78 @@ fn multibyte_chars_indices() {
 
     assert_eq!(&s[r.start..r.end], needle);
 }
+
+mod budget {
+    use crate::budget::SearchBudget;
+    use crate::config::Fuse;
+    use crate::fuseable::Fuseable;
+    use crate::types::FuseProperty;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, Instant};
+
+    struct Title(&'static str);
+
+    impl Fuseable for Title {
+        fn properties(&self) -> Vec<FuseProperty> {
+            vec![FuseProperty::init("title")]
+        }
+
+        fn lookup(&self, key: &str) -> Option<&str> {
+            (key == "title").then_some(self.0)
+        }
+    }
+
+    #[test]
+    fn unlimited_budget_scans_everything() {
+        let scan = SearchBudget::unlimited().scan(0..1000, |n| (n % 2 == 0).then_some(n));
+        assert!(!scan.partial);
+        assert_eq!(scan.results.len(), 500);
+    }
+
+    #[test]
+    fn passed_deadline_stops_scan() {
+        let budget = SearchBudget::unlimited().with_deadline(Instant::now());
+        let scan = budget.scan(0..1000, Some);
+        assert!(scan.partial);
+        assert!(scan.results.is_empty());
+        assert!(
+            !SearchBudget::unlimited()
+                .with_timeout(Duration::MAX)
+                .is_exhausted()
+        );
+    }
+
+    #[test]
+    fn cancel_flag_keeps_results_found_so_far() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let budget = SearchBudget::unlimited().with_cancel_flag(flag.clone());
+
+        let scan = budget.scan(0..1000, |n| {
+            if n == 300 {
+                flag.store(true, Ordering::Relaxed);
+            }
+            Some(n)
+        });
+        assert!(scan.partial);
+        // The flag is seen at the next check, after item 511
+        assert_eq!(scan.results.len(), 512);
+    }
+
+    #[test]
+    fn list_search_reports_partial_results() {
+        let titles = [Title("summer pockets"), Title("kanon")];
+        let fuse = Fuse::default();
+
+        let scan =
+            fuse.search_text_in_fuse_list_within("kanon", &titles, &SearchBudget::unlimited());
+        assert!(!scan.partial);
+        assert_eq!(scan.results[0].index, 1);
+
+        let flag = Arc::new(AtomicBool::new(true));
+        let budget = SearchBudget::unlimited().with_cancel_flag(flag);
+        let scan = fuse.search_text_in_fuse_list_within("kanon", &titles, &budget);
+        assert!(scan.partial);
+        assert!(scan.results.is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+use crate::domain::analytics::entities::search_event::SearchEvent;
+use crate::domain::analytics::services::search_stats_service::{
+    LatencyPercentiles, QueryCount, SearchStatsService,
+};
+use crate::error::AppError;
+use crate::infrastructure::persistence::redis::search_event_store::SearchEventStore;
+use redis::aio::ConnectionManager;
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Where and how long searches are recorded
+#[derive(Debug, Clone)]
+pub struct AnalyticsConfig {
+    /// Redis stream holding the events
+    pub stream_key: String,
+    /// Events older than this are trimmed, and reports cannot look further back
+    pub retention: Duration,
+    /// Most events read for one report, the newest are kept
+    pub max_events: usize,
+}
+
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            stream_key: "analytics:searches".to_string(),
+            retention: Duration::from_secs(7 * 24 * 60 * 60),
+            max_events: 200_000,
+        }
+    }
+}
+
+/// Records served searches and reports on them
+///
+/// Recording happens in the background and never fails a search. Without
+/// Redis nothing is recorded and reports are empty.
+pub struct SearchAnalyticsService {
+    config: AnalyticsConfig,
+    store: Option<SearchEventStore>,
+    stats: SearchStatsService,
+}
+
+impl SearchAnalyticsService {
+    pub fn new(config: AnalyticsConfig, redis: Option<ConnectionManager>) -> Self {
+        let store = redis
+            .map(|con| SearchEventStore::new(con, config.stream_key.clone(), config.retention));
+        Self {
+            config,
+            store,
+            stats: SearchStatsService::new(),
+        }
+    }
+
+    /// Record a search without waiting for Redis
+    pub fn record(&self, endpoint: &str, query: &str, results: usize, latency: Duration, ai: bool) {
+        let Some(store) = self.store.clone() else {
+            return;
+        };
+        let latency_ms = u64::try_from(latency.as_millis()).unwrap_or(u64::MAX);
+        let event = SearchEvent::new(Self::now_millis(), query, endpoint, results, latency_ms, ai);
+        tokio::spawn(async move {
+            if let Err(e) = store.append(&event).await {
+                tracing::warn!("Failed to record search event: {e}");
+            }
+        });
+    }
+
+    /// Most searched queries within `window`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the events cannot be read from Redis
+    pub async fn top_queries(
+        &self,
+        window: Duration,
+        limit: usize,
+    ) -> Result<(usize, Vec<QueryCount>), AppError> {
+        let events = self.events(window).await?;
+        Ok((events.len(), self.stats.top_queries(&events, limit)))
+    }
+
+    /// Most searched queries without results within `window`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the events cannot be read from Redis
+    pub async fn top_zero_result_queries(
+        &self,
+        window: Duration,
+        limit: usize,
+    ) -> Result<(usize, Vec<QueryCount>), AppError> {
+        let events = self.events(window).await?;
+        Ok((
+            events.len(),
+            self.stats.top_zero_result_queries(&events, limit),
+        ))
+    }
+
+    /// Latency percentiles per endpoint within `window`, plus `all` endpoints
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the events cannot be read from Redis
+    pub async fn latency(
+        &self,
+        window: Duration,
+    ) -> Result<BTreeMap<String, LatencyPercentiles>, AppError> {
+        let events = self.events(window).await?;
+
+        let mut by_endpoint: BTreeMap<String, Vec<SearchEvent>> = BTreeMap::new();
+        for event in &events {
+            by_endpoint
+                .entry(event.endpoint.clone())
+                .or_default()
+                .push(event.clone());
+        }
+
+        let mut latency: BTreeMap<String, LatencyPercentiles> = by_endpoint
+            .into_iter()
+            .map(|(endpoint, events)| (endpoint, self.stats.latency_percentiles(&events)))
+            .collect();
+        latency.insert("all".to_string(), self.stats.latency_percentiles(&events));
+        Ok(latency)
+    }
+
+    /// Longest window a report can cover
+    pub fn retention(&self) -> Duration {
+        self.config.retention
+    }
+
+    async fn events(&self, window: Duration) -> Result<Vec<SearchEvent>, AppError> {
+        let Some(store) = &self.store else {
+            return Ok(Vec::new());
+        };
+        let window = window.min(self.config.retention);
+        let to = Self::now_millis();
+        let from = to.saturating_sub(u64::try_from(window.as_millis()).unwrap_or(u64::MAX));
+        Ok(store.range(from, to, self.config.max_events).await?)
+    }
+
+    fn now_millis() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+    }
+}
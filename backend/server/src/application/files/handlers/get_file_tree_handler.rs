@@ -1,5 +1,7 @@
 use crate::application::files::queries::get_file_tree_query::GetFileTreeQuery;
-use crate::domain::files::entities::tree_node::{NavigationResult, TreeNode};
+use crate::application::shared::dto::common::Page;
+use crate::domain::files::entities::tree_node::{NavigationResult, NodeType, TreeNode};
+use crate::domain::files::services::directory_listing_service::DirectoryListingService;
 use crate::error::AppError;
 
 /// Handler for getting file tree nodes
@@ -29,4 +31,15 @@ impl GetFileTreeHandler {
             result => Ok(result),
         }
     }
+
+    /// The requested page of the entries of `folder`, sorted as the query asks
+    pub fn list<'a>(
+        &self,
+        query: &GetFileTreeQuery,
+        folder: &'a TreeNode,
+    ) -> Page<(&'a String, &'a NodeType)> {
+        let order = query.order.unwrap_or(query.sort.default_order());
+        let entries = DirectoryListingService::sorted(folder, query.sort, order);
+        Page::paginate(entries, query.offset, query.limit)
+    }
 }
@@ -1,3 +1,4 @@
+use crate::domain::files::value_objects::listing_order::{ListingSort, SortOrder};
 use serde::{Deserialize, Serialize};
 
 /// Query for getting a file or directory node by path
@@ -5,16 +6,40 @@ use serde::{Deserialize, Serialize};
 pub struct GetFileTreeQuery {
     /// File path to navigate to. Empty string means root.
     pub path: String,
+    /// Key a folder listing is sorted by
+    pub sort: ListingSort,
+    /// Direction of the listing, `None` for the default of `sort`
+    pub order: Option<SortOrder>,
+    /// Number of entries to skip before the returned page
+    pub offset: usize,
+    /// Maximum number of entries to return, `None` for all of them
+    pub limit: Option<usize>,
 }
 
 impl GetFileTreeQuery {
     pub fn new(path: String) -> Self {
-        Self { path }
+        Self {
+            path,
+            sort: ListingSort::default(),
+            order: None,
+            offset: 0,
+            limit: None,
+        }
     }
 
     pub fn root() -> Self {
-        Self {
-            path: String::new(),
-        }
+        Self::new(String::new())
+    }
+
+    pub fn with_sort(mut self, sort: ListingSort, order: Option<SortOrder>) -> Self {
+        self.sort = sort;
+        self.order = order;
+        self
+    }
+
+    pub fn with_page(mut self, offset: usize, limit: Option<usize>) -> Self {
+        self.offset = offset;
+        self.limit = limit;
+        self
     }
 }
@@ -1,9 +1,57 @@
-use crate::domain::files::entities::tree_node::{NodeType, TreeNode};
+use crate::domain::files::entities::tree_node::{NavigationResult, NodeType, TreeNode};
+use crate::error::AppError;
+
+/// Bucket path of the galgame0 subtree that is mounted as `galgame0` in the combined tree
+pub const GALGAME0_ROOT: &str = "合集系列/浮士德galgame游戏合集";
 
 /// Application service for file tree operations
 pub struct FileTreeService;
 
 impl FileTreeService {
+    /// Map a bucket file path to its path in the combined frontend tree
+    ///
+    /// Mirrors the link building in the frontend's `AnswerItem`:
+    /// galgame0 files drop the `合集系列/浮士德galgame游戏合集` prefix and
+    /// live under `galgame0/`, everything else lives under `shinnku/`.
+    pub fn tree_path(file_path: &str) -> String {
+        match file_path
+            .strip_prefix(GALGAME0_ROOT)
+            .and_then(|rest| rest.strip_prefix('/'))
+        {
+            Some(rest) => format!("galgame0/{rest}"),
+            None => format!("shinnku/{file_path}"),
+        }
+    }
+
+    /// Subtrees of the combined tree paired with the bucket path they are mounted at
+    pub fn bucket_roots(tree: &TreeNode) -> Vec<(&'static str, &TreeNode)> {
+        [("shinnku", ""), ("galgame0", GALGAME0_ROOT)]
+            .into_iter()
+            .filter_map(|(name, prefix)| match tree.as_ref().get(name) {
+                Some(NodeType::Node(node)) => Some((prefix, node)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Resolve a search scope to the canonical path of a folder in the combined tree
+    ///
+    /// The scope goes through the same decoding as `/files/{path}`. The tree
+    /// root resolves to `None`, meaning no restriction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scope is not a folder of the tree
+    pub fn resolve_scope(tree: &TreeNode, scope: &str) -> Result<Option<String>, AppError> {
+        match tree.navigate_path(scope) {
+            NavigationResult::Folder(_) => {
+                let segments = TreeNode::path_segments(scope);
+                Ok((!segments.is_empty()).then(|| segments.join("/")))
+            }
+            _ => Err(AppError::NotFound(format!("scope '{scope}' not found"))),
+        }
+    }
+
     /// Construct the combined tree used by the frontend
     ///
     /// This mirrors the following TypeScript snippet:
@@ -0,0 +1,3 @@
+pub mod related_hit;
+pub mod search_hit;
+pub mod search_response;
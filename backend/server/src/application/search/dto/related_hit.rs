@@ -0,0 +1,19 @@
+use crate::domain::search::entities::search_item::SearchItem;
+use crate::domain::search::value_objects::similarity::Similarity;
+use serde::{Deserialize, Serialize};
+
+/// A file similar to the requested one, with the signals it shares
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RelatedHit {
+    #[serde(flatten)]
+    pub item: SearchItem,
+    pub similarity: Similarity,
+}
+
+/// Files similar to the requested one, best first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelatedResults {
+    pub hits: Vec<RelatedHit>,
+    /// `true` if the scan stopped early, so `hits` only ranks part of the index
+    pub partial: bool,
+}
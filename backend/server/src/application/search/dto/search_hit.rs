@@ -0,0 +1,61 @@
+use crate::domain::files::entities::file_info::FileInfo;
+use crate::domain::search::entities::fused_result::FusedResult;
+use crate::domain::search::entities::search_item::SearchItem;
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
+use serde::{Deserialize, Serialize};
+
+/// One entry of a search response
+///
+/// Serializes as the matched item, plus a `group` object when several files
+/// of the same release were folded into this hit.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SearchHit {
+    /// Best-ranked file of the hit
+    #[serde(flatten)]
+    pub item: SearchItem,
+    /// Parts, volumes and versions of the same release, absent for single files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<ReleaseGroup>,
+    /// Query variant of a combined search that ranked this hit, absent when
+    /// that was the first, raw query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_query: Option<String>,
+    /// Paths of every copy of the file, present when duplicates were collapsed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locations: Option<Vec<String>>,
+}
+
+/// Files sharing one release key, folded into a single hit
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReleaseGroup {
+    /// Normalized release key shared by all members
+    pub key: String,
+    /// Member files sorted by path, so parts come out in order
+    pub members: Vec<FileInfo>,
+    /// Combined size of all members in bytes
+    pub total_size: u64,
+    /// Most recent upload time among the members in milliseconds
+    pub newest_timestamp: u64,
+}
+
+impl From<SearchItem> for SearchHit {
+    fn from(item: SearchItem) -> Self {
+        Self {
+            item,
+            group: None,
+            matched_query: None,
+            locations: None,
+        }
+    }
+}
+
+impl SearchHit {
+    /// Hit of a fused search over `queries`, see [`SearchHit::matched_query`]
+    pub fn from_fused(result: FusedResult, queries: &[WeightedQuery]) -> Self {
+        let matched_query = (result.query > 0).then(|| queries[result.query].text.clone());
+        Self {
+            matched_query,
+            ..Self::from(result.item)
+        }
+    }
+}
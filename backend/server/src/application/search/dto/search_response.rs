@@ -0,0 +1,61 @@
+use crate::application::search::dto::search_hit::SearchHit;
+use crate::application::search::services::facet_service::{FacetService, SearchFacets};
+use crate::application::search::services::result_grouping_service::ResultGroupingService;
+use crate::application::shared::dto::common::Page;
+use serde::{Deserialize, Serialize};
+
+/// Response envelope returned by the search handlers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResponse {
+    /// The requested page of results
+    #[serde(flatten)]
+    pub page: Page<SearchHit>,
+    /// Facet counts over the full match set, not just this page
+    pub facets: SearchFacets,
+    /// Alternative queries offered when nothing or only weak matches were found
+    pub did_you_mean: Vec<String>,
+    /// The search ran out of time or was cancelled, so the results are the
+    /// best found in the part of the index scanned
+    #[serde(default)]
+    pub partial: bool,
+}
+
+impl SearchResponse {
+    /// Build the response from the full ordered match set
+    ///
+    /// With `group` set, parts and versions of one release become a single
+    /// hit and the total counts releases; facets always count files, never
+    /// folder hits.
+    pub fn from_results(
+        results: Vec<SearchHit>,
+        offset: usize,
+        limit: Option<usize>,
+        group: bool,
+    ) -> Self {
+        let facets = FacetService::count(results.iter().map(|hit| &hit.item));
+        let hits = if group {
+            ResultGroupingService::group(results)
+        } else {
+            results
+        };
+        Self {
+            page: Page::paginate(hits, offset, limit),
+            facets,
+            did_you_mean: Vec::new(),
+            partial: false,
+        }
+    }
+
+    /// The page at `offset` of a response built with every result on one page
+    ///
+    /// Facets, suggestions and the partial flag cover the full match set
+    /// already, so they carry over unchanged.
+    pub fn page(&self, offset: usize, limit: Option<usize>) -> Self {
+        Self {
+            page: self.page.slice(offset, limit),
+            facets: self.facets.clone(),
+            did_you_mean: self.did_you_mean.clone(),
+            partial: self.partial,
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::application::search::dto::search_hit::SearchHit;
+use crate::application::search::dto::search_response::SearchResponse;
 use crate::application::search::queries::combined_search_query::CombinedSearchQuery;
 use crate::domain::search::entities::search_item::SearchList;
 use crate::domain::search::repositories::fuzzy_search_repository::FuzzySearchRepository;
@@ -12,9 +14,23 @@ impl<R: FuzzySearchRepository> CombinedSearchHandler<R> {
         Self { repository }
     }
 
-    /// Execute the combined search query
-    pub fn handle(&self, query: &CombinedSearchQuery, search_index: &SearchList) -> SearchList {
-        self.repository
-            .combined_search(&query.query1, &query.query2, query.limit, search_index)
+    /// Execute the combined search query and return the requested page with facets
+    pub fn handle(&self, query: &CombinedSearchQuery, search_index: &SearchList) -> SearchResponse {
+        // Fetch the full match set so the page can report an accurate total
+        let results = self
+            .repository
+            .fused_search(
+                &query.queries,
+                query.strategy,
+                search_index.len(),
+                search_index,
+            )
+            .into_iter()
+            .map(|result| SearchHit::from_fused(result, &query.queries))
+            .collect();
+        let mut response =
+            SearchResponse::from_results(results, query.offset, Some(query.limit), query.group);
+        response.partial = self.repository.is_partial();
+        response
     }
 }
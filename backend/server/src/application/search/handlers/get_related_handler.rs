@@ -0,0 +1,69 @@
+use crate::application::search::dto::related_hit::{RelatedHit, RelatedResults};
+use crate::application::search::queries::get_related_query::GetRelatedQuery;
+use crate::domain::search::entities::search_item::SearchList;
+use crate::domain::search::services::related_items_service::RelatedItemsService;
+use crate::domain::search::value_objects::cancellation::Cancellation;
+use crate::domain::search::value_objects::item_kind::ItemKind;
+use crate::error::AppError;
+use std::time::{Duration, Instant};
+
+/// Handler for "more like this" recommendations of one file
+#[derive(Default)]
+pub struct GetRelatedHandler {
+    cancellation: Cancellation,
+    time_budget: Option<Duration>,
+}
+
+impl GetRelatedHandler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop scanning once `cancellation` is set, keeping what was found so far
+    pub fn with_cancellation(mut self, cancellation: Cancellation) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Stop scanning `budget` after the query starts, keeping what was found so far
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Execute the related query against the search index
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` is not a file of the index
+    pub fn handle(
+        &self,
+        query: &GetRelatedQuery,
+        search_index: &SearchList,
+    ) -> Result<RelatedResults, AppError> {
+        let deadline = self
+            .time_budget
+            .and_then(|budget| Instant::now().checked_add(budget));
+        let target = search_index
+            .iter()
+            .find(|item| item.kind == ItemKind::File && *item.info.file_path == *query.path)
+            .ok_or_else(|| AppError::NotFound(format!("file '{}' not found", query.path)))?;
+
+        let should_stop = || {
+            self.cancellation.is_cancelled()
+                || deadline.is_some_and(|deadline| Instant::now() >= deadline)
+        };
+        let (related, partial) =
+            RelatedItemsService::related(target, search_index, query.limit, should_stop);
+        Ok(RelatedResults {
+            hits: related
+                .into_iter()
+                .map(|(item, similarity)| RelatedHit {
+                    item: item.clone(),
+                    similarity,
+                })
+                .collect(),
+            partial,
+        })
+    }
+}
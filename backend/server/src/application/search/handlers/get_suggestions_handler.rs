@@ -0,0 +1,17 @@
+use crate::application::search::queries::get_suggestions_query::GetSuggestionsQuery;
+use crate::domain::search::entities::suggest_index::{SuggestIndex, Suggestion};
+
+/// Handler for search-as-you-type suggestions
+#[derive(Default)]
+pub struct GetSuggestionsHandler;
+
+impl GetSuggestionsHandler {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Execute the suggest query against the prefix index
+    pub fn handle(&self, query: &GetSuggestionsQuery, index: &SuggestIndex) -> Vec<Suggestion> {
+        index.suggest(&query.prefix, query.limit)
+    }
+}
@@ -1,2 +1,4 @@
 pub mod combined_search_handler;
+pub mod get_related_handler;
+pub mod get_suggestions_handler;
 pub mod search_files_handler;
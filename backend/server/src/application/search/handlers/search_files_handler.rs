@@ -1,29 +1,215 @@
+use crate::application::search::dto::search_hit::SearchHit;
+use crate::application::search::dto::search_response::SearchResponse;
 use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::application::search::services::duplicate_collapse_service::DuplicateCollapseService;
+use crate::domain::search::entities::duplicate_index::DuplicateIndex;
+use crate::domain::search::entities::popularity_snapshot::PopularitySnapshot;
+use crate::domain::search::entities::query_vocabulary::QueryVocabulary;
 use crate::domain::search::entities::search_item::SearchList;
 use crate::domain::search::repositories::fuzzy_search_repository::FuzzySearchRepository;
+use crate::domain::search::services::popularity_service::{PopularityService, Ranked};
+use crate::domain::search::value_objects::fusion_strategy::FusionStrategy;
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
+use std::sync::Arc;
+
+/// Best-match score above which results count as weak and "did you mean" kicks in
+const WEAK_MATCH_SCORE: f64 = 0.3;
+/// Number of alternative queries offered
+const DID_YOU_MEAN_LIMIT: usize = 3;
+
+/// Popularity blended into the ranking of queries that ask for it
+struct PopularityRanking {
+    snapshot: Arc<PopularitySnapshot>,
+    weight: f64,
+    window: usize,
+    /// Clicks made from the searched query and their extra weight
+    query: Option<(Arc<PopularitySnapshot>, f64)>,
+}
 
 /// Handler for file search operations
 pub struct SearchFilesHandler<R: FuzzySearchRepository> {
     repository: R,
+    vocabulary: Option<Arc<QueryVocabulary>>,
+    popularity: Option<PopularityRanking>,
+    duplicates: Option<Arc<DuplicateIndex>>,
 }
 
 impl<R: FuzzySearchRepository> SearchFilesHandler<R> {
     pub fn new(repository: R) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            vocabulary: None,
+            popularity: None,
+            duplicates: None,
+        }
     }
 
-    /// Execute the search files query
-    ///
-    /// # Errors
+    /// Re-rank the `window` best results of queries with `popularity` set by
+    /// download popularity, see [`PopularityService::rerank`]
+    pub fn with_popularity(
+        mut self,
+        snapshot: Arc<PopularitySnapshot>,
+        weight: f64,
+        window: usize,
+    ) -> Self {
+        self.popularity = Some(PopularityRanking {
+            snapshot,
+            weight,
+            window,
+            query: None,
+        });
+        self
+    }
+
+    /// Count clicks made from the searched query `query_weight` times extra in
+    /// the popularity ranking, see [`PopularitySnapshot::blend`]
     ///
-    /// Returns an error if the search operation fails
-    pub fn handle(&self, query: &SearchFilesQuery, search_index: &SearchList) -> SearchList {
-        let results = self.repository.search(&query.query, search_index);
+    /// Has no effect without [`Self::with_popularity`].
+    pub fn with_query_popularity(
+        mut self,
+        snapshot: Arc<PopularitySnapshot>,
+        query_weight: f64,
+    ) -> Self {
+        if let Some(popularity) = &mut self.popularity {
+            popularity.query = Some((snapshot, query_weight));
+        }
+        self
+    }
+
+    /// Offer "did you mean" rewrites from `vocabulary` on zero or weak results
+    pub fn with_vocabulary(mut self, vocabulary: Arc<QueryVocabulary>) -> Self {
+        self.vocabulary = Some(vocabulary);
+        self
+    }
+
+    /// Collapse copies of one file from `duplicates` for queries with `dedupe` set
+    pub fn with_duplicates(mut self, duplicates: Arc<DuplicateIndex>) -> Self {
+        self.duplicates = Some(duplicates);
+        self
+    }
+
+    /// Execute the search files query and return the requested page with facets
+    pub fn handle(&self, query: &SearchFilesQuery, search_index: &SearchList) -> SearchResponse {
+        let filters = &query.filters;
+
+        if query.query.is_empty() && !filters.is_empty() {
+            // Pure predicate query: no fuzzy ranking, keep index order
+            let results = search_index
+                .iter()
+                .filter(|item| filters.matches(item))
+                .cloned()
+                .map(SearchHit::from)
+                .collect();
+            return self.respond(query, results);
+        }
 
-        if let Some(limit) = query.limit {
-            results.into_iter().take(limit).collect()
+        // Filtering first keeps scoped and filtered searches to their candidates
+        let filtered: SearchList;
+        let candidates = if filters.is_empty() {
+            search_index
         } else {
-            results
+            filtered = search_index
+                .iter()
+                .filter(|item| filters.matches(item))
+                .cloned()
+                .collect();
+            &filtered
+        };
+
+        if !query.aliases.is_empty() {
+            return self.handle_with_aliases(query, candidates);
         }
+
+        let mut scored = self.repository.search_scored(&query.query, candidates);
+
+        let weak = scored
+            .first()
+            .is_none_or(|best| best.score.value() > WEAK_MATCH_SCORE);
+        let did_you_mean = match &self.vocabulary {
+            Some(vocabulary) if weak => {
+                vocabulary.suggest_queries(&query.query, DID_YOU_MEAN_LIMIT)
+            }
+            _ => Vec::new(),
+        };
+
+        // Weak-match detection above looks at relevance alone
+        self.rerank_by_popularity(query, &mut scored);
+
+        let results = scored
+            .into_iter()
+            .map(|result| SearchHit::from(result.item))
+            .collect();
+        let mut response = self.respond(query, results);
+        response.did_you_mean = did_you_mean;
+        response.partial = self.repository.is_partial();
+        response
+    }
+
+    /// Search the query and its aliases together, each item ranked by its
+    /// best weighted score over all variants
+    fn handle_with_aliases(
+        &self,
+        query: &SearchFilesQuery,
+        candidates: &SearchList,
+    ) -> SearchResponse {
+        let variants: Vec<WeightedQuery> =
+            std::iter::once(WeightedQuery::unweighted(query.query.clone()))
+                .chain(query.aliases.iter().cloned())
+                .collect();
+
+        let mut fused = self.repository.fused_search(
+            &variants,
+            FusionStrategy::Min,
+            candidates.len(),
+            candidates,
+        );
+        // Min fusion keeps fuse scores, so popularity blends in as for plain queries
+        self.rerank_by_popularity(query, &mut fused);
+
+        let results: Vec<SearchHit> = fused
+            .into_iter()
+            .map(|result| SearchHit::from_fused(result, &variants))
+            .collect();
+
+        let did_you_mean = match &self.vocabulary {
+            Some(vocabulary) if results.is_empty() => {
+                vocabulary.suggest_queries(&query.query, DID_YOU_MEAN_LIMIT)
+            }
+            _ => Vec::new(),
+        };
+        let mut response = self.respond(query, results);
+        response.did_you_mean = did_you_mean;
+        response.partial = self.repository.is_partial();
+        response
+    }
+
+    /// Blend popularity into the ranking of queries that ask for it
+    fn rerank_by_popularity<T: Ranked>(&self, query: &SearchFilesQuery, results: &mut [T]) {
+        if let Some(popularity) = &self.popularity
+            && query.popularity.is_some()
+        {
+            let blended = popularity.query.as_ref().map(|(clicks, query_weight)| {
+                let head = results.iter().take(popularity.window);
+                let paths = head.map(|result| &*result.item().info.file_path);
+                popularity.snapshot.blend(clicks, *query_weight, paths)
+            });
+            PopularityService::rerank(
+                results,
+                blended.as_ref().unwrap_or(&popularity.snapshot),
+                popularity.weight,
+                popularity.window,
+            );
+        }
+    }
+
+    /// Page the ranked results, collapsing duplicates first if the query asks for it
+    fn respond(&self, query: &SearchFilesQuery, results: Vec<SearchHit>) -> SearchResponse {
+        let results = match &self.duplicates {
+            Some(duplicates) if query.dedupe => {
+                DuplicateCollapseService::collapse(results, duplicates)
+            }
+            _ => results,
+        };
+        SearchResponse::from_results(results, query.offset, query.limit, query.group)
     }
 }
@@ -1,22 +1,90 @@
+use crate::domain::search::value_objects::fusion_strategy::FusionStrategy;
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
+use crate::error::AppError;
 use serde::{Deserialize, Serialize};
 
-/// Query for searching files using two combined query strings
+/// Upper bound of query variants in one combined search, each one is a full index scan
+pub const MAX_COMBINED_QUERIES: usize = 16;
+
+/// Query for searching files using several weighted query strings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CombinedSearchQuery {
-    /// First search query string
-    pub query1: String,
-    /// Second search query string
-    pub query2: String,
+    /// Query variants with their weights
+    pub queries: Vec<WeightedQuery>,
+    /// How the rankings of the variants are merged
+    pub strategy: FusionStrategy,
     /// Maximum number of results to return
     pub limit: usize,
+    /// Number of results to skip before the returned page
+    pub offset: usize,
+    /// Fold parts and versions of one release into a single hit
+    pub group: bool,
+    /// Label of the fuzzy matching configuration when it is not the default,
+    /// so searches with different tuning never share a cache entry
+    pub tuning: Option<String>,
 }
 
 impl CombinedSearchQuery {
-    pub fn new(query1: String, query2: String, limit: usize) -> Self {
+    pub fn new(queries: Vec<WeightedQuery>, limit: usize, offset: usize) -> Self {
         Self {
-            query1,
-            query2,
+            queries,
+            strategy: FusionStrategy::default(),
             limit,
+            offset,
+            group: false,
+            tuning: None,
+        }
+    }
+
+    pub fn with_strategy(mut self, strategy: FusionStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn with_grouping(mut self, group: bool) -> Self {
+        self.group = group;
+        self
+    }
+
+    pub fn with_tuning(mut self, tuning: Option<String>) -> Self {
+        self.tuning = tuning;
+        self
+    }
+
+    /// The same search with every result on a single page
+    pub fn unpaged(mut self) -> Self {
+        self.limit = usize::MAX;
+        self.offset = 0;
+        self
+    }
+
+    /// Check the variants before running the search
+    ///
+    /// # Errors
+    ///
+    /// Returns a bad request error if there are no variants, more than
+    /// [`MAX_COMBINED_QUERIES`], or a weight that is not a positive number
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.queries.is_empty() {
+            return Err(AppError::BadRequest(
+                "at least one query is required".into(),
+            ));
+        }
+        if self.queries.len() > MAX_COMBINED_QUERIES {
+            return Err(AppError::BadRequest(format!(
+                "at most {MAX_COMBINED_QUERIES} queries are allowed"
+            )));
+        }
+        if let Some(query) = self
+            .queries
+            .iter()
+            .find(|query| !(query.weight.is_finite() && query.weight > 0.0))
+        {
+            return Err(AppError::BadRequest(format!(
+                "weight of query '{}' must be a positive number",
+                query.text
+            )));
         }
+        Ok(())
     }
 }
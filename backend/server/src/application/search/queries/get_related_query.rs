@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Query for files similar to one file of the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetRelatedQuery {
+    /// Bucket path of the file, as in `info.file_path` of a search hit
+    pub path: String,
+    /// Maximum number of related files to return
+    pub limit: usize,
+}
+
+impl GetRelatedQuery {
+    pub fn new(path: String, limit: usize) -> Self {
+        Self { path, limit }
+    }
+}
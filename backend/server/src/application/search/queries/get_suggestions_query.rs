@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Query for title and folder completions of a partially typed search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetSuggestionsQuery {
+    /// What the user has typed so far
+    pub prefix: String,
+    /// Maximum number of suggestions to return
+    pub limit: usize,
+}
+
+impl GetSuggestionsQuery {
+    pub fn new(prefix: String, limit: usize) -> Self {
+        Self { prefix, limit }
+    }
+}
@@ -1,2 +1,6 @@
 pub mod combined_search_query;
+pub mod get_related_query;
+pub mod get_suggestions_query;
 pub mod search_files_query;
+pub mod search_filters;
+pub mod validated_query;
@@ -1,3 +1,5 @@
+use crate::application::search::queries::search_filters::SearchFilters;
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
 use serde::{Deserialize, Serialize};
 
 /// Query for searching files using fuzzy search
@@ -7,10 +9,73 @@ pub struct SearchFilesQuery {
     pub query: String,
     /// Maximum number of results to return
     pub limit: Option<usize>,
+    /// Number of results to skip before the returned page
+    pub offset: usize,
+    /// Field predicates every result must satisfy
+    pub filters: SearchFilters,
+    /// Fold parts and versions of one release into a single hit
+    pub group: bool,
+    /// Fold copies of the same file stored at several paths into a single hit
+    pub dedupe: bool,
+    /// Alternative spellings of `query` from the alias dictionary, searched alongside it
+    pub aliases: Vec<WeightedQuery>,
+    /// Generation of the popularity snapshot blended into the ranking, `None`
+    /// to rank by relevance alone
+    pub popularity: Option<u64>,
+    /// Label of the fuzzy matching configuration when it is not the default,
+    /// so searches with different tuning never share a cache entry
+    pub tuning: Option<String>,
 }
 
 impl SearchFilesQuery {
-    pub fn new(query: String, limit: Option<usize>) -> Self {
-        Self { query, limit }
+    pub fn new(query: String, limit: Option<usize>, offset: usize) -> Self {
+        Self {
+            query,
+            limit,
+            offset,
+            filters: SearchFilters::default(),
+            group: false,
+            dedupe: false,
+            aliases: Vec::new(),
+            popularity: None,
+            tuning: None,
+        }
+    }
+
+    pub fn with_filters(mut self, filters: SearchFilters) -> Self {
+        self.filters = filters;
+        self
+    }
+
+    pub fn with_grouping(mut self, group: bool) -> Self {
+        self.group = group;
+        self
+    }
+
+    pub fn with_dedupe(mut self, dedupe: bool) -> Self {
+        self.dedupe = dedupe;
+        self
+    }
+
+    pub fn with_aliases(mut self, aliases: Vec<WeightedQuery>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    pub fn with_popularity(mut self, generation: u64) -> Self {
+        self.popularity = Some(generation);
+        self
+    }
+
+    pub fn with_tuning(mut self, tuning: Option<String>) -> Self {
+        self.tuning = tuning;
+        self
+    }
+
+    /// The same search with every result on a single page
+    pub fn unpaged(mut self) -> Self {
+        self.limit = None;
+        self.offset = 0;
+        self
     }
 }
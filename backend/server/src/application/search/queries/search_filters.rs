@@ -0,0 +1,85 @@
+use crate::application::files::services::file_tree_service::FileTreeService;
+use crate::application::search::services::facet_service::FacetService;
+use crate::domain::search::entities::search_item::SearchItem;
+use crate::domain::search::value_objects::item_kind::ItemKind;
+use serde::{Deserialize, Serialize};
+
+/// Field predicates narrowing a search, produced by the query parser
+///
+/// Values within one field are alternatives (`ext:rar ext:7z` keeps either),
+/// different fields must all match.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchFilters {
+    /// Allowed buckets (`shinnku`, `galgame0`)
+    pub buckets: Vec<String>,
+    /// Allowed lowercase file extensions; folders have none
+    pub extensions: Vec<String>,
+    /// Allowed game types (`生肉`, `熟肉`, `手机`)
+    pub game_types: Vec<String>,
+    /// Minimum file size in bytes, inclusive; folders never match a size bound
+    pub min_size: Option<u64>,
+    /// Maximum file size in bytes, inclusive
+    pub max_size: Option<u64>,
+    /// Earliest upload time in milliseconds, inclusive
+    pub uploaded_after: Option<u64>,
+    /// Latest upload time in milliseconds, exclusive
+    pub uploaded_before: Option<u64>,
+    /// Combined-tree folder results must lie under, e.g. `shinnku/zd/1001-1500`
+    pub scope: Option<String>,
+}
+
+impl SearchFilters {
+    /// Whether no predicate is set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the item satisfies every predicate
+    pub fn matches(&self, item: &SearchItem) -> bool {
+        let info = &item.info;
+
+        if !self.buckets.is_empty() || self.scope.is_some() {
+            let tree_path = FileTreeService::tree_path(&info.file_path);
+            let bucket = tree_path.split('/').next().unwrap_or_default();
+            if !self.buckets.is_empty() && !self.buckets.iter().any(|b| b == bucket) {
+                return false;
+            }
+            if let Some(scope) = &self.scope
+                && !tree_path
+                    .strip_prefix(scope.as_str())
+                    .is_some_and(|rest| rest.starts_with('/'))
+            {
+                return false;
+            }
+        }
+
+        let is_folder = item.kind == ItemKind::Folder;
+
+        if !self.extensions.is_empty() {
+            match FacetService::extension(&info.file_path).filter(|_| !is_folder) {
+                Some(ext) if self.extensions.contains(&ext) => {}
+                _ => return false,
+            }
+        }
+
+        if !self.game_types.is_empty() {
+            let game_type = FacetService::game_type(&info.file_path);
+            if !self.game_types.iter().any(|t| t == game_type) {
+                return false;
+            }
+        }
+
+        if is_folder && (self.min_size.is_some() || self.max_size.is_some()) {
+            return false;
+        }
+
+        self.min_size.is_none_or(|min| info.file_size >= min)
+            && self.max_size.is_none_or(|max| info.file_size <= max)
+            && self
+                .uploaded_after
+                .is_none_or(|after| info.upload_timestamp >= after)
+            && self
+                .uploaded_before
+                .is_none_or(|before| info.upload_timestamp < before)
+    }
+}
@@ -0,0 +1,112 @@
+use crate::error::AppError;
+use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
+
+/// Longest accepted query, in characters after normalization
+pub const MAX_QUERY_CHARS: usize = 256;
+/// Largest accepted page size
+pub const MAX_LIMIT: usize = 1000;
+/// Page size when the request gives none
+pub const DEFAULT_LIMIT: usize = 100;
+
+/// Why a search input was rejected
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum QueryViolation {
+    #[error("query must not be empty")]
+    Empty,
+    #[error("query must be at most {MAX_QUERY_CHARS} characters")]
+    TooLong,
+    /// Names the request parameter that carried the limit
+    #[error("`{0}` must be at most {MAX_LIMIT}")]
+    LimitTooLarge(&'static str),
+    #[error("unknown preset '{0}'")]
+    UnknownPreset(String),
+}
+
+impl QueryViolation {
+    /// Machine-readable code reported next to the message
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Empty => "query_empty",
+            Self::TooLong => "query_too_long",
+            Self::LimitTooLarge(_) => "limit_too_large",
+            Self::UnknownPreset(_) => "unknown_preset",
+        }
+    }
+}
+
+impl From<QueryViolation> for AppError {
+    fn from(violation: QueryViolation) -> Self {
+        AppError::InvalidInput {
+            code: violation.code(),
+            message: violation.to_string(),
+        }
+    }
+}
+
+/// Search text and page size that passed validation
+///
+/// The text is NFKC-normalized, so full-width letters and half-width kana
+/// match their usual forms, with control characters removed and whitespace
+/// collapsed to single spaces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedQuery {
+    text: String,
+    limit: Option<usize>,
+}
+
+impl ValidatedQuery {
+    /// Normalize and check a raw query and its requested page size
+    ///
+    /// # Errors
+    ///
+    /// Returns a violation if the normalized text is empty or longer than
+    /// [`MAX_QUERY_CHARS`], or the limit, sent as `n`, is above [`MAX_LIMIT`]
+    pub fn new(raw: &str, limit: Option<usize>) -> Result<Self, QueryViolation> {
+        let text = Self::normalize(raw);
+        if text.is_empty() {
+            return Err(QueryViolation::Empty);
+        }
+        if text.chars().count() > MAX_QUERY_CHARS {
+            return Err(QueryViolation::TooLong);
+        }
+        Ok(Self {
+            text,
+            limit: Self::check_limit(limit, "n")?,
+        })
+    }
+
+    /// Check a requested page size on its own, for inputs with several queries
+    /// or none; `param` is the name the request sent it under
+    ///
+    /// # Errors
+    ///
+    /// Returns a violation if the limit is above [`MAX_LIMIT`]
+    pub fn check_limit(
+        limit: Option<usize>,
+        param: &'static str,
+    ) -> Result<Option<usize>, QueryViolation> {
+        match limit {
+            Some(limit) if limit > MAX_LIMIT => Err(QueryViolation::LimitTooLarge(param)),
+            limit => Ok(limit),
+        }
+    }
+
+    /// NFKC-normalize `raw`, drop control characters and collapse whitespace
+    pub fn normalize(raw: &str) -> String {
+        let text: String = raw
+            .nfkc()
+            .filter(|c| c.is_whitespace() || !c.is_control())
+            .collect();
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Requested page size, [`DEFAULT_LIMIT`] if none was given
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_LIMIT)
+    }
+}
@@ -0,0 +1,79 @@
+use crate::domain::search::entities::alias_dictionary::AliasDictionary;
+use crate::error::AppError;
+use crate::infrastructure::persistence::config::alias_config::load_alias_file;
+use crate::infrastructure::persistence::redis::alias_overlay::load_alias_overlay;
+use redis::aio::ConnectionManager;
+use std::sync::{Arc, RwLock};
+
+/// Where the alias dictionary is loaded from
+#[derive(Debug, Clone)]
+pub struct AliasConfig {
+    /// TOML or JSON file read at startup and on every reload
+    pub path: String,
+    /// Redis hash overlaid on the file, editable at runtime
+    pub redis_key: String,
+}
+
+impl Default for AliasConfig {
+    fn default() -> Self {
+        Self {
+            path: "aliases.toml".to_string(),
+            redis_key: "search:aliases".to_string(),
+        }
+    }
+}
+
+/// Holds the alias dictionary used for query expansion and swaps it on reload
+pub struct AliasService {
+    config: AliasConfig,
+    redis: Option<ConnectionManager>,
+    dictionary: RwLock<Arc<AliasDictionary>>,
+}
+
+impl AliasService {
+    /// Load the dictionary, starting empty if it cannot be loaded
+    pub async fn load(config: AliasConfig, redis: Option<ConnectionManager>) -> Self {
+        let service = Self {
+            config,
+            redis,
+            dictionary: RwLock::new(Arc::new(AliasDictionary::default())),
+        };
+        if let Err(e) = service.reload().await {
+            tracing::error!("Failed to load aliases, starting without them: {e}");
+        }
+        service
+    }
+
+    /// Re-read the file and the Redis overlay and swap in the new dictionary
+    ///
+    /// On failure the current dictionary stays in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The alias file cannot be read or parsed
+    /// - The Redis overlay cannot be read
+    pub async fn reload(&self) -> Result<Arc<AliasDictionary>, AppError> {
+        let mut dictionary = AliasDictionary::new(load_alias_file(&self.config.path).await?);
+        if let Some(redis) = &self.redis {
+            let overlay = load_alias_overlay(redis, &self.config.redis_key).await?;
+            dictionary = dictionary.with_overlay(overlay);
+        }
+
+        let dictionary = Arc::new(dictionary);
+        *self
+            .dictionary
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = dictionary.clone();
+        tracing::info!("Loaded {} alias groups", dictionary.len());
+        Ok(dictionary)
+    }
+
+    /// The current dictionary
+    pub fn dictionary(&self) -> Arc<AliasDictionary> {
+        self.dictionary
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+}
@@ -0,0 +1,49 @@
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
+use std::collections::HashSet;
+
+/// Weight factor between consecutive `/findname` candidates
+const CANDIDATE_WEIGHT_DECAY: f64 = 0.75;
+/// Most AI candidates turned into sub-queries, `/findname` returns up to 12
+pub const MAX_CANDIDATES: usize = 12;
+
+/// Turns the canonical names proposed by the AI service into weighted sub-queries
+pub struct CandidateExpansionService;
+
+impl CandidateExpansionService {
+    /// The raw query, then the alias variants, then the deduplicated candidates
+    ///
+    /// Alias variants keep their own weight and do not count towards the
+    /// [`MAX_CANDIDATES`] AI candidates. The raw query and the first candidate
+    /// get weight 1, each later candidate `CANDIDATE_WEIGHT_DECAY` times the
+    /// previous one. Blank candidates and repeats (ignoring case) are dropped.
+    pub fn expand(
+        raw_query: &str,
+        aliases: Vec<WeightedQuery>,
+        candidates: Vec<String>,
+    ) -> Vec<WeightedQuery> {
+        let mut seen = HashSet::from([raw_query.trim().to_lowercase()]);
+        let mut queries = vec![WeightedQuery::unweighted(raw_query)];
+        queries.extend(
+            aliases
+                .into_iter()
+                .filter(|alias| seen.insert(alias.text.trim().to_lowercase())),
+        );
+
+        let mut weight = 1.0;
+        let mut taken = 0;
+        for candidate in candidates {
+            let candidate = candidate.trim();
+            if candidate.is_empty() || !seen.insert(candidate.to_lowercase()) {
+                continue;
+            }
+            queries.push(WeightedQuery::new(candidate, weight));
+            weight *= CANDIDATE_WEIGHT_DECAY;
+            taken += 1;
+            if taken == MAX_CANDIDATES {
+                break;
+            }
+        }
+
+        queries
+    }
+}
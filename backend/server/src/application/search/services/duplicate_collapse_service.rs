@@ -0,0 +1,29 @@
+use crate::application::search::dto::search_hit::SearchHit;
+use crate::domain::search::entities::duplicate_index::DuplicateIndex;
+use std::collections::HashSet;
+
+/// Folds copies of the same file stored at several paths into a single hit
+pub struct DuplicateCollapseService;
+
+impl DuplicateCollapseService {
+    /// Keep the best-ranked copy of every duplicate group
+    ///
+    /// The kept hit lists the paths of all copies in `locations`, including
+    /// copies that did not match the search. Hits without duplicates pass
+    /// through unchanged.
+    pub fn collapse(hits: Vec<SearchHit>, duplicates: &DuplicateIndex) -> Vec<SearchHit> {
+        let mut seen = HashSet::new();
+        hits.into_iter()
+            .filter_map(|mut hit| {
+                let Some(group) = duplicates.group_of(&hit.item.info.file_path) else {
+                    return Some(hit);
+                };
+                if !seen.insert(group.locations[0].clone()) {
+                    return None;
+                }
+                hit.locations = Some(group.locations.clone());
+                Some(hit)
+            })
+            .collect()
+    }
+}
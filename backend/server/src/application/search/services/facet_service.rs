@@ -0,0 +1,105 @@
+use crate::application::files::services::file_tree_service::FileTreeService;
+use crate::domain::search::entities::search_item::SearchItem;
+use crate::domain::search::value_objects::item_kind::ItemKind;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const MILLIS_PER_DAY: u64 = 86_400_000;
+
+/// Facet counts over a full search match set
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SearchFacets {
+    /// Hits per bucket (`shinnku`, `galgame0`)
+    pub buckets: BTreeMap<String, usize>,
+    /// Hits per top-level folder inside a bucket, keyed as `bucket/folder`
+    pub categories: BTreeMap<String, usize>,
+    /// Hits per lowercase file extension
+    pub extensions: BTreeMap<String, usize>,
+    /// Hits per game type (`生肉`, `熟肉`, `手机`)
+    pub game_types: BTreeMap<String, usize>,
+    /// Hits per upload year
+    pub years: BTreeMap<i64, usize>,
+}
+
+/// Application service computing facet counts for search results
+pub struct FacetService;
+
+impl FacetService {
+    /// Count facets over every file of the match set, folder hits are skipped
+    pub fn count<'a>(items: impl IntoIterator<Item = &'a SearchItem>) -> SearchFacets {
+        let mut facets = SearchFacets::default();
+
+        for item in items
+            .into_iter()
+            .filter(|item| item.kind != ItemKind::Folder)
+        {
+            let file_path = &item.info.file_path;
+            let tree_path = FileTreeService::tree_path(file_path);
+            let mut segments = tree_path.split('/');
+
+            if let Some(bucket) = segments.next() {
+                *facets.buckets.entry(bucket.to_string()).or_default() += 1;
+
+                // Only folders count as categories, not files sitting at the bucket root
+                if let (Some(category), Some(_)) = (segments.next(), segments.next()) {
+                    *facets
+                        .categories
+                        .entry(format!("{bucket}/{category}"))
+                        .or_default() += 1;
+                }
+            }
+
+            if let Some(ext) = Self::extension(file_path) {
+                *facets.extensions.entry(ext).or_default() += 1;
+            }
+
+            *facets
+                .game_types
+                .entry(Self::game_type(file_path).to_string())
+                .or_default() += 1;
+
+            *facets
+                .years
+                .entry(Self::year_from_millis(item.info.upload_timestamp))
+                .or_default() += 1;
+        }
+
+        facets
+    }
+
+    /// Lowercase extension of the file name, if it has one
+    pub fn extension(file_path: &str) -> Option<String> {
+        let name = file_path.rsplit('/').next().unwrap_or(file_path);
+        match name.rsplit_once('.') {
+            Some((stem, ext)) if !stem.is_empty() && !ext.is_empty() => Some(ext.to_lowercase()),
+            _ => None,
+        }
+    }
+
+    /// Game type of a file, mirroring `get_game_type` in the frontend
+    pub fn game_type(file_path: &str) -> &'static str {
+        if file_path.starts_with("合集系列") {
+            "生肉"
+        } else if file_path.starts_with("zd") || file_path.starts_with("0/win") {
+            "熟肉"
+        } else {
+            "手机"
+        }
+    }
+
+    /// Calendar year (UTC) of a millisecond Unix timestamp
+    pub fn year_from_millis(millis: u64) -> i64 {
+        // Days-to-civil conversion from Howard Hinnant's date algorithms
+        let days = i64::try_from(millis / MILLIS_PER_DAY).unwrap_or(i64::MAX / 2) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let year = year_of_era + era * 400;
+
+        // Months are counted from March, so January and February belong to the next year
+        if month_index >= 10 { year + 1 } else { year }
+    }
+}
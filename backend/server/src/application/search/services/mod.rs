@@ -1,3 +1,14 @@
+pub mod alias_service;
+pub mod candidate_expansion_service;
+pub mod duplicate_collapse_service;
+pub mod facet_service;
+pub mod popularity_tracker;
+pub mod preset_service;
+pub mod query_parser;
+pub mod relevance_evaluator;
+pub mod result_grouping_service;
+pub mod search_cache_service;
+pub mod search_execution_service;
+
 // Application services for search operations
-// Currently delegating to domain services directly
 // Add application-level concerns here if needed (caching, validation, etc.)
@@ -0,0 +1,235 @@
+use crate::domain::search::entities::popularity_snapshot::PopularitySnapshot;
+use crate::domain::search::services::popularity_service::PopularityService;
+use crate::error::AppError;
+use crate::infrastructure::persistence::redis::click_store::ClickStore;
+use redis::aio::ConnectionManager;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Most per-query snapshots kept between refreshes
+const MAX_CACHED_QUERIES: usize = 10_000;
+
+/// Click tracking and popularity ranking settings
+#[derive(Debug, Clone)]
+pub struct PopularityConfig {
+    /// Prefix of the Redis keys holding the click counters
+    pub key_prefix: String,
+    /// Time after which a click counts half as much
+    pub half_life: Duration,
+    /// Per-query counters expire after this long without clicks
+    pub query_ttl: Duration,
+    /// Repeated clicks of one client on a file within this window count once
+    pub dedupe_window: Duration,
+    /// How often the in-process snapshot is reloaded from Redis
+    pub refresh_interval: Duration,
+    /// Most files kept in the snapshot and in Redis
+    pub max_files: usize,
+    /// How much full popularity lowers a fuse score
+    pub weight: f64,
+    /// Extra weight of a click made from the searched query
+    pub query_weight: f64,
+    /// Number of best results re-ranked by popularity
+    pub window: usize,
+}
+
+impl Default for PopularityConfig {
+    fn default() -> Self {
+        Self {
+            key_prefix: "popularity".to_string(),
+            half_life: Duration::from_secs(7 * 24 * 60 * 60),
+            query_ttl: Duration::from_secs(30 * 24 * 60 * 60),
+            dedupe_window: Duration::from_secs(60 * 60),
+            refresh_interval: Duration::from_secs(5 * 60),
+            max_files: 100_000,
+            weight: 0.15,
+            query_weight: 1.0,
+            window: 100,
+        }
+    }
+}
+
+/// Records download clicks and serves a periodically refreshed popularity snapshot
+///
+/// Searches read the in-process snapshot, so ranking by popularity costs no
+/// Redis round trip. Per-query counters are loaded on first use and kept until
+/// the next refresh, so one generation ranks a query the same way throughout.
+/// Without Redis clicks are dropped and the snapshot stays empty.
+pub struct PopularityTracker {
+    config: PopularityConfig,
+    store: Option<ClickStore>,
+    service: PopularityService,
+    snapshot: RwLock<Arc<PopularitySnapshot>>,
+    queries: Mutex<HashMap<String, Arc<PopularitySnapshot>>>,
+}
+
+impl PopularityTracker {
+    pub fn new(config: PopularityConfig, redis: Option<ConnectionManager>) -> Self {
+        let store = redis.map(|con| {
+            ClickStore::new(
+                con,
+                config.key_prefix.clone(),
+                config.query_ttl,
+                config.dedupe_window,
+            )
+        });
+        let service = PopularityService::new(config.half_life);
+        Self {
+            config,
+            store,
+            service,
+            snapshot: RwLock::new(Arc::new(PopularitySnapshot::default())),
+            queries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record that `client` opened `file_path`, from the search for `query` if given
+    ///
+    /// `query` is expected to be validated already. Repeated clicks of one
+    /// client on a file within the dedupe window are ignored; clicks of an
+    /// unknown client always count, rather than all sharing one dedupe slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the click cannot be written to Redis
+    pub async fn record_click(
+        &self,
+        file_path: &str,
+        query: Option<&str>,
+        client: Option<&str>,
+    ) -> Result<(), AppError> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        let query = query.and_then(Self::query_key);
+        let now = Self::now_millis();
+        let increment = self.service.click_increment(now);
+        store
+            .record(
+                self.service.era(now),
+                file_path,
+                query.as_deref(),
+                client,
+                increment,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Decayed popularity of the files opened from searches for `query`
+    ///
+    /// Returns `None` without Redis, for queries nobody clicked from, or if
+    /// the counters cannot be read.
+    pub async fn query_snapshot(&self, query: &str) -> Option<Arc<PopularitySnapshot>> {
+        let store = self.store.as_ref()?;
+        let key = Self::query_key(query)?;
+        if let Some(snapshot) = self.cached_queries().get(&key) {
+            return Some(snapshot.clone()).filter(|snapshot| !snapshot.is_empty());
+        }
+
+        let now = Self::now_millis();
+        let era = self.service.era(now);
+        let factor = PopularityService::rebase_factor();
+        let top = async {
+            store.rebase(era, Some(&key), factor).await?;
+            store.top_query_files(era, &key, self.config.window).await
+        };
+        let top = match top.await {
+            Ok(top) => top,
+            Err(e) => {
+                tracing::warn!("Failed to load popularity of query '{key}': {e}");
+                return None;
+            }
+        };
+        let scores = top
+            .into_iter()
+            .map(|(path, stored)| (path, self.service.decayed(stored, now)))
+            .collect();
+        let snapshot = Arc::new(PopularitySnapshot::new(self.snapshot().generation, scores));
+
+        let mut queries = self.cached_queries();
+        if queries.len() >= MAX_CACHED_QUERIES {
+            queries.clear();
+        }
+        let snapshot = queries.entry(key).or_insert(snapshot).clone();
+        Some(snapshot).filter(|snapshot| !snapshot.is_empty())
+    }
+
+    /// Reload the snapshot from Redis
+    ///
+    /// Once an era has passed, the counters are carried over into the next
+    /// one first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the counters cannot be read from Redis
+    pub async fn refresh(&self) -> Result<Arc<PopularitySnapshot>, AppError> {
+        let Some(store) = &self.store else {
+            return Ok(self.snapshot());
+        };
+        let now = Self::now_millis();
+        let era = self.service.era(now);
+        store
+            .rebase(era, None, PopularityService::rebase_factor())
+            .await?;
+        let scores = store
+            .top_files(era, self.config.max_files)
+            .await?
+            .into_iter()
+            .map(|(path, stored)| (path, self.service.decayed(stored, now)))
+            .collect();
+
+        let generation = self.snapshot().generation + 1;
+        let snapshot = Arc::new(PopularitySnapshot::new(generation, scores));
+        *self
+            .snapshot
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = snapshot.clone();
+        self.cached_queries().clear();
+        Ok(snapshot)
+    }
+
+    /// Refresh the snapshot every `refresh_interval` in the background
+    pub fn spawn_refresh(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.refresh_interval);
+            loop {
+                interval.tick().await;
+                match self.refresh().await {
+                    Ok(snapshot) => {
+                        tracing::debug!("Popularity of {} files loaded", snapshot.len())
+                    }
+                    Err(e) => tracing::warn!("Failed to refresh popularity: {e}"),
+                }
+            }
+        })
+    }
+
+    /// The current snapshot
+    pub fn snapshot(&self) -> Arc<PopularitySnapshot> {
+        self.snapshot
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn config(&self) -> &PopularityConfig {
+        &self.config
+    }
+
+    /// Redis key part of `query`: its lowercased words, `None` if it has none
+    fn query_key(query: &str) -> Option<String> {
+        let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        Some(words.join(" ")).filter(|key| !key.is_empty())
+    }
+
+    fn cached_queries(&self) -> MutexGuard<'_, HashMap<String, Arc<PopularitySnapshot>>> {
+        self.queries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn now_millis() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+    }
+}
@@ -0,0 +1,73 @@
+use crate::application::search::queries::validated_query::QueryViolation;
+use crate::error::AppError;
+use crate::infrastructure::adapters::search::fuse_search_adapter::FuseConfig;
+use crate::infrastructure::persistence::config::preset_config::load_preset_file;
+use std::collections::BTreeMap;
+
+/// Where the search presets are loaded from
+#[derive(Debug, Clone)]
+pub struct PresetConfig {
+    /// TOML file read at startup
+    pub path: String,
+}
+
+impl Default for PresetConfig {
+    fn default() -> Self {
+        Self {
+            path: "presets.toml".to_string(),
+        }
+    }
+}
+
+/// Named fuzzy matching configurations selectable per request with `?preset=`
+pub struct PresetService {
+    presets: BTreeMap<String, FuseConfig>,
+}
+
+impl PresetService {
+    /// Keep the presets whose fields are within bounds, logging the others
+    pub fn new(presets: BTreeMap<String, FuseConfig>) -> Self {
+        let presets = presets
+            .into_iter()
+            .filter(|(name, config)| match config.validate() {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::error!("Ignoring search preset '{name}': {e}");
+                    false
+                }
+            })
+            .collect();
+        Self { presets }
+    }
+
+    /// Load the presets, starting without any if the file cannot be loaded
+    pub async fn load(config: PresetConfig) -> Self {
+        let presets = load_preset_file(&config.path).await.unwrap_or_else(|e| {
+            tracing::error!("Failed to load search presets, starting without them: {e}");
+            BTreeMap::new()
+        });
+        let service = Self::new(presets);
+        tracing::info!("Loaded {} search presets", service.presets.len());
+        service
+    }
+
+    /// Configuration of the named preset, the default one without a name
+    ///
+    /// # Errors
+    ///
+    /// Returns a bad request error if no preset has that name
+    pub fn resolve(&self, name: Option<&str>) -> Result<FuseConfig, AppError> {
+        match name {
+            None => Ok(FuseConfig::default()),
+            Some(name) => self
+                .presets
+                .get(name)
+                .cloned()
+                .ok_or_else(|| QueryViolation::UnknownPreset(name.to_string()).into()),
+        }
+    }
+
+    pub fn presets(&self) -> &BTreeMap<String, FuseConfig> {
+        &self.presets
+    }
+}
@@ -0,0 +1,327 @@
+use crate::application::search::queries::search_filters::SearchFilters;
+use crate::application::search::queries::validated_query::ValidatedQuery;
+use crate::error::AppError;
+use thiserror::Error;
+
+const MILLIS_PER_DAY: i64 = 86_400_000;
+const BUCKETS: [&str; 2] = ["shinnku", "galgame0"];
+
+/// A query split into field predicates and the remaining free text
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedQuery {
+    /// Free text handed to the fuzzy search, normalized like [`ValidatedQuery`]
+    pub text: String,
+    /// Field predicates found in the query
+    pub filters: SearchFilters,
+}
+
+/// A syntax error in a field-qualified query
+///
+/// `position` is the 0-based character offset of the offending part of the input.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message} at position {position}")]
+pub struct QueryParseError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl From<QueryParseError> for AppError {
+    fn from(err: QueryParseError) -> Self {
+        AppError::InvalidInput {
+            code: "invalid_query_syntax",
+            message: err.to_string(),
+        }
+    }
+}
+
+/// Comparison operator between a field and its value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+/// Parser for the `/search` query language
+///
+/// Tokens of the form `field:value` or `field<op>value` for a known field are
+/// predicates; everything else is free text. Supported fields:
+///
+/// - `bucket:shinnku|galgame0`
+/// - `ext:rar` (comma separated alternatives allowed, e.g. `ext:rar,7z`)
+/// - `type:生肉|熟肉|手机` (or `raw`, `translated`, `mobile`)
+/// - `size>1GB`, `size>=500MB`, `size<2GB`, `size<=10KB`
+/// - `after:2023-01`, `before:2024` (`YYYY`, `YYYY-MM` or `YYYY-MM-DD`, UTC)
+///
+/// Unknown fields stay in the free text so titles like `Re:Zero` still work.
+pub struct QueryParser;
+
+impl QueryParser {
+    /// Parse a raw query string
+    ///
+    /// Predicates are read from `input` as is and only the remaining free text
+    /// is normalized, so error positions point into `input`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with the character position if a known field has an
+    /// invalid operator or value
+    pub fn parse(input: &str) -> Result<ParsedQuery, QueryParseError> {
+        let mut parsed = ParsedQuery::default();
+        let mut words: Vec<&str> = Vec::new();
+
+        for (position, token) in Self::tokens(input) {
+            match Self::split_predicate(token) {
+                Some((field, op, value)) => {
+                    let value_position = position + token.chars().count() - value.chars().count();
+                    Self::apply(
+                        &mut parsed.filters,
+                        field,
+                        op,
+                        value,
+                        position,
+                        value_position,
+                    )?;
+                }
+                None => words.push(token),
+            }
+        }
+
+        parsed.text = ValidatedQuery::normalize(&words.join(" "));
+        Ok(parsed)
+    }
+
+    /// Whitespace-separated tokens with their character offsets
+    fn tokens(input: &str) -> Vec<(usize, &str)> {
+        let mut tokens = Vec::new();
+        let mut start: Option<(usize, usize)> = None;
+
+        for (char_idx, (byte_idx, c)) in input.char_indices().enumerate() {
+            match (c.is_whitespace(), start) {
+                (true, Some((char_start, byte_start))) => {
+                    tokens.push((char_start, &input[byte_start..byte_idx]));
+                    start = None;
+                }
+                (false, None) => start = Some((char_idx, byte_idx)),
+                _ => {}
+            }
+        }
+        if let Some((char_start, byte_start)) = start {
+            tokens.push((char_start, &input[byte_start..]));
+        }
+
+        tokens
+    }
+
+    /// Split `field<op>value` for a known field, `None` for free text
+    fn split_predicate(token: &str) -> Option<(&'static str, Op, &str)> {
+        let field_len = token
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(token.len());
+        let field = match token[..field_len].to_ascii_lowercase().as_str() {
+            "bucket" => "bucket",
+            "ext" => "ext",
+            "type" => "type",
+            "size" => "size",
+            "after" => "after",
+            "before" => "before",
+            _ => return None,
+        };
+
+        let rest = &token[field_len..];
+        let (op, value) = if let Some(v) = rest.strip_prefix(">=") {
+            (Op::Ge, v)
+        } else if let Some(v) = rest.strip_prefix("<=") {
+            (Op::Le, v)
+        } else if let Some(v) = rest.strip_prefix('>') {
+            (Op::Gt, v)
+        } else if let Some(v) = rest.strip_prefix('<') {
+            (Op::Lt, v)
+        } else if let Some(v) = rest.strip_prefix(':') {
+            (Op::Eq, v)
+        } else {
+            return None;
+        };
+
+        Some((field, op, value))
+    }
+
+    fn apply(
+        filters: &mut SearchFilters,
+        field: &str,
+        op: Op,
+        value: &str,
+        position: usize,
+        value_position: usize,
+    ) -> Result<(), QueryParseError> {
+        let error = |position: usize, message: String| QueryParseError { position, message };
+
+        if value.is_empty() {
+            return Err(error(
+                value_position,
+                format!("missing value for `{field}`"),
+            ));
+        }
+        if field == "size" {
+            if op == Op::Eq {
+                return Err(error(
+                    position,
+                    "`size` expects a comparison like `size>1GB`".to_string(),
+                ));
+            }
+        } else if op != Op::Eq {
+            return Err(error(
+                position,
+                format!("`{field}` expects `{field}:value`"),
+            ));
+        }
+
+        match field {
+            "bucket" => {
+                for (position, bucket) in Self::list_items(value, value_position) {
+                    if !BUCKETS.contains(&bucket) {
+                        return Err(error(
+                            position,
+                            format!(
+                                "unknown bucket '{bucket}', expected one of {}",
+                                BUCKETS.join(", ")
+                            ),
+                        ));
+                    }
+                    filters.buckets.push(bucket.to_string());
+                }
+            }
+            "ext" => {
+                for (position, ext) in Self::list_items(value, value_position) {
+                    let ext = ext.trim_start_matches('.').to_lowercase();
+                    if ext.is_empty() {
+                        return Err(error(position, "empty extension".to_string()));
+                    }
+                    filters.extensions.push(ext);
+                }
+            }
+            "type" => {
+                let game_type = match value.to_lowercase().as_str() {
+                    "生肉" | "raw" => "生肉",
+                    "熟肉" | "translated" => "熟肉",
+                    "手机" | "mobile" => "手机",
+                    _ => {
+                        return Err(error(
+                            value_position,
+                            format!("unknown type '{value}', expected 生肉, 熟肉 or 手机"),
+                        ));
+                    }
+                };
+                filters.game_types.push(game_type.to_string());
+            }
+            "size" => {
+                let bytes = Self::parse_size(value).ok_or_else(|| {
+                    error(
+                        value_position,
+                        format!(
+                            "invalid size '{value}', expected a number with an optional unit (B, KB, MB, GB, TB)"
+                        ),
+                    )
+                })?;
+                match op {
+                    Op::Gt => filters.min_size = Some(bytes.saturating_add(1)),
+                    Op::Ge => filters.min_size = Some(bytes),
+                    Op::Lt => filters.max_size = Some(bytes.saturating_sub(1)),
+                    Op::Le | Op::Eq => filters.max_size = Some(bytes),
+                }
+            }
+            _ => {
+                let millis = Self::parse_date(value).ok_or_else(|| {
+                    error(
+                        value_position,
+                        format!("invalid date '{value}', expected YYYY, YYYY-MM or YYYY-MM-DD"),
+                    )
+                })?;
+                if field == "after" {
+                    filters.uploaded_after = Some(millis);
+                } else {
+                    filters.uploaded_before = Some(millis);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Comma-separated alternatives of a value with their character offsets
+    fn list_items(value: &str, value_position: usize) -> Vec<(usize, &str)> {
+        let mut position = value_position;
+        value
+            .split(',')
+            .map(|item| {
+                let start = position;
+                position += item.chars().count() + 1;
+                (start, item)
+            })
+            .collect()
+    }
+
+    /// Parse a size like `1GB`, `1.5gb`, `500MB` or `1024` into bytes (1024-based units)
+    fn parse_size(value: &str) -> Option<u64> {
+        let split = value
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(value.len());
+        let (number, unit) = value.split_at(split);
+        let number: f64 = number.parse().ok()?;
+
+        let multiplier: f64 = match unit.to_ascii_uppercase().as_str() {
+            "" | "B" => 1.0,
+            "K" | "KB" => 1024.0,
+            "M" | "MB" => 1024.0 * 1024.0,
+            "G" | "GB" => 1024.0 * 1024.0 * 1024.0,
+            "T" | "TB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+            _ => return None,
+        };
+
+        let bytes = (number * multiplier).round();
+        if !bytes.is_finite() || bytes < 0.0 || bytes >= u64::MAX as f64 {
+            return None;
+        }
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let bytes = bytes as u64;
+        Some(bytes)
+    }
+
+    /// Parse `YYYY`, `YYYY-MM` or `YYYY-MM-DD` into the UTC start of that period in milliseconds
+    fn parse_date(value: &str) -> Option<u64> {
+        let mut parts = value.split('-');
+        let year: i64 = parts.next().filter(|y| y.len() == 4)?.parse().ok()?;
+        let month: i64 = parts.next().map_or(Some(1), |m| m.parse().ok())?;
+        let day: i64 = parts.next().map_or(Some(1), |d| d.parse().ok())?;
+        if parts.next().is_some() || !(1..=12).contains(&month) {
+            return None;
+        }
+
+        let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+        let days_in_month = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        if !(1..=days_in_month).contains(&day) {
+            return None;
+        }
+
+        let days = Self::days_from_civil(year, month, day);
+        u64::try_from(days.checked_mul(MILLIS_PER_DAY)?).ok()
+    }
+
+    /// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's algorithm)
+    fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let shifted_month = (month + 9) % 12;
+        let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+}
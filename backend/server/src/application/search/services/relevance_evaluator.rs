@@ -0,0 +1,33 @@
+use crate::domain::search::entities::golden_query::GoldenQuery;
+use crate::domain::search::entities::relevance_report::RelevanceReport;
+use crate::domain::search::entities::search_item::SearchList;
+use crate::domain::search::repositories::fuzzy_search_repository::FuzzySearchRepository;
+use crate::domain::search::services::relevance_metrics_service::RelevanceMetricsService;
+
+/// Runs a golden query set against a search backend and scores its rankings
+pub struct RelevanceEvaluator<R: FuzzySearchRepository> {
+    repository: R,
+}
+
+impl<R: FuzzySearchRepository> RelevanceEvaluator<R> {
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    /// Search every golden query in `items` and report MRR, NDCG and recall
+    pub fn evaluate(&self, golden: &[GoldenQuery], items: &SearchList) -> RelevanceReport {
+        let metrics = RelevanceMetricsService::new();
+        let queries = golden
+            .iter()
+            .map(|golden| {
+                let results = self.repository.search_scored(&golden.query, items);
+                let ranked: Vec<&str> = results
+                    .iter()
+                    .map(|result| &*result.item.info.file_path)
+                    .collect();
+                metrics.evaluate_query(&golden.query, &ranked, &golden.relevant)
+            })
+            .collect();
+        metrics.summarize(queries)
+    }
+}
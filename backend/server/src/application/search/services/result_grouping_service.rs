@@ -0,0 +1,65 @@
+use crate::application::search::dto::search_hit::{ReleaseGroup, SearchHit};
+use crate::domain::search::value_objects::item_kind::ItemKind;
+use crate::domain::search::value_objects::release_key::ReleaseKey;
+use std::collections::HashMap;
+
+/// Folds split archives and versions of one release into a single hit
+pub struct ResultGroupingService;
+
+impl ResultGroupingService {
+    /// Group ranked results by release key
+    ///
+    /// Each group takes the position and item of its best-ranked member, so
+    /// the ranking between releases is preserved. Folder hits stay on their own.
+    pub fn group(hits: Vec<SearchHit>) -> Vec<SearchHit> {
+        let mut positions: HashMap<ReleaseKey, usize> = HashMap::new();
+        let mut groups: Vec<(ReleaseKey, Vec<SearchHit>)> = Vec::new();
+
+        for hit in hits {
+            let key = ReleaseKey::new(&hit.item.info.file_path);
+            // A folder is never a part of a release, even when named like one
+            if hit.item.kind == ItemKind::Folder {
+                groups.push((key, vec![hit]));
+                continue;
+            }
+            match positions.get(&key) {
+                Some(&idx) => groups[idx].1.push(hit),
+                None => {
+                    positions.insert(key.clone(), groups.len());
+                    groups.push((key, vec![hit]));
+                }
+            }
+        }
+
+        groups
+            .into_iter()
+            .map(|(key, items)| Self::fold(&key, items))
+            .collect()
+    }
+
+    fn fold(key: &ReleaseKey, mut hits: Vec<SearchHit>) -> SearchHit {
+        let mut best = hits.remove(0);
+        if hits.is_empty() {
+            return best;
+        }
+
+        let mut members: Vec<_> = std::iter::once(best.item.info.clone())
+            .chain(hits.into_iter().map(|hit| hit.item.info))
+            .collect();
+        members.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+
+        let group = ReleaseGroup {
+            key: key.to_string(),
+            total_size: members.iter().map(|info| info.file_size).sum(),
+            newest_timestamp: members
+                .iter()
+                .map(|info| info.upload_timestamp)
+                .max()
+                .unwrap_or_default(),
+            members,
+        };
+
+        best.group = Some(group);
+        best
+    }
+}
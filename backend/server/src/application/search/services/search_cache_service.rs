@@ -0,0 +1,171 @@
+use crate::application::search::dto::related_hit::RelatedHit;
+use crate::application::search::dto::search_response::SearchResponse;
+use crate::application::search::queries::combined_search_query::CombinedSearchQuery;
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use lru::LruCache;
+use redis::aio::ConnectionManager;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Configuration of the search result cache
+#[derive(Debug, Clone)]
+pub struct SearchCacheConfig {
+    /// Responses kept in the in-process tier
+    pub capacity: usize,
+    /// Lifetime of entries in the Redis tier
+    pub redis_ttl: Duration,
+    /// Larger serialized responses stay in-process only
+    pub max_redis_entry_bytes: usize,
+}
+
+impl Default for SearchCacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 512,
+            redis_ttl: Duration::from_secs(10 * 60),
+            max_redis_entry_bytes: 512 * 1024,
+        }
+    }
+}
+
+/// Two-tier cache of search responses: an in-process LRU in front of Redis
+///
+/// Keys contain the index version computed at bootstrap, so reloading the
+/// data makes every older entry unreachable; stale Redis entries expire with
+/// their TTL. Related files of a file are kept in-process only.
+pub struct SearchCacheService {
+    index_version: String,
+    config: SearchCacheConfig,
+    local: Mutex<LruCache<String, Arc<SearchResponse>>>,
+    related: Mutex<LruCache<String, Arc<Vec<RelatedHit>>>>,
+    redis: Option<ConnectionManager>,
+}
+
+impl SearchCacheService {
+    pub fn new(
+        index_version: impl Into<String>,
+        config: SearchCacheConfig,
+        redis: Option<ConnectionManager>,
+    ) -> Self {
+        let capacity = NonZeroUsize::new(config.capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            index_version: index_version.into(),
+            config,
+            local: Mutex::new(LruCache::new(capacity)),
+            related: Mutex::new(LruCache::new(capacity)),
+            redis,
+        }
+    }
+
+    /// Cache key of a `/search` query
+    ///
+    /// Pages of one search share the key; the entry holds every result, see
+    /// [`SearchFilesQuery::unpaged`].
+    pub fn files_key(&self, query: &SearchFilesQuery) -> String {
+        let mut query = query.clone().unpaged();
+        if query.tuning.is_none() {
+            query.query = Self::normalize(&query.query);
+        }
+        self.key("files", &query)
+    }
+
+    /// Cache key of a combined search query
+    ///
+    /// Pages of one search share the key; the entry holds every result, see
+    /// [`CombinedSearchQuery::unpaged`].
+    pub fn combined_key(&self, query: &CombinedSearchQuery) -> String {
+        let mut query = query.clone().unpaged();
+        if query.tuning.is_none() {
+            for variant in &mut query.queries {
+                variant.text = Self::normalize(&variant.text);
+            }
+        }
+        self.key("combined", &query)
+    }
+
+    /// Cache key of the files related to the file at `path`
+    pub fn related_key(&self, path: &str) -> String {
+        format!("cache:related:{}:{path}", self.index_version)
+    }
+
+    /// Cached related files for `key`
+    pub fn get_related(&self, key: &str) -> Option<Arc<Vec<RelatedHit>>> {
+        self.lock_related().get(key).cloned()
+    }
+
+    /// Store freshly computed related files
+    pub fn put_related(&self, key: String, hits: Arc<Vec<RelatedHit>>) {
+        self.lock_related().put(key, hits);
+    }
+
+    /// Cached response for `key`, looking in-process first, then in Redis
+    pub async fn get(&self, key: &str) -> Option<Arc<SearchResponse>> {
+        if let Some(response) = self.lock().get(key) {
+            return Some(response.clone());
+        }
+
+        let mut con = self.redis.clone()?;
+        let raw: Option<String> = match redis::cmd("GET").arg(key).query_async(&mut con).await {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::error!("Redis GET {key} error: {e}");
+                return None;
+            }
+        };
+        let response: Arc<SearchResponse> = Arc::new(serde_json::from_str(&raw?).ok()?);
+        self.lock().put(key.to_string(), response.clone());
+        Some(response)
+    }
+
+    /// Store a freshly computed response in both tiers
+    pub async fn put(&self, key: String, response: Arc<SearchResponse>) {
+        self.lock().put(key.clone(), response.clone());
+
+        let Some(mut con) = self.redis.clone() else {
+            return;
+        };
+        let Ok(value) = serde_json::to_string(response.as_ref()) else {
+            return;
+        };
+        if value.len() > self.config.max_redis_entry_bytes {
+            return;
+        }
+        let result: redis::RedisResult<()> = redis::cmd("SET")
+            .arg(&key)
+            .arg(value)
+            .arg("EX")
+            .arg(self.config.redis_ttl.as_secs().max(1))
+            .query_async(&mut con)
+            .await;
+        if let Err(e) = result {
+            tracing::error!("Redis SET {key} error: {e}");
+        }
+    }
+
+    fn key(&self, kind: &str, query: &impl serde::Serialize) -> String {
+        // Serializing the whole query covers every field that affects the response
+        let query = serde_json::to_string(query).unwrap_or_default();
+        format!("cache:search:{}:{kind}:{query}", self.index_version)
+    }
+
+    /// The default Fuse tuning ignores case, so queries differing only in case
+    /// or spacing share an entry; tuned searches, which may be case-sensitive
+    /// or not tokenize, keep their text as typed
+    fn normalize(text: &str) -> String {
+        let words: Vec<String> = text.split_whitespace().map(str::to_lowercase).collect();
+        words.join(" ")
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruCache<String, Arc<SearchResponse>>> {
+        self.local
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn lock_related(&self) -> std::sync::MutexGuard<'_, LruCache<String, Arc<Vec<RelatedHit>>>> {
+        self.related
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,214 @@
+use crate::application::search::dto::search_response::SearchResponse;
+use crate::application::search::services::search_cache_service::SearchCacheService;
+use crate::error::AppError;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OnceCell, Semaphore};
+
+/// Limits on blocking search work
+#[derive(Debug, Clone)]
+pub struct SearchExecutionConfig {
+    /// Full scans allowed to run at the same time
+    pub max_concurrent: usize,
+    /// Searches allowed to wait for a free slot; more are rejected at once
+    pub max_queued: usize,
+    /// How long a queued search waits for a slot before it is rejected
+    pub queue_timeout: Duration,
+    /// How long one scan may run before it stops with the results found so far
+    pub time_budget: Duration,
+}
+
+impl Default for SearchExecutionConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: std::thread::available_parallelism().map_or(4, |n| n.get()),
+            max_queued: 64,
+            queue_timeout: Duration::from_secs(5),
+            time_budget: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Outcome of one computation, shared by every caller of the same flight
+type FlightResult = Result<Arc<SearchResponse>, FlightError>;
+
+#[derive(Debug, Clone)]
+enum FlightError {
+    Busy,
+    Failed(String),
+}
+
+impl From<FlightError> for AppError {
+    fn from(e: FlightError) -> Self {
+        match e {
+            FlightError::Busy => AppError::Unavailable("too many searches in progress".into()),
+            FlightError::Failed(msg) => AppError::Internal(msg),
+        }
+    }
+}
+
+/// Runs searches through the result cache
+///
+/// Identical queries arriving while one is being computed wait for that
+/// computation instead of starting their own scan (single flight). Scans run
+/// on blocking threads, at most `max_concurrent` at a time; searches that
+/// cannot get a slot are rejected with 503 rather than piling up.
+pub struct SearchExecutionService {
+    cache: Arc<SearchCacheService>,
+    config: SearchExecutionConfig,
+    permits: Arc<Semaphore>,
+    queued: AtomicUsize,
+    in_flight: Mutex<HashMap<String, Arc<OnceCell<FlightResult>>>>,
+}
+
+impl SearchExecutionService {
+    pub fn new(cache: Arc<SearchCacheService>, config: SearchExecutionConfig) -> Self {
+        let permits = Arc::new(Semaphore::new(config.max_concurrent.max(1)));
+        Self {
+            cache,
+            config,
+            permits,
+            queued: AtomicUsize::new(0),
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Cached response for `key`, or the result of `compute` run on a
+    /// blocking thread and stored in the cache
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No search slot frees up in time, or too many searches are queued
+    /// - The blocking task panics
+    pub async fn run<F>(&self, key: String, compute: F) -> Result<Arc<SearchResponse>, AppError>
+    where
+        F: FnOnce() -> SearchResponse + Send + 'static,
+    {
+        if let Some(cached) = self.cache.get(&key).await {
+            return Ok(cached);
+        }
+
+        let flight = self
+            .lock()
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(OnceCell::new()))
+            .clone();
+
+        // Only the first caller's future runs; the others wait for its result.
+        // If that caller goes away, a waiting one takes over the computation.
+        let result = flight
+            .get_or_init(|| self.compute(key.clone(), compute))
+            .await
+            .clone();
+
+        // The flight only coalesces concurrent callers, later ones go through the cache
+        let mut in_flight = self.lock();
+        if in_flight
+            .get(&key)
+            .is_some_and(|current| Arc::ptr_eq(current, &flight))
+        {
+            in_flight.remove(&key);
+        }
+        drop(in_flight);
+
+        Ok(result?)
+    }
+
+    /// How long one scan may run, see [`SearchExecutionConfig::time_budget`]
+    pub fn time_budget(&self) -> Duration {
+        self.config.time_budget
+    }
+
+    /// Result of `work` run on a blocking thread, within the same limits on
+    /// concurrent and queued scans as searches but without caching
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No search slot frees up in time, or too many searches are queued
+    /// - The blocking task panics
+    pub async fn run_blocking<T, F>(&self, work: F) -> Result<T, AppError>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        Ok(self.spawn(work).await?)
+    }
+
+    async fn compute<F>(&self, key: String, compute: F) -> FlightResult
+    where
+        F: FnOnce() -> SearchResponse + Send + 'static,
+    {
+        let response = self.spawn(compute).await?;
+
+        // A partial response depends on load at the time, so it is not reused
+        let response = Arc::new(response);
+        if !response.partial {
+            self.cache.put(key, response.clone()).await;
+        }
+        Ok(response)
+    }
+
+    /// Run `work` on a blocking thread once a search slot is free
+    async fn spawn<T, F>(&self, work: F) -> Result<T, FlightError>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let permit = match self.permits.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                let Some(_slot) = QueueSlot::take(&self.queued, self.config.max_queued) else {
+                    return Err(FlightError::Busy);
+                };
+                match tokio::time::timeout(
+                    self.config.queue_timeout,
+                    self.permits.clone().acquire_owned(),
+                )
+                .await
+                {
+                    Ok(Ok(permit)) => permit,
+                    _ => return Err(FlightError::Busy),
+                }
+            }
+        };
+
+        // The permit moves into the task, so the slot stays taken until the
+        // scan really ends, even if every caller has gone away
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            work()
+        })
+        .await
+        .map_err(|e| FlightError::Failed(e.to_string()))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<OnceCell<FlightResult>>>> {
+        self.in_flight
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Place in the wait queue, released on drop so cancelled waits free it too
+struct QueueSlot<'a>(&'a AtomicUsize);
+
+impl<'a> QueueSlot<'a> {
+    fn take(queued: &'a AtomicUsize, max: usize) -> Option<Self> {
+        queued
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < max).then_some(n + 1)
+            })
+            .ok()
+            .map(|_| Self(queued))
+    }
+}
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
@@ -1 +1,88 @@
-// Common DTOs
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+
+/// One page of results together with the information needed to fetch the next one
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Page<T> {
+    /// Total number of results across all pages
+    pub total: usize,
+    /// Opaque cursor pointing at the next page, `None` on the last page
+    pub next_cursor: Option<String>,
+    /// Results on this page
+    pub results: Vec<T>,
+}
+
+impl<T> Page<T> {
+    /// Cut a page out of the full, already ordered result list.
+    ///
+    /// `limit = None` returns everything from `offset` onwards.
+    pub fn paginate(results: Vec<T>, offset: usize, limit: Option<usize>) -> Self {
+        let total = results.len();
+        let results = results
+            .into_iter()
+            .skip(offset)
+            .take(limit.unwrap_or(usize::MAX))
+            .collect();
+        Self::window(total, results, offset, limit)
+    }
+
+    /// Page of `results` starting at `offset` out of `total` results
+    fn window(total: usize, results: Vec<T>, offset: usize, limit: Option<usize>) -> Self {
+        let end = offset.saturating_add(results.len());
+        let next_cursor = (limit.is_some() && end < total).then(|| encode_cursor(end));
+
+        Self {
+            total,
+            next_cursor,
+            results,
+        }
+    }
+}
+
+impl<T: Clone> Page<T> {
+    /// Cut a page out of a page holding the full result list, e.g. a cached one
+    ///
+    /// Only the results of the new page are copied.
+    pub fn slice(&self, offset: usize, limit: Option<usize>) -> Self {
+        let results = self
+            .results
+            .iter()
+            .skip(offset)
+            .take(limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect();
+        Self::window(self.total, results, offset, limit)
+    }
+}
+
+/// Encode a result offset into an opaque page cursor
+pub fn encode_cursor(offset: usize) -> String {
+    format!("o{offset:x}")
+}
+
+/// Decode a page cursor produced by [`encode_cursor`] back into an offset
+///
+/// # Errors
+///
+/// Returns `AppError::BadRequest` if the cursor is malformed
+pub fn decode_cursor(cursor: &str) -> Result<usize, AppError> {
+    cursor
+        .strip_prefix('o')
+        .and_then(|hex| usize::from_str_radix(hex, 16).ok())
+        .ok_or_else(|| AppError::BadRequest(format!("invalid cursor '{cursor}'")))
+}
+
+/// Resolve the starting offset from either an explicit `offset` or a `cursor`
+///
+/// # Errors
+///
+/// Returns `AppError::BadRequest` if both are given or the cursor is malformed
+pub fn resolve_offset(offset: Option<usize>, cursor: Option<&str>) -> Result<usize, AppError> {
+    match (offset, cursor) {
+        (Some(_), Some(_)) => Err(AppError::BadRequest(
+            "use either `offset` or `cursor`, not both".into(),
+        )),
+        (_, Some(cursor)) => decode_cursor(cursor),
+        (offset, None) => Ok(offset.unwrap_or(0)),
+    }
+}
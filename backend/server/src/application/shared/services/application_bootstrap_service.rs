@@ -1,11 +1,15 @@
 use crate::application::files::services::file_tree_service::FileTreeService;
 use crate::domain::files::entities::tree_node::TreeNode;
+use crate::domain::search::entities::duplicate_index::DuplicateIndex;
+use crate::domain::search::entities::query_vocabulary::QueryVocabulary;
 use crate::domain::search::entities::search_item::SearchList;
+use crate::domain::search::entities::suggest_index::SuggestIndex;
 use crate::domain::search::services::search_index_service::SearchIndexService;
 use crate::infrastructure::persistence::json::bucket_files_repository::{
     GALGAME0_FILES, SHINNKU_FILES, filter_galgame0_files,
 };
 use anyhow::Result;
+use std::sync::Arc;
 use tokio::task::spawn_blocking;
 
 /// Application state data structure
@@ -13,6 +17,12 @@ use tokio::task::spawn_blocking;
 pub struct ApplicationData {
     pub combined_tree: TreeNode,
     pub search_index: SearchList,
+    pub suggest_index: Arc<SuggestIndex>,
+    pub vocabulary: Arc<QueryVocabulary>,
+    /// Files stored more than once, across buckets or folders
+    pub duplicates: Arc<DuplicateIndex>,
+    /// Fingerprint of the search index, part of every search cache key
+    pub index_version: String,
 }
 
 /// Application bootstrap service for initializing application state
@@ -42,16 +52,30 @@ impl ApplicationBootstrapService {
             let galgame0_filtered =
                 filter_galgame0_files(galgame0_bucket_files, "合集系列/浮士德galgame游戏合集");
 
-            let search_index_service = SearchIndexService::new();
-            let search_index = search_index_service
-                .build_index(&[shinnku_bucket_files.clone(), galgame0_filtered]);
-
             let combined_tree =
                 FileTreeService::build_combined_frontend_tree(&shinnku_tree, &galgame0_tree);
+            let suggest_index = Arc::new(SuggestIndex::build(&combined_tree));
+
+            let search_index_service = SearchIndexService::new();
+            let mut search_index = search_index_service
+                .build_index(&[shinnku_bucket_files.clone(), galgame0_filtered]);
+            // Folder paths repeat the tokens of the files below them, so the
+            // vocabulary only counts files
+            let vocabulary = Arc::new(QueryVocabulary::build(&search_index));
+            let duplicates = Arc::new(DuplicateIndex::build(&search_index));
+            search_index.extend(
+                search_index_service
+                    .build_folder_index(&FileTreeService::bucket_roots(&combined_tree)),
+            );
+            let index_version = search_index_service.fingerprint(&search_index);
 
             Ok(ApplicationData {
                 combined_tree,
                 search_index,
+                suggest_index,
+                vocabulary,
+                duplicates,
+                index_version,
             })
         })
         .await?
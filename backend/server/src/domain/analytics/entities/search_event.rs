@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// One search served by the API, as recorded for analytics
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchEvent {
+    /// Unix time of the search in milliseconds
+    pub timestamp: u64,
+    /// Query text, lowercased with whitespace collapsed
+    pub query: String,
+    /// Endpoint that served it, e.g. `search` or `aisearch`
+    pub endpoint: String,
+    /// Total number of results, not just the returned page
+    pub results: usize,
+    /// Time spent answering in milliseconds
+    pub latency_ms: u64,
+    /// Whether the AI name service answered; `false` for plain searches and
+    /// AI searches that fell back to plain fuse
+    pub ai: bool,
+}
+
+impl SearchEvent {
+    pub fn new(
+        timestamp: u64,
+        query: &str,
+        endpoint: &str,
+        results: usize,
+        latency_ms: u64,
+        ai: bool,
+    ) -> Self {
+        let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        Self {
+            timestamp,
+            query: words.join(" "),
+            endpoint: endpoint.to_string(),
+            results,
+            latency_ms,
+            ai,
+        }
+    }
+}
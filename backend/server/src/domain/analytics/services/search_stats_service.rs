@@ -0,0 +1,79 @@
+use crate::domain::analytics::entities::search_event::SearchEvent;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// How often a query was searched
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QueryCount {
+    pub query: String,
+    pub count: usize,
+}
+
+/// Latency distribution of a set of searches in milliseconds
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LatencyPercentiles {
+    pub count: usize,
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub max: u64,
+}
+
+/// Domain service aggregating recorded searches
+#[derive(Default)]
+pub struct SearchStatsService;
+
+impl SearchStatsService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Most searched queries, most frequent first, ties in query order
+    pub fn top_queries(&self, events: &[SearchEvent], limit: usize) -> Vec<QueryCount> {
+        Self::count(events.iter(), limit)
+    }
+
+    /// Most searched queries that found nothing
+    pub fn top_zero_result_queries(&self, events: &[SearchEvent], limit: usize) -> Vec<QueryCount> {
+        Self::count(events.iter().filter(|event| event.results == 0), limit)
+    }
+
+    /// Nearest-rank latency percentiles
+    pub fn latency_percentiles(&self, events: &[SearchEvent]) -> LatencyPercentiles {
+        let mut latencies: Vec<u64> = events.iter().map(|event| event.latency_ms).collect();
+        latencies.sort_unstable();
+
+        let Some(&max) = latencies.last() else {
+            return LatencyPercentiles::default();
+        };
+        let percentile = |p: usize| latencies[(p * latencies.len()).div_ceil(100).max(1) - 1];
+
+        LatencyPercentiles {
+            count: latencies.len(),
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max,
+        }
+    }
+
+    fn count<'a>(events: impl Iterator<Item = &'a SearchEvent>, limit: usize) -> Vec<QueryCount> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for event in events {
+            if !event.query.is_empty() {
+                *counts.entry(&event.query).or_default() += 1;
+            }
+        }
+
+        let mut counts: Vec<QueryCount> = counts
+            .into_iter()
+            .map(|(query, count)| QueryCount {
+                query: query.to_string(),
+                count,
+            })
+            .collect();
+        counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.query.cmp(&b.query)));
+        counts.truncate(limit);
+        counts
+    }
+}
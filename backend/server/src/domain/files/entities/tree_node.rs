@@ -1,4 +1,7 @@
 use crate::domain::files::entities::file_info::FileInfo;
+use crate::domain::files::services::directory_listing_service::DirectoryListingService;
+use crate::domain::files::value_objects::listing_order::{ListingSort, SortOrder};
+use crate::domain::search::value_objects::release_info::ReleaseInfo;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -35,10 +38,21 @@ impl<'a> From<NavigationResult<'a>> for Option<crate::interfaces::http::dto::fil
     fn from(result: NavigationResult<'a>) -> Self {
         match result {
             NavigationResult::Folder(node) => {
-                Some(crate::interfaces::http::dto::files_dto::Inode::Folder { data: node.into() })
+                let entries =
+                    DirectoryListingService::sorted(node, ListingSort::Name, SortOrder::Asc);
+                Some(crate::interfaces::http::dto::files_dto::Inode::Folder {
+                    total: entries.len(),
+                    next_cursor: None,
+                    data: entries.into_iter().map(Into::into).collect(),
+                })
             }
             NavigationResult::File { name, info } => {
-                Some(crate::interfaces::http::dto::files_dto::Inode::File { name, info })
+                let release = ReleaseInfo::parse(&name);
+                Some(crate::interfaces::http::dto::files_dto::Inode::File {
+                    name,
+                    info,
+                    release,
+                })
             }
             NavigationResult::NotFound => None,
         }
@@ -58,13 +72,12 @@ impl TreeNode {
                 Some(NodeType::Node(node)) => {
                     current = node;
                 }
-                Some(NodeType::File(info))
-                    if idx == path_segments.len() - 1 => {
-                        return NavigationResult::File {
-                            name: segment.clone(),
-                            info: info.clone(),
-                        };
-                    }
+                Some(NodeType::File(info)) if idx == path_segments.len() - 1 => {
+                    return NavigationResult::File {
+                        name: segment.clone(),
+                        info: info.clone(),
+                    };
+                }
                 Some(NodeType::File(_)) | None => return NavigationResult::NotFound,
             }
         }
@@ -72,17 +85,26 @@ impl TreeNode {
         NavigationResult::Folder(current)
     }
 
+    /// Children ordered by name, for walks whose output must not depend on hashing
+    pub fn sorted_children(&self) -> Vec<(&String, &NodeType)> {
+        let mut children: Vec<_> = self.0.iter().collect();
+        children.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        children
+    }
+
     /// Navigate to a node by path string with URL decoding support
     pub fn navigate_path<'a>(&'a self, path: &str) -> NavigationResult<'a> {
+        self.navigate(&Self::path_segments(path))
+    }
+
+    /// URL-decoded, non-empty segments of a slash-separated path
+    pub fn path_segments(path: &str) -> Vec<String> {
         use percent_encoding::percent_decode_str;
 
-        let segments: Vec<String> = path
-            .split('/')
+        path.split('/')
             .filter(|s| !s.is_empty())
             .map(|s| percent_decode_str(s).decode_utf8_lossy().to_string())
-            .collect();
-
-        self.navigate(&segments)
+            .collect()
     }
 }
 
@@ -118,20 +140,3 @@ impl From<&[FileInfo]> for TreeNode {
         root
     }
 }
-
-impl From<&TreeNode> for Vec<crate::interfaces::http::dto::files_dto::Node> {
-    fn from(tree: &TreeNode) -> Self {
-        tree.as_ref()
-            .iter()
-            .map(|(name, value)| match value {
-                NodeType::File(info) => crate::interfaces::http::dto::files_dto::Node::File {
-                    name: name.clone(),
-                    info: info.clone(),
-                },
-                NodeType::Node(_) => {
-                    crate::interfaces::http::dto::files_dto::Node::Folder { name: name.clone() }
-                }
-            })
-            .collect()
-    }
-}
@@ -1 +1,3 @@
 pub mod entities;
+pub mod services;
+pub mod value_objects;
@@ -0,0 +1,88 @@
+use crate::domain::files::entities::tree_node::{NodeType, TreeNode};
+use crate::domain::files::value_objects::listing_order::{ListingSort, SortOrder};
+use std::cmp::Ordering;
+
+/// Domain service ordering the entries of a folder
+///
+/// Folders always come before files. Folders have no size or date of their
+/// own, so they are ordered by name under every sort; `order` still applies.
+/// Entries that compare equal fall back to natural name order, then to the
+/// raw name, so a listing never depends on hash map order.
+pub struct DirectoryListingService;
+
+impl DirectoryListingService {
+    /// Entries of `folder`, sorted by `sort` in `order`
+    pub fn sorted(
+        folder: &TreeNode,
+        sort: ListingSort,
+        order: SortOrder,
+    ) -> Vec<(&String, &NodeType)> {
+        let mut entries: Vec<(&String, &NodeType)> = folder.as_ref().iter().collect();
+        entries.sort_by(|a, b| {
+            let by_kind = Self::is_file(a.1).cmp(&Self::is_file(b.1));
+            let by_key = match (sort, a.1, b.1) {
+                (ListingSort::Size, NodeType::File(x), NodeType::File(y)) => {
+                    x.file_size.cmp(&y.file_size)
+                }
+                (ListingSort::Date, NodeType::File(x), NodeType::File(y)) => {
+                    x.upload_timestamp.cmp(&y.upload_timestamp)
+                }
+                _ => Ordering::Equal,
+            }
+            .then_with(|| Self::natural_cmp(a.0, b.0))
+            .then_with(|| a.0.cmp(b.0));
+            let by_key = match order {
+                SortOrder::Asc => by_key,
+                SortOrder::Desc => by_key.reverse(),
+            };
+            by_kind.then(by_key)
+        });
+        entries
+    }
+
+    /// Case-insensitive comparison reading runs of ASCII digits as numbers
+    ///
+    /// `vol2` sorts before `vol10`, and `1001-1500` before `10001-10500`.
+    pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+        let mut a = a.chars().peekable();
+        let mut b = b.chars().peekable();
+        loop {
+            match (a.peek().copied(), b.peek().copied()) {
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                    let x = Self::take_number(&mut a);
+                    let y = Self::take_number(&mut b);
+                    let ordering = x.len().cmp(&y.len()).then_with(|| x.cmp(&y));
+                    if ordering != Ordering::Equal {
+                        return ordering;
+                    }
+                }
+                (Some(x), Some(y)) => {
+                    let ordering = x.to_lowercase().cmp(y.to_lowercase());
+                    if ordering != Ordering::Equal {
+                        return ordering;
+                    }
+                    a.next();
+                    b.next();
+                }
+            }
+        }
+    }
+
+    /// Digits of the number starting at `chars`, without leading zeros
+    fn take_number(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+        let mut digits = String::new();
+        while let Some(c) = chars.next_if(char::is_ascii_digit) {
+            if !(digits.is_empty() && c == '0') {
+                digits.push(c);
+            }
+        }
+        digits
+    }
+
+    fn is_file(node: &NodeType) -> bool {
+        matches!(node, NodeType::File(_))
+    }
+}
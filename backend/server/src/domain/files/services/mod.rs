@@ -0,0 +1 @@
+pub mod directory_listing_service;
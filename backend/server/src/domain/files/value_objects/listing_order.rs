@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// Key a directory listing is sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListingSort {
+    /// Natural name order, `2.rar` before `10.rar`
+    #[default]
+    Name,
+    /// File size in bytes
+    Size,
+    /// Upload time
+    Date,
+}
+
+impl ListingSort {
+    /// Order used when none is given: A to Z for names, biggest and newest
+    /// first for sizes and dates
+    pub fn default_order(self) -> SortOrder {
+        match self {
+            Self::Name => SortOrder::Asc,
+            Self::Size | Self::Date => SortOrder::Desc,
+        }
+    }
+}
+
+/// Direction of a sorted listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
@@ -0,0 +1 @@
+pub mod listing_order;
@@ -1,3 +1,4 @@
+pub mod analytics;
 pub mod files;
 pub mod search;
 pub mod wiki;
@@ -0,0 +1,117 @@
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
+use std::collections::{BTreeMap, HashSet};
+
+/// Weight of a query variant produced by swapping in an alias
+pub const ALIAS_WEIGHT: f64 = 0.8;
+/// Most alias variants one query expands into
+pub const MAX_ALIAS_VARIANTS: usize = 8;
+
+/// Groups of names that refer to the same game
+///
+/// Each group is keyed by its canonical name and lists the other names the
+/// game goes by: translations, romanizations and fan abbreviations such as
+/// `魔夜` or `FD`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AliasDictionary {
+    groups: BTreeMap<String, Vec<String>>,
+}
+
+impl AliasDictionary {
+    /// Build a dictionary from canonical names mapped to their aliases
+    ///
+    /// Blank names are dropped, and so are groups left without any alias.
+    pub fn new(groups: BTreeMap<String, Vec<String>>) -> Self {
+        let groups = groups
+            .into_iter()
+            .filter_map(|(canonical, aliases)| {
+                let canonical = canonical.trim().to_string();
+                let aliases: Vec<String> = aliases
+                    .into_iter()
+                    .map(|alias| alias.trim().to_string())
+                    .filter(|alias| !alias.is_empty() && *alias != canonical)
+                    .collect();
+                (!canonical.is_empty() && !aliases.is_empty()).then_some((canonical, aliases))
+            })
+            .collect();
+        Self { groups }
+    }
+
+    /// Apply an overlay on top of the dictionary
+    ///
+    /// An overlay group replaces the group with the same canonical name; an
+    /// overlay group without aliases removes it.
+    pub fn with_overlay(self, overlay: BTreeMap<String, Vec<String>>) -> Self {
+        let mut groups = self.groups;
+        for (canonical, aliases) in overlay {
+            groups.remove(canonical.trim());
+            groups.insert(canonical, aliases);
+        }
+        Self::new(groups)
+    }
+
+    /// Canonical names mapped to their aliases
+    pub fn groups(&self) -> &BTreeMap<String, Vec<String>> {
+        &self.groups
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Alternative queries for `query`, each with one known name swapped for
+    /// another name of the same game
+    ///
+    /// Names match case-insensitively and only as whole words, so `FD` does
+    /// not match inside `FDX`; CJK names match anywhere. The raw query itself
+    /// is not part of the result.
+    pub fn expand(&self, query: &str) -> Vec<WeightedQuery> {
+        let normalized = query
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        let mut seen = HashSet::from([normalized.clone()]);
+        let mut variants = Vec::new();
+
+        for (canonical, aliases) in &self.groups {
+            let names: Vec<&String> = std::iter::once(canonical).chain(aliases).collect();
+            let Some((start, end)) = names
+                .iter()
+                .filter_map(|name| Self::find_word(&normalized, &name.to_lowercase()))
+                .max_by_key(|(start, end)| (end - start, std::cmp::Reverse(*start)))
+            else {
+                continue;
+            };
+
+            let matched = &normalized[start..end];
+            for name in names {
+                if name.to_lowercase() == matched {
+                    continue;
+                }
+                let text = format!("{}{name}{}", &normalized[..start], &normalized[end..]);
+                if seen.insert(text.to_lowercase()) {
+                    variants.push(WeightedQuery::new(text, ALIAS_WEIGHT));
+                }
+                if variants.len() == MAX_ALIAS_VARIANTS {
+                    return variants;
+                }
+            }
+        }
+
+        variants
+    }
+
+    /// Byte range of the first whole-word occurrence of `name` in `text`
+    fn find_word(text: &str, name: &str) -> Option<(usize, usize)> {
+        if name.is_empty() {
+            return None;
+        }
+        text.match_indices(name).find_map(|(start, _)| {
+            let end = start + name.len();
+            let before = text[..start].chars().next_back();
+            let after = text[end..].chars().next();
+            let is_boundary = |c: Option<char>| c.is_none_or(|c| !c.is_ascii_alphanumeric());
+            (is_boundary(before) && is_boundary(after)).then_some((start, end))
+        })
+    }
+}
@@ -0,0 +1,120 @@
+use crate::domain::search::entities::search_item::SearchItem;
+use crate::domain::search::value_objects::item_kind::ItemKind;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Copies of one release stored at several paths
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DuplicateGroup {
+    /// File name of the first location
+    pub name: String,
+    /// Size in bytes shared by every copy
+    pub file_size: u64,
+    /// Bucket paths of every copy, sorted
+    pub locations: Vec<String>,
+}
+
+impl DuplicateGroup {
+    /// Bytes taken up by all copies but one
+    pub fn wasted_bytes(&self) -> u64 {
+        self.file_size * (self.locations.len() as u64 - 1)
+    }
+}
+
+/// Likely duplicate files of the search index
+///
+/// Two files are duplicates when they have the exact same size and the same
+/// normalized name: the parsed title in lowercase with everything but letters
+/// and digits dropped, plus the extension. Date and brand tags, case and
+/// punctuation thus do not matter, so `[180629][Key] Summer Pockets.rar` in
+/// one bucket matches `summer_pockets.rar` in another. Empty files are never
+/// duplicates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DuplicateIndex {
+    /// Groups with the most wasted bytes first
+    groups: Vec<DuplicateGroup>,
+    /// Group position of every path that has a duplicate
+    by_path: HashMap<String, usize>,
+}
+
+impl DuplicateIndex {
+    /// Find the duplicate files among `items`; folders are skipped
+    pub fn build(items: &[SearchItem]) -> Self {
+        let mut candidates: HashMap<(String, u64), Vec<String>> = HashMap::new();
+        for item in items {
+            if item.kind != ItemKind::File || item.info.file_size == 0 {
+                continue;
+            }
+            candidates
+                .entry((Self::normalized_name(item), item.info.file_size))
+                .or_default()
+                .push(item.info.file_path.to_string());
+        }
+
+        let mut groups: Vec<DuplicateGroup> = candidates
+            .into_iter()
+            .filter(|(_, locations)| locations.len() > 1)
+            .map(|((_, file_size), mut locations)| {
+                locations.sort();
+                locations.dedup();
+                let name = locations[0]
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or(&locations[0])
+                    .to_string();
+                DuplicateGroup {
+                    name,
+                    file_size,
+                    locations,
+                }
+            })
+            .filter(|group| group.locations.len() > 1)
+            .collect();
+        groups.sort_by(|a, b| {
+            b.wasted_bytes()
+                .cmp(&a.wasted_bytes())
+                .then_with(|| a.locations.cmp(&b.locations))
+        });
+
+        let by_path = groups
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, group)| group.locations.iter().map(move |path| (path.clone(), idx)))
+            .collect();
+        Self { groups, by_path }
+    }
+
+    pub fn groups(&self) -> &[DuplicateGroup] {
+        &self.groups
+    }
+
+    /// Group of the file at `path`, `None` if it has no duplicate
+    pub fn group_of(&self, path: &str) -> Option<&DuplicateGroup> {
+        self.by_path.get(path).map(|&idx| &self.groups[idx])
+    }
+
+    /// Number of files that have at least one duplicate
+    pub fn files(&self) -> usize {
+        self.by_path.len()
+    }
+
+    /// Bytes that removing every duplicate would free
+    pub fn wasted_bytes(&self) -> u64 {
+        self.groups.iter().map(DuplicateGroup::wasted_bytes).sum()
+    }
+
+    fn normalized_name(item: &SearchItem) -> String {
+        let mut name: String = item
+            .release
+            .title
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect();
+        if let Some(extension) = &item.release.extension {
+            name.push('.');
+            name.push_str(extension);
+        }
+        name
+    }
+}
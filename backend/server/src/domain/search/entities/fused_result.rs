@@ -0,0 +1,19 @@
+use crate::domain::search::entities::search_item::SearchItem;
+use crate::domain::search::value_objects::score::Score;
+
+/// A search item found by a fused multi-query search
+#[derive(Debug, Clone, PartialEq)]
+pub struct FusedResult {
+    pub item: SearchItem,
+    /// Index of the query variant that contributed most to the item's rank
+    pub query: usize,
+    /// Fused value, lower is better: a weighted fuse score for average and
+    /// min fusion, a negated reciprocal rank sum for RRF
+    pub score: Score,
+}
+
+impl FusedResult {
+    pub fn new(item: SearchItem, query: usize, score: Score) -> Self {
+        Self { item, query, score }
+    }
+}
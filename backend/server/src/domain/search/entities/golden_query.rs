@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// A query of a golden set together with the files a searcher expects for it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoldenQuery {
+    /// Query as typed into the search box
+    #[serde(rename = "q")]
+    pub query: String,
+    /// Bucket paths (`info.file_path`) of the relevant files, any order
+    pub relevant: Vec<String>,
+}
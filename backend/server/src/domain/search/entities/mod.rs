@@ -1,2 +1,10 @@
+pub mod alias_dictionary;
+pub mod duplicate_index;
+pub mod fused_result;
+pub mod golden_query;
+pub mod popularity_snapshot;
+pub mod query_vocabulary;
+pub mod relevance_report;
 pub mod search_item;
 pub mod search_result;
+pub mod suggest_index;
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+/// Download popularity of files at one point in time
+///
+/// Scores are decayed click counts keyed by bucket file path; files nobody
+/// opened are absent and count as 0.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PopularitySnapshot {
+    /// Increases with every refresh, so rankings built on an older snapshot can be told apart
+    pub generation: u64,
+    scores: HashMap<String, f64>,
+}
+
+impl PopularitySnapshot {
+    pub fn new(generation: u64, scores: HashMap<String, f64>) -> Self {
+        Self { generation, scores }
+    }
+
+    /// Popularity of the file at `file_path`
+    pub fn score(&self, file_path: &str) -> f64 {
+        self.scores.get(file_path).copied().unwrap_or_default()
+    }
+
+    /// Popularity of `file_paths`, with clicks from one query counting extra
+    ///
+    /// `query` holds the clicks made from the searched query; each adds
+    /// `query_weight` on top of the overall popularity it is also part of.
+    pub fn blend<'a>(
+        &self,
+        query: &PopularitySnapshot,
+        query_weight: f64,
+        file_paths: impl IntoIterator<Item = &'a str>,
+    ) -> PopularitySnapshot {
+        let scores = file_paths
+            .into_iter()
+            .map(|path| {
+                let score = self.score(path) + query_weight * query.score(path);
+                (path.to_string(), score)
+            })
+            .collect();
+        PopularitySnapshot::new(self.generation, scores)
+    }
+
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+}
@@ -0,0 +1,140 @@
+use crate::domain::search::entities::search_item::SearchList;
+use std::collections::{HashMap, HashSet};
+
+/// Vocabulary of title tokens found in the search index and how often each occurs
+///
+/// Used to propose "did you mean" rewrites of queries that match nothing.
+#[derive(Debug, Clone, Default)]
+pub struct QueryVocabulary {
+    frequencies: HashMap<String, usize>,
+    /// Tokens bucketed by character count, so only plausible lengths are compared
+    by_length: HashMap<usize, Vec<String>>,
+}
+
+impl QueryVocabulary {
+    /// Collect tokens from every indexed path
+    pub fn build(items: &SearchList) -> Self {
+        let mut frequencies: HashMap<String, usize> = HashMap::new();
+        for item in items {
+            let name = match item.id.rsplit_once('.') {
+                Some((stem, _)) => stem,
+                None => &item.id,
+            };
+            for token in Self::tokenize(name) {
+                *frequencies.entry(token).or_default() += 1;
+            }
+        }
+
+        let mut by_length: HashMap<usize, Vec<String>> = HashMap::new();
+        for token in frequencies.keys() {
+            by_length
+                .entry(token.chars().count())
+                .or_default()
+                .push(token.clone());
+        }
+
+        Self {
+            frequencies,
+            by_length,
+        }
+    }
+
+    /// Lowercase word tokens of `text`, skipping single characters and bare numbers
+    pub fn tokenize(text: &str) -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| word.chars().count() > 1 && !word.chars().all(|c| c.is_ascii_digit()))
+            .map(str::to_lowercase)
+            .collect()
+    }
+
+    /// Up to `limit` vocabulary tokens close to `token`, closest and most frequent first
+    pub fn corrections(&self, token: &str, limit: usize) -> Vec<String> {
+        let token: Vec<char> = token.chars().collect();
+        let max_distance = Self::max_distance(token.len());
+
+        let mut candidates: Vec<(usize, usize, &String)> = Vec::new();
+        for len in token.len().saturating_sub(max_distance)..=token.len() + max_distance {
+            for word in self.by_length.get(&len).into_iter().flatten() {
+                let distance = Self::edit_distance(&token, word);
+                if distance <= max_distance {
+                    candidates.push((distance, self.frequencies[word], word));
+                }
+            }
+        }
+
+        candidates.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.cmp(b.2)));
+        candidates
+            .into_iter()
+            .take(limit)
+            .map(|(_, _, word)| word.clone())
+            .collect()
+    }
+
+    /// Up to `limit` rewrites of `query` with unknown tokens replaced by close known ones
+    ///
+    /// The first rewrite uses the best correction for every token, the rest
+    /// swap in runner-up corrections one token at a time.
+    pub fn suggest_queries(&self, query: &str, limit: usize) -> Vec<String> {
+        let tokens = Self::tokenize(query);
+        let options: Vec<Vec<String>> = tokens
+            .iter()
+            .map(|token| {
+                if self.frequencies.contains_key(token) {
+                    vec![token.clone()]
+                } else {
+                    let corrections = self.corrections(token, limit);
+                    if corrections.is_empty() {
+                        vec![token.clone()]
+                    } else {
+                        corrections
+                    }
+                }
+            })
+            .collect();
+
+        let best: Vec<&str> = options.iter().map(|opts| opts[0].as_str()).collect();
+        let mut rewrites = vec![best.join(" ")];
+        for (idx, opts) in options.iter().enumerate() {
+            for alternative in opts.iter().skip(1) {
+                let mut words = best.clone();
+                words[idx] = alternative;
+                rewrites.push(words.join(" "));
+            }
+        }
+
+        let original = tokens.join(" ");
+        let mut seen = HashSet::new();
+        rewrites
+            .into_iter()
+            .filter(|rewrite| *rewrite != original && seen.insert(rewrite.clone()))
+            .take(limit)
+            .collect()
+    }
+
+    /// Edits tolerated for a token of `len` characters
+    fn max_distance(len: usize) -> usize {
+        match len {
+            0..=4 => 1,
+            5..=8 => 2,
+            _ => 3,
+        }
+    }
+
+    /// Levenshtein distance between two strings, counted in characters
+    fn edit_distance(a: &[char], b: &str) -> usize {
+        let b: Vec<char> = b.chars().collect();
+        let mut prev: Vec<usize> = (0..=b.len()).collect();
+        let mut curr = vec![0; b.len() + 1];
+
+        for (i, ca) in a.iter().enumerate() {
+            curr[0] = i + 1;
+            for (j, cb) in b.iter().enumerate() {
+                let substitution = prev[j] + usize::from(ca != cb);
+                curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+            }
+            std::mem::swap(&mut prev, &mut curr);
+        }
+
+        prev[b.len()]
+    }
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Ranking quality of one golden query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryEvaluation {
+    pub query: String,
+    /// 1-based rank of the first relevant hit, `None` if none was found
+    pub first_relevant: Option<usize>,
+    pub reciprocal_rank: f64,
+    pub ndcg: f64,
+    /// Share of the relevant files found within the first `k` hits, keyed by `k`
+    pub recall: BTreeMap<usize, f64>,
+}
+
+/// Ranking quality of a whole golden set, averaged over its queries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelevanceReport {
+    /// Mean reciprocal rank
+    pub mrr: f64,
+    /// Mean NDCG over the first [`Self::ndcg_cutoff`] hits
+    pub ndcg: f64,
+    pub ndcg_cutoff: usize,
+    /// Mean recall, keyed by cutoff
+    pub recall: BTreeMap<usize, f64>,
+    pub queries: Vec<QueryEvaluation>,
+}
+
+/// One metric of a run next to the same metric of the baseline run
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricChange {
+    /// Golden query the metric belongs to, `None` for the whole set
+    pub query: Option<String>,
+    pub metric: String,
+    pub baseline: f64,
+    pub current: f64,
+}
+
+impl MetricChange {
+    pub fn delta(&self) -> f64 {
+        self.current - self.baseline
+    }
+}
+
+/// Differences between a run and a baseline run of the same golden set
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelevanceDiff {
+    /// Drops up to this size count as noise, not as regressions
+    pub tolerance: f64,
+    /// Set-wide metrics first, then the per-query metrics that moved
+    pub changes: Vec<MetricChange>,
+    /// Golden queries the baseline has no result for
+    pub new_queries: Vec<String>,
+}
+
+impl RelevanceDiff {
+    /// Metrics that dropped by more than the tolerance
+    pub fn regressions(&self) -> impl Iterator<Item = &MetricChange> {
+        self.changes
+            .iter()
+            .filter(|change| change.delta() < -self.tolerance)
+    }
+
+    pub fn has_regressions(&self) -> bool {
+        self.regressions().next().is_some()
+    }
+}
+
+impl fmt::Display for RelevanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<12} {}", "queries", self.queries.len())?;
+        writeln!(f, "{:<12} {:.3}", "MRR", self.mrr)?;
+        writeln!(
+            f,
+            "{:<12} {:.3}",
+            format!("NDCG@{}", self.ndcg_cutoff),
+            self.ndcg
+        )?;
+        for (k, recall) in &self.recall {
+            writeln!(f, "{:<12} {recall:.3}", format!("recall@{k}"))?;
+        }
+        for query in &self.queries {
+            let rank = query
+                .first_relevant
+                .map_or_else(|| "-".to_string(), |rank| rank.to_string());
+            writeln!(f, "  {rank:>4}  {}", query.query)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RelevanceDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            let marker = if change.delta() < -self.tolerance {
+                "!"
+            } else {
+                " "
+            };
+            let name = match &change.query {
+                Some(query) => format!("{} [{query}]", change.metric),
+                None => change.metric.clone(),
+            };
+            // Stored baselines round-trip through JSON, hide the noise that leaves
+            let delta = if change.delta().abs() < 0.0005 {
+                0.0
+            } else {
+                change.delta()
+            };
+            writeln!(
+                f,
+                "{marker} {name:<32} {:.3} -> {:.3} ({delta:+.3})",
+                change.baseline, change.current
+            )?;
+        }
+        for query in &self.new_queries {
+            writeln!(f, "+ new query [{query}]")?;
+        }
+        Ok(())
+    }
+}
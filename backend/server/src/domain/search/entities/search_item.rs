@@ -1,11 +1,25 @@
 use crate::domain::files::entities::file_info::FileInfo;
+use crate::domain::search::value_objects::item_kind::ItemKind;
+use crate::domain::search::value_objects::release_info::ReleaseInfo;
 use serde::{Deserialize, Serialize};
 
 /// Search item for indexing and searching
+///
+/// Folders are indexed too: their `info` holds the folder path, the total
+/// size of every file below it and the newest upload time among them.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SearchItem {
     pub id: String,
     pub info: FileInfo,
+    /// Date, brand and title parsed from the file name
+    #[serde(default)]
+    pub release: ReleaseInfo,
+    /// File or folder, serialized as `type`
+    #[serde(rename = "type", default)]
+    pub kind: ItemKind,
+    /// Number of files below a folder, absent for files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_count: Option<usize>,
 }
 
 pub type SearchList = Vec<SearchItem>;
@@ -1 +1,15 @@
-// SearchResult entity
+use crate::domain::search::entities::search_item::SearchItem;
+use crate::domain::search::value_objects::score::Score;
+
+/// A search item matched by a query, together with how well it matched
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub item: SearchItem,
+    pub score: Score,
+}
+
+impl SearchResult {
+    pub fn new(item: SearchItem, score: Score) -> Self {
+        Self { item, score }
+    }
+}
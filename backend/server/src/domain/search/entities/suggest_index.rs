@@ -0,0 +1,194 @@
+use crate::domain::files::entities::tree_node::{NodeType, TreeNode};
+use crate::domain::search::value_objects::release_info::ReleaseInfo;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Upper bound of prefix matches inspected per lookup, keeps short prefixes cheap
+const MAX_SCANNED: usize = 512;
+
+/// Kind of tree node a suggestion points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SuggestionKind {
+    #[serde(rename = "folder")]
+    Folder,
+    #[serde(rename = "file")]
+    File,
+}
+
+/// A completion offered while the user types
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Suggestion {
+    /// Display title, e.g. a game title or folder name
+    pub text: String,
+    /// Path of the node in the combined tree
+    pub path: String,
+    #[serde(rename = "type")]
+    pub kind: SuggestionKind,
+}
+
+/// Prefix index over game titles and folder names
+///
+/// Keys are kept in one sorted array, so a lookup is a binary search for the
+/// first key with the prefix followed by a short forward scan. Every title is
+/// indexed from its start and from the start of each later word, so typing
+/// `hulotte` finds `[181026][hulotte] 出会って5分は俺のもの！`.
+#[derive(Debug, Clone, Default)]
+pub struct SuggestIndex {
+    entries: Vec<Suggestion>,
+    /// Normalized full name of each entry, the text the keys point into
+    sources: Vec<String>,
+    /// Keys sorted by the text they point at
+    keys: Vec<SuggestKey>,
+}
+
+/// Suffix of an entry's normalized name starting at a word boundary
+#[derive(Debug, Clone, Copy)]
+struct SuggestKey {
+    entry: usize,
+    offset: usize,
+    /// Whether the suffix starts with the display title rather than a tag
+    at_title: bool,
+}
+
+impl SuggestIndex {
+    /// Build the index from the combined tree
+    pub fn build(tree: &TreeNode) -> Self {
+        let mut index = Self::default();
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        index.collect(tree, "", &mut seen);
+
+        let mut keys = std::mem::take(&mut index.keys);
+        keys.sort_unstable_by(|a, b| index.key_text(a).cmp(index.key_text(b)));
+        index.keys = keys;
+        index
+    }
+
+    /// Up to `limit` suggestions whose title or one of its words starts with `prefix`
+    ///
+    /// Titles starting with the prefix rank first, then shorter titles.
+    pub fn suggest(&self, prefix: &str, limit: usize) -> Vec<Suggestion> {
+        let prefix = Self::normalize(prefix);
+        if prefix.is_empty() || limit == 0 {
+            return Vec::new();
+        }
+
+        let start = self
+            .keys
+            .partition_point(|key| self.key_text(key) < prefix.as_str());
+
+        let mut best: HashMap<usize, bool> = HashMap::new();
+        for key in self.keys[start..].iter().take(MAX_SCANNED) {
+            if !self.key_text(key).starts_with(&prefix) {
+                break;
+            }
+            *best.entry(key.entry).or_default() |= key.at_title;
+        }
+
+        let mut ranked: Vec<(usize, bool)> = best.into_iter().collect();
+        ranked.sort_by(|(a, a_start), (b, b_start)| {
+            b_start
+                .cmp(a_start)
+                .then_with(|| {
+                    self.entries[*a]
+                        .text
+                        .chars()
+                        .count()
+                        .cmp(&self.entries[*b].text.chars().count())
+                })
+                .then_with(|| self.entries[*a].text.cmp(&self.entries[*b].text))
+                .then_with(|| a.cmp(b))
+        });
+
+        ranked
+            .into_iter()
+            .take(limit)
+            .map(|(entry, _)| self.entries[entry].clone())
+            .collect()
+    }
+
+    fn collect(&mut self, node: &TreeNode, parent: &str, seen: &mut HashMap<String, usize>) {
+        // In name order, so which of two equal titles is kept does not vary
+        for (name, child) in node.sorted_children() {
+            let path = if parent.is_empty() {
+                name.clone()
+            } else {
+                format!("{parent}/{name}")
+            };
+
+            let (text, kind, stem) = match child {
+                NodeType::Node(sub) => {
+                    self.collect(sub, &path, seen);
+                    let text = name.trim().to_string();
+                    (text.clone(), SuggestionKind::Folder, text)
+                }
+                NodeType::File(_) => {
+                    let stem = Self::stem_of(name);
+                    let title = ReleaseInfo::parse(name).title;
+                    (title, SuggestionKind::File, stem.to_string())
+                }
+            };
+
+            // Bare numbers and ranges like `1001-1500` are not useful completions
+            if !text.chars().any(char::is_alphabetic) {
+                continue;
+            }
+
+            let normalized = Self::normalize(&text);
+            match seen.get(&normalized) {
+                // Prefer pointing at the folder when a title exists as both
+                Some(&idx) => {
+                    if kind == SuggestionKind::Folder
+                        && self.entries[idx].kind == SuggestionKind::File
+                    {
+                        self.entries[idx] = Suggestion { text, path, kind };
+                    }
+                }
+                None => {
+                    let entry = self.entries.len();
+                    // Index every word of the full name, so tags like the brand are searchable
+                    let source = Self::normalize(&stem);
+                    for offset in Self::word_starts(&source) {
+                        self.keys.push(SuggestKey {
+                            entry,
+                            offset,
+                            at_title: source[offset..].starts_with(&normalized),
+                        });
+                    }
+                    seen.insert(normalized, entry);
+                    self.sources.push(source);
+                    self.entries.push(Suggestion { text, path, kind });
+                }
+            }
+        }
+    }
+
+    fn key_text(&self, key: &SuggestKey) -> &str {
+        &self.sources[key.entry][key.offset..]
+    }
+
+    /// File name without its extension
+    fn stem_of(file_name: &str) -> &str {
+        match file_name.rsplit_once('.') {
+            Some((stem, ext)) if !stem.is_empty() && ext.chars().all(char::is_alphanumeric) => stem,
+            _ => file_name,
+        }
+    }
+
+    /// Byte offsets in `text` where a word starts, always including 0
+    fn word_starts(text: &str) -> Vec<usize> {
+        let mut starts = vec![0];
+        let mut prev_is_word = false;
+        for (offset, c) in text.char_indices() {
+            let is_word = c.is_alphanumeric();
+            if is_word && !prev_is_word && offset != 0 {
+                starts.push(offset);
+            }
+            prev_is_word = is_word;
+        }
+        starts
+    }
+
+    fn normalize(text: &str) -> String {
+        text.trim().to_lowercase()
+    }
+}
@@ -1,4 +1,8 @@
+use crate::domain::search::entities::fused_result::FusedResult;
 use crate::domain::search::entities::search_item::SearchList;
+use crate::domain::search::entities::search_result::SearchResult;
+use crate::domain::search::value_objects::fusion_strategy::FusionStrategy;
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
 
 /// Repository trait for performing fuzzy search operations on search items.
 ///
@@ -6,28 +10,42 @@ use crate::domain::search::entities::search_item::SearchList;
 /// implementations (like Fuse, Elasticsearch, etc.) to be used without
 /// affecting the domain logic.
 pub trait FuzzySearchRepository {
-    /// Performs a single-query fuzzy search on the provided search items.
+    /// Performs a single-query fuzzy search and keeps the match scores.
     ///
     /// # Arguments
     /// * `query` - The search query string
     /// * `items` - The collection of items to search through
     ///
     /// # Returns
-    /// A filtered and sorted collection of search items that match the query
-    fn search(&self, query: &str, items: &SearchList) -> SearchList;
+    /// The matching items with their scores, best match first
+    fn search_scored(&self, query: &str, items: &SearchList) -> Vec<SearchResult>;
 
-    /// Performs a combined fuzzy search using two queries.
+    /// Performs a fuzzy search for several weighted query variants and fuses
+    /// their rankings.
     ///
-    /// This method searches for items that match either query and combines
-    /// the results with appropriate scoring.
-    ///
-    /// # Arguments  
-    /// * `q1` - The first search query
-    /// * `q2` - The second search query
+    /// # Arguments
+    /// * `queries` - The query variants with their weights
+    /// * `strategy` - How the per-variant rankings are merged
     /// * `limit` - Maximum number of results to return
     /// * `items` - The collection of items to search through
     ///
     /// # Returns
-    /// A filtered, scored, and limited collection of search items
-    fn combined_search(&self, q1: &str, q2: &str, limit: usize, items: &SearchList) -> SearchList;
+    /// Items matching any variant, best fused rank first, at most `limit`,
+    /// each with the variant that contributed most to its rank
+    fn fused_search(
+        &self,
+        queries: &[WeightedQuery],
+        strategy: FusionStrategy,
+        limit: usize,
+        items: &SearchList,
+    ) -> Vec<FusedResult>;
+
+    /// Whether a search stopped early, at its time budget or because it was
+    /// cancelled, so its results only cover part of the items.
+    ///
+    /// # Returns
+    /// `true` once any search through this repository was cut short
+    fn is_partial(&self) -> bool {
+        false
+    }
 }
@@ -1,3 +1,7 @@
+pub mod popularity_service;
+pub mod rank_fusion_service;
+pub mod related_items_service;
+pub mod relevance_metrics_service;
 pub mod search_index_service;
 
 // Domain services contain pure business logic
@@ -0,0 +1,145 @@
+use crate::domain::search::entities::fused_result::FusedResult;
+use crate::domain::search::entities::popularity_snapshot::PopularitySnapshot;
+use crate::domain::search::entities::search_item::SearchItem;
+use crate::domain::search::entities::search_result::SearchResult;
+use crate::domain::search::value_objects::score::Score;
+use std::time::Duration;
+
+/// Start of the first era of the click counters, 2025-01-01T00:00:00Z in milliseconds
+pub const DECAY_EPOCH_MS: u64 = 1_735_689_600_000;
+
+/// Half-lives per era, so a click adds less than `2^ERA_HALF_LIVES`
+pub const ERA_HALF_LIVES: u32 = 32;
+
+/// A ranked result whose score popularity can be blended into
+pub trait Ranked: Clone {
+    fn item(&self) -> &SearchItem;
+    /// Ranking score, lower is better
+    fn score(&self) -> Score;
+    fn set_score(&mut self, score: Score);
+}
+
+impl Ranked for SearchResult {
+    fn item(&self) -> &SearchItem {
+        &self.item
+    }
+
+    fn score(&self) -> Score {
+        self.score
+    }
+
+    fn set_score(&mut self, score: Score) {
+        self.score = score;
+    }
+}
+
+impl Ranked for FusedResult {
+    fn item(&self) -> &SearchItem {
+        &self.item
+    }
+
+    fn score(&self) -> Score {
+        self.score
+    }
+
+    fn set_score(&mut self, score: Score) {
+        self.score = score;
+    }
+}
+
+/// Domain service for decaying click counts and blending them into rankings
+///
+/// Clicks decay exponentially with a fixed half-life. Instead of rewriting
+/// every counter over time, each click adds `2^(time since the era start /
+/// half-life)`, and reads divide by the same factor for the read time. Newer
+/// clicks thus weigh more and stored counters only grow within an era.
+///
+/// Eras last [`ERA_HALF_LIVES`] half-lives, which keeps the factors far from
+/// overflowing and precise. The counters of an era start out as those of the
+/// previous one scaled by [`Self::rebase_factor`].
+pub struct PopularityService {
+    half_life: Duration,
+}
+
+impl PopularityService {
+    pub fn new(half_life: Duration) -> Self {
+        Self { half_life }
+    }
+
+    /// Era of the counters a click at `now_ms` goes to
+    pub fn era(&self, now_ms: u64) -> u64 {
+        self.since_epoch(now_ms) / self.era_millis()
+    }
+
+    /// Factor carrying counters of one era over into the next
+    pub fn rebase_factor() -> f64 {
+        (-f64::from(ERA_HALF_LIVES)).exp2()
+    }
+
+    /// Amount a click at `now_ms` adds to a stored counter of its era
+    pub fn click_increment(&self, now_ms: u64) -> f64 {
+        self.growth(now_ms)
+    }
+
+    /// Decayed popularity at `now_ms` of a stored counter of the era of `now_ms`
+    pub fn decayed(&self, stored: f64, now_ms: u64) -> f64 {
+        stored / self.growth(now_ms)
+    }
+
+    /// Re-rank the `window` best results, blending their scores with popularity
+    ///
+    /// Popularity is log-scaled against the most popular result in the window
+    /// to `0..=1`, then subtracted from the fuse score with `weight`. Results
+    /// outside the window keep their place, so a popular file that barely
+    /// matches cannot jump to the top.
+    pub fn rerank<R: Ranked>(
+        results: &mut [R],
+        popularity: &PopularitySnapshot,
+        weight: f64,
+        window: usize,
+    ) {
+        let window = window.min(results.len());
+        let head = &mut results[..window];
+
+        let popularity_of = |result: &R| popularity.score(&result.item().info.file_path).ln_1p();
+        let max = head.iter().map(popularity_of).fold(0.0, f64::max);
+        if max <= 0.0 {
+            return;
+        }
+
+        let mut blended: Vec<(f64, R)> = head
+            .iter()
+            .map(|result| {
+                let score = result.score().value() - weight * popularity_of(result) / max;
+                (score, result.clone())
+            })
+            .collect();
+        // Stable, so equal blends keep their relevance order
+        blended.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        for (slot, (score, mut result)) in head.iter_mut().zip(blended) {
+            result.set_score(Score::new(score));
+            *slot = result;
+        }
+    }
+
+    fn growth(&self, now_ms: u64) -> f64 {
+        let age = (self.since_epoch(now_ms) % self.era_millis()) as f64;
+        (age / self.half_life_millis() as f64).exp2()
+    }
+
+    fn since_epoch(&self, now_ms: u64) -> u64 {
+        now_ms.saturating_sub(DECAY_EPOCH_MS)
+    }
+
+    fn era_millis(&self) -> u64 {
+        self.half_life_millis()
+            .saturating_mul(u64::from(ERA_HALF_LIVES))
+    }
+
+    fn half_life_millis(&self) -> u64 {
+        u64::try_from(self.half_life.as_millis())
+            .unwrap_or(u64::MAX)
+            .max(1)
+    }
+}
@@ -0,0 +1,92 @@
+use crate::domain::search::value_objects::fusion_strategy::FusionStrategy;
+use std::collections::HashMap;
+
+/// Rank offset of reciprocal rank fusion, damps the influence of the top ranks
+const RRF_K: f64 = 60.0;
+
+/// Domain service merging the ranked results of several query variants
+///
+/// Works on item indices and fuse scores (0.0 is a perfect match), so the
+/// same fusion applies to any search backend.
+#[derive(Default)]
+pub struct RankFusionService;
+
+impl RankFusionService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Fuse per-variant result lists into one ranking, best first
+    ///
+    /// Each list is `(weight, results)` with results sorted best first. Items
+    /// appearing in any list are kept; ties keep index order. Every item comes
+    /// as `(index, variant, value)`: the index of the list that contributed
+    /// most to its rank and its fused value, lower is better.
+    pub fn fuse(
+        &self,
+        lists: &[(f64, Vec<(usize, f64)>)],
+        strategy: FusionStrategy,
+    ) -> Vec<(usize, usize, f64)> {
+        let mut fused: HashMap<usize, FusedEntry> = HashMap::new();
+
+        for (variant, (weight, results)) in lists.iter().enumerate() {
+            for (rank, &(idx, score)) in results.iter().enumerate() {
+                // What this variant adds to the item's rank, lower is better
+                let contribution = match strategy {
+                    FusionStrategy::Average | FusionStrategy::Min => score / weight,
+                    FusionStrategy::Rrf => -weight / (RRF_K + rank as f64 + 1.0),
+                };
+
+                let entry = fused.entry(idx).or_insert(FusedEntry {
+                    value: match strategy {
+                        FusionStrategy::Min => f64::INFINITY,
+                        _ => 0.0,
+                    },
+                    weights: 0.0,
+                    best_variant: variant,
+                    best_contribution: contribution,
+                });
+                if contribution < entry.best_contribution {
+                    entry.best_variant = variant;
+                    entry.best_contribution = contribution;
+                }
+
+                match strategy {
+                    // Accumulate the weighted score sum and the weight sum
+                    FusionStrategy::Average => {
+                        entry.value += weight * score;
+                        entry.weights += weight;
+                    }
+                    // A heavier variant divides its score down, i.e. counts as a closer match
+                    FusionStrategy::Min => entry.value = entry.value.min(contribution),
+                    // Negated reciprocal ranks, so that lower is better for every strategy
+                    FusionStrategy::Rrf => entry.value += contribution,
+                }
+            }
+        }
+
+        let mut ranked: Vec<(usize, f64, usize)> = fused
+            .into_iter()
+            .map(|(idx, entry)| {
+                let value = match strategy {
+                    FusionStrategy::Average => entry.value / entry.weights,
+                    _ => entry.value,
+                };
+                (idx, value, entry.best_variant)
+            })
+            .collect();
+        ranked.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        ranked
+            .into_iter()
+            .map(|(idx, value, variant)| (idx, variant, value))
+            .collect()
+    }
+}
+
+/// Running fusion state of one item
+struct FusedEntry {
+    value: f64,
+    weights: f64,
+    best_variant: usize,
+    best_contribution: f64,
+}
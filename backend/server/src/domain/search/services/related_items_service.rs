@@ -0,0 +1,93 @@
+use crate::domain::search::entities::search_item::SearchItem;
+use crate::domain::search::value_objects::item_kind::ItemKind;
+use crate::domain::search::value_objects::similarity::Similarity;
+use std::collections::HashSet;
+
+/// Title overlap that makes a file related without sharing brand or folder
+pub const MIN_TITLE_OVERLAP: f64 = 0.3;
+/// Items compared between two checks of whether the scan should stop
+const STOP_CHECK_INTERVAL: usize = 256;
+
+/// Domain service finding files similar to a given one
+///
+/// Titles are compared by their character bigrams, which works the same for
+/// space-separated Latin titles and unspaced CJK ones. A candidate is related
+/// when its title overlaps enough, or when it shares the brand or the parent
+/// folder of the file.
+pub struct RelatedItemsService;
+
+impl RelatedItemsService {
+    /// The `limit` files of `items` most similar to `target`, best first
+    ///
+    /// Folders and the target itself are never returned. Ties keep index order.
+    /// The scan polls `should_stop` every few hundred items and, once it
+    /// returns `true`, ranks only the items compared so far; the returned flag
+    /// tells whether that happened.
+    pub fn related<'a>(
+        target: &SearchItem,
+        items: &'a [SearchItem],
+        limit: usize,
+        should_stop: impl Fn() -> bool,
+    ) -> (Vec<(&'a SearchItem, Similarity)>, bool) {
+        let target_path: &str = &target.info.file_path;
+        let target_grams = Self::title_grams(&target.release.title);
+        let target_brand = Self::brand(target);
+        let target_folder = Self::parent_folder(target_path);
+
+        let mut partial = false;
+        let mut related: Vec<(&SearchItem, Similarity)> = items
+            .iter()
+            .enumerate()
+            .take_while(|(scanned, _)| {
+                partial = scanned % STOP_CHECK_INTERVAL == 0 && should_stop();
+                !partial
+            })
+            .map(|(_, item)| item)
+            .filter(|item| item.kind == ItemKind::File && &*item.info.file_path != target_path)
+            .filter_map(|item| {
+                let overlap = Self::overlap(&target_grams, &Self::title_grams(&item.release.title));
+                let same_brand = target_brand.is_some() && Self::brand(item) == target_brand;
+                let same_folder = Self::parent_folder(&item.info.file_path) == target_folder;
+                (overlap >= MIN_TITLE_OVERLAP || same_brand || same_folder)
+                    .then(|| (item, Similarity::new(overlap, same_brand, same_folder)))
+            })
+            .collect();
+
+        related.sort_by(|a, b| b.1.score.total_cmp(&a.1.score));
+        related.truncate(limit);
+        (related, partial)
+    }
+
+    /// Lowercase character bigrams of the letters and digits of `title`
+    ///
+    /// A one-character title yields that character as its only gram.
+    fn title_grams(title: &str) -> HashSet<(char, Option<char>)> {
+        let chars: Vec<char> = title
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect();
+        match chars.as_slice() {
+            [] => HashSet::new(),
+            [c] => HashSet::from([(*c, None)]),
+            _ => chars.windows(2).map(|w| (w[0], Some(w[1]))).collect(),
+        }
+    }
+
+    /// Dice coefficient of two gram sets
+    fn overlap(a: &HashSet<(char, Option<char>)>, b: &HashSet<(char, Option<char>)>) -> f64 {
+        if a.is_empty() || b.is_empty() {
+            return 0.0;
+        }
+        let shared = a.intersection(b).count();
+        2.0 * shared as f64 / (a.len() + b.len()) as f64
+    }
+
+    fn brand(item: &SearchItem) -> Option<String> {
+        item.release.brand.as_deref().map(str::to_lowercase)
+    }
+
+    fn parent_folder(path: &str) -> &str {
+        path.rsplit_once('/').map_or("", |(folder, _)| folder)
+    }
+}
@@ -0,0 +1,170 @@
+use crate::domain::search::entities::relevance_report::{
+    MetricChange, QueryEvaluation, RelevanceDiff, RelevanceReport,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Hits looked at by NDCG
+pub const NDCG_CUTOFF: usize = 10;
+/// Cutoffs recall is reported at
+pub const RECALL_CUTOFFS: [usize; 3] = [1, 10, 50];
+
+/// Domain service scoring rankings against golden relevance judgments
+///
+/// Judgments are binary: a hit is relevant if its path is listed for the
+/// query, and every relevant file counts the same.
+#[derive(Default)]
+pub struct RelevanceMetricsService;
+
+impl RelevanceMetricsService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Score one ranking, given as paths best first
+    pub fn evaluate_query(
+        &self,
+        query: &str,
+        ranked: &[&str],
+        relevant: &[String],
+    ) -> QueryEvaluation {
+        let relevant: HashSet<&str> = relevant.iter().map(String::as_str).collect();
+        let first_relevant = ranked
+            .iter()
+            .position(|path| relevant.contains(path))
+            .map(|idx| idx + 1);
+
+        QueryEvaluation {
+            query: query.to_string(),
+            first_relevant,
+            reciprocal_rank: first_relevant.map_or(0.0, |rank| 1.0 / rank as f64),
+            ndcg: Self::ndcg(ranked, &relevant, NDCG_CUTOFF),
+            recall: RECALL_CUTOFFS
+                .iter()
+                .map(|&k| (k, Self::recall(ranked, &relevant, k)))
+                .collect(),
+        }
+    }
+
+    /// Average per-query scores into a report
+    pub fn summarize(&self, queries: Vec<QueryEvaluation>) -> RelevanceReport {
+        let mean = |value: &dyn Fn(&QueryEvaluation) -> f64| {
+            if queries.is_empty() {
+                0.0
+            } else {
+                queries.iter().map(value).sum::<f64>() / queries.len() as f64
+            }
+        };
+
+        RelevanceReport {
+            mrr: mean(&|query| query.reciprocal_rank),
+            ndcg: mean(&|query| query.ndcg),
+            ndcg_cutoff: NDCG_CUTOFF,
+            recall: RECALL_CUTOFFS
+                .iter()
+                .map(|&k| {
+                    (
+                        k,
+                        mean(&|query| query.recall.get(&k).copied().unwrap_or(0.0)),
+                    )
+                })
+                .collect(),
+            queries,
+        }
+    }
+
+    /// Compare a run with a baseline run
+    ///
+    /// Set-wide metrics are always listed; per-query reciprocal rank and NDCG
+    /// only when they moved by more than `tolerance`.
+    pub fn compare(
+        &self,
+        current: &RelevanceReport,
+        baseline: &RelevanceReport,
+        tolerance: f64,
+    ) -> RelevanceDiff {
+        let mut changes = vec![
+            Self::change(None, "MRR", baseline.mrr, current.mrr),
+            Self::change(
+                None,
+                &format!("NDCG@{}", current.ndcg_cutoff),
+                baseline.ndcg,
+                current.ndcg,
+            ),
+        ];
+        for (k, recall) in &current.recall {
+            if let Some(before) = baseline.recall.get(k) {
+                changes.push(Self::change(None, &format!("recall@{k}"), *before, *recall));
+            }
+        }
+
+        let before: HashMap<&str, &QueryEvaluation> = baseline
+            .queries
+            .iter()
+            .map(|query| (query.query.as_str(), query))
+            .collect();
+        let mut new_queries = Vec::new();
+        for query in &current.queries {
+            let Some(old) = before.get(query.query.as_str()) else {
+                new_queries.push(query.query.clone());
+                continue;
+            };
+            let metrics = [
+                ("RR", old.reciprocal_rank, query.reciprocal_rank),
+                ("NDCG", old.ndcg, query.ndcg),
+            ];
+            for (metric, old, new) in metrics {
+                if (new - old).abs() > tolerance {
+                    changes.push(Self::change(Some(&query.query), metric, old, new));
+                }
+            }
+        }
+
+        RelevanceDiff {
+            tolerance,
+            changes,
+            new_queries,
+        }
+    }
+
+    /// Discounted cumulative gain of the first `k` hits, divided by the gain
+    /// of an ideal ranking
+    pub fn ndcg(ranked: &[&str], relevant: &HashSet<&str>, k: usize) -> f64 {
+        let discount = |idx: usize| 1.0 / (idx as f64 + 2.0).log2();
+        let ideal: f64 = (0..relevant.len().min(k)).map(discount).sum();
+        if ideal == 0.0 {
+            return 0.0;
+        }
+
+        let gain: f64 = ranked
+            .iter()
+            .take(k)
+            .enumerate()
+            .filter(|(_, path)| relevant.contains(*path))
+            .map(|(idx, _)| discount(idx))
+            .fold(0.0, |total, gain| total + gain);
+        gain / ideal
+    }
+
+    /// Share of the relevant files among the first `k` hits
+    pub fn recall(ranked: &[&str], relevant: &HashSet<&str>, k: usize) -> f64 {
+        if relevant.is_empty() {
+            return 0.0;
+        }
+        let found = ranked
+            .iter()
+            .take(k)
+            .filter(|path| relevant.contains(*path))
+            .collect::<HashSet<_>>()
+            .len();
+        found as f64 / relevant.len() as f64
+    }
+
+    fn change(query: Option<&str>, metric: &str, baseline: f64, current: f64) -> MetricChange {
+        MetricChange {
+            query: query.map(str::to_string),
+            metric: metric.to_string(),
+            baseline,
+            current,
+        }
+    }
+}
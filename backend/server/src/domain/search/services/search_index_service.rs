@@ -1,6 +1,10 @@
 use crate::domain::files::entities::file_info::FileInfo;
+use crate::domain::files::entities::tree_node::{NodeType, TreeNode};
 use crate::domain::search::entities::search_item::{SearchItem, SearchList};
+use crate::domain::search::value_objects::item_kind::ItemKind;
+use crate::domain::search::value_objects::release_info::ReleaseInfo;
 use crate::domain::search::value_objects::search_path::SearchPath;
+use std::hash::{DefaultHasher, Hash, Hasher};
 
 /// Domain service for building search indexes
 ///
@@ -27,10 +31,76 @@ impl SearchIndexService {
                 search_list.push(SearchItem {
                     id: search_path.to_string(),
                     info: file_info.clone(),
+                    release: ReleaseInfo::parse(&file_info.file_path),
+                    kind: ItemKind::File,
+                    file_count: None,
                 });
             }
         }
 
         search_list
     }
+
+    /// Build search items for every folder below the given roots
+    ///
+    /// Each root is a tree paired with the bucket path it is mounted at, so
+    /// folder items carry bucket paths just like file items. The roots
+    /// themselves are not indexed. Folders come in a fixed order, by name
+    /// with sub-folders before their parent.
+    pub fn build_folder_index(&self, roots: &[(&str, &TreeNode)]) -> SearchList {
+        let mut search_list = Vec::new();
+        for (prefix, tree) in roots {
+            Self::collect_folders(tree, prefix, &mut search_list);
+        }
+        search_list
+    }
+
+    /// Append the folders below `node` and return its file count, total size
+    /// and newest upload time
+    fn collect_folders(node: &TreeNode, path: &str, out: &mut SearchList) -> (usize, u64, u64) {
+        let mut totals = (0, 0, 0);
+
+        for (name, child) in node.sorted_children() {
+            let (count, size, newest) = match child {
+                NodeType::File(info) => (1, info.file_size, info.upload_timestamp),
+                NodeType::Node(folder) => {
+                    let folder_path = if path.is_empty() {
+                        name.clone()
+                    } else {
+                        format!("{path}/{name}")
+                    };
+                    let stats = Self::collect_folders(folder, &folder_path, out);
+                    out.push(SearchItem {
+                        id: SearchPath::new(&folder_path).to_string(),
+                        release: ReleaseInfo::parse_folder(&folder_path),
+                        info: FileInfo {
+                            file_path: folder_path.into(),
+                            upload_timestamp: stats.2,
+                            file_size: stats.1,
+                        },
+                        kind: ItemKind::Folder,
+                        file_count: Some(stats.0),
+                    });
+                    stats
+                }
+            };
+            totals = (totals.0 + count, totals.1 + size, totals.2.max(newest));
+        }
+
+        totals
+    }
+
+    /// Fingerprint of the index contents, changes whenever any file is added,
+    /// removed, renamed, resized or re-uploaded
+    pub fn fingerprint(&self, items: &SearchList) -> String {
+        let mut hasher = DefaultHasher::new();
+        items.len().hash(&mut hasher);
+        for item in items {
+            item.info.file_path.hash(&mut hasher);
+            item.info.file_size.hash(&mut hasher);
+            item.info.upload_timestamp.hash(&mut hasher);
+            item.kind.hash(&mut hasher);
+        }
+        format!("{:016x}", hasher.finish())
+    }
 }
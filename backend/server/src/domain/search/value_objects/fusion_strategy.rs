@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// How the rankings of several query variants are merged into one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FusionStrategy {
+    /// Weighted mean of the scores of the variants an item matched
+    #[default]
+    Average,
+    /// Best score of any variant, each divided by its weight
+    Min,
+    /// Reciprocal rank fusion: sums `weight / (k + rank)`, ignoring raw scores
+    Rrf,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// Whether a search item is a single file or a folder of the file tree
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemKind {
+    #[default]
+    File,
+    Folder,
+}
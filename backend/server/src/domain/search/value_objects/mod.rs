@@ -1,2 +1,9 @@
+pub mod cancellation;
+pub mod fusion_strategy;
+pub mod item_kind;
+pub mod release_info;
+pub mod release_key;
 pub mod score;
 pub mod search_path;
+pub mod similarity;
+pub mod weighted_query;
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+/// Structured parts of a release file name
+///
+/// Release names follow the `[YYMMDD][brand] title.ext` convention, e.g.
+/// `[181026][hulotte] 出会って5分は俺のもの！.rar`. Parts that are missing
+/// from a name are left empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseInfo {
+    /// Release date as `YYYY-MM-DD`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// Brand or circle, the first bracket tag that is not a date
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub brand: Option<String>,
+    /// Title with bracket tags and extension removed
+    pub title: String,
+    /// Lowercase file extension
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension: Option<String>,
+}
+
+impl ReleaseInfo {
+    /// Parse the last segment of `file_path`
+    pub fn parse(file_path: &str) -> Self {
+        let name = file_path.rsplit('/').next().unwrap_or(file_path);
+        let (stem, extension) = match name.rsplit_once('.') {
+            Some((stem, ext))
+                if !stem.is_empty()
+                    && !ext.is_empty()
+                    && ext.chars().all(|c| c.is_ascii_alphanumeric()) =>
+            {
+                (stem, Some(ext.to_lowercase()))
+            }
+            _ => (name, None),
+        };
+
+        Self::from_stem(stem, extension)
+    }
+
+    /// Parse the last segment of a folder path, which has no extension
+    pub fn parse_folder(folder_path: &str) -> Self {
+        let name = folder_path.rsplit('/').next().unwrap_or(folder_path);
+        Self::from_stem(name, None)
+    }
+
+    fn from_stem(stem: &str, extension: Option<String>) -> Self {
+        let mut info = Self {
+            extension,
+            ..Self::default()
+        };
+
+        let mut rest = stem.trim_start();
+        loop {
+            let (open, close) = match rest.chars().next() {
+                Some('[') => ('[', ']'),
+                Some('【') => ('【', '】'),
+                _ => break,
+            };
+            let Some(end) = rest.find(close) else {
+                break;
+            };
+            let tag = rest[open.len_utf8()..end].trim();
+            rest = rest[end + close.len_utf8()..].trim_start();
+
+            if info.date.is_none()
+                && let Some(date) = Self::parse_date(tag)
+            {
+                info.date = Some(date);
+            } else if info.brand.is_none() && !tag.is_empty() {
+                info.brand = Some(tag.to_string());
+            }
+        }
+
+        let title = rest.trim();
+        info.title = if title.is_empty() { stem.trim() } else { title }.to_string();
+        info
+    }
+
+    /// Parse a `YYMMDD` or `YYYYMMDD` tag into `YYYY-MM-DD`
+    pub fn parse_date(tag: &str) -> Option<String> {
+        if !tag.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let (year, rest) = match tag.len() {
+            6 => {
+                let yy: u32 = tag[..2].parse().ok()?;
+                (if yy >= 90 { 1900 + yy } else { 2000 + yy }, &tag[2..])
+            }
+            8 => (tag[..4].parse().ok()?, &tag[4..]),
+            _ => return None,
+        };
+        let month: u32 = rest[..2].parse().ok()?;
+        let day: u32 = rest[2..].parse().ok()?;
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return None;
+        }
+        Some(format!("{year:04}-{month:02}-{day:02}"))
+    }
+}
@@ -0,0 +1,155 @@
+use crate::domain::search::value_objects::release_info::ReleaseInfo;
+
+/// Brackets a release date tag can be written in
+const DATE_BRACKETS: [(char, char); 3] = [('[', ']'), ('【', '】'), ('(', ')')];
+
+/// Value object identifying one release across its split parts and versions
+///
+/// The key is the lowercase path with the extension, part/volume suffixes
+/// (`.part1.rar`, `.7z.001`, `.z01`, `.r00`), leading or trailing date tags
+/// (`[180629]`) and trailing version or patch tags (`_v1.02`, ` ver2`,
+/// ` patch`) removed, so every file of a release maps to the same key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReleaseKey(String);
+
+impl ReleaseKey {
+    pub fn new(file_path: &str) -> Self {
+        Self(Self::normalize(file_path))
+    }
+
+    fn normalize(file_path: &str) -> String {
+        let lower = file_path.to_lowercase();
+        let (dir, name) = match lower.rsplit_once('/') {
+            Some((dir, name)) => (Some(dir), name),
+            None => (None, lower.as_str()),
+        };
+
+        let mut stem = Self::strip_leading_date_tag(Self::strip_volume_suffixes(name));
+        loop {
+            let trimmed = Self::strip_version_tag(Self::strip_trailing_date_tag(stem));
+            if trimmed == stem {
+                break;
+            }
+            stem = trimmed;
+        }
+
+        match dir {
+            Some(dir) => format!("{dir}/{stem}"),
+            None => stem.to_string(),
+        }
+    }
+
+    /// Remove the extension together with any part/volume numbering
+    fn strip_volume_suffixes(name: &str) -> &str {
+        let mut stem = name;
+
+        // `.001`, `.002`: numbered volumes, possibly after an archive extension
+        if let Some((rest, ext)) = stem.rsplit_once('.')
+            && Self::is_digits(ext)
+        {
+            stem = rest;
+        }
+
+        // `.rar`, `.7z`, `.zip`, `.z01`, `.r00`, ...
+        if let Some((rest, ext)) = stem.rsplit_once('.')
+            && !rest.is_empty()
+            && !ext.is_empty()
+            && ext.len() <= 4
+            && ext.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            stem = rest;
+        }
+
+        // `.part1`, `.part02`
+        if let Some((rest, part)) = stem.rsplit_once('.')
+            && let Some(number) = part.strip_prefix("part")
+            && Self::is_digits(number)
+        {
+            stem = rest;
+        }
+
+        stem
+    }
+
+    /// Remove one trailing version or patch tag, returning the input if there is none
+    fn strip_version_tag(stem: &str) -> &str {
+        let stem = stem.trim_end();
+
+        for tag in ["patch", "パッチ", "补丁", "修正"] {
+            if let Some(rest) = stem.strip_suffix(tag)
+                && let Some(rest) = Self::strip_separator(rest)
+            {
+                return rest;
+            }
+        }
+
+        // `v1.02`, `ver2`, `ver.1.1`
+        let version_start = stem
+            .char_indices()
+            .rev()
+            .find(|(_, c)| !(c.is_ascii_digit() || *c == '.'))
+            .map_or(0, |(idx, c)| idx + c.len_utf8());
+        let digits = stem[version_start..].trim_start_matches('.');
+        if digits.is_empty() {
+            return stem;
+        }
+        let before = &stem[..stem.len() - digits.len()];
+        let before = before.strip_suffix('.').unwrap_or(before);
+        for prefix in ["ver", "v"] {
+            if let Some(rest) = before.strip_suffix(prefix)
+                && let Some(rest) = Self::strip_separator(rest)
+            {
+                return rest;
+            }
+        }
+
+        stem
+    }
+
+    /// Remove a `[YYMMDD]` release date in front of the title
+    fn strip_leading_date_tag(stem: &str) -> &str {
+        for (open, close) in DATE_BRACKETS {
+            if let Some(rest) = stem.strip_prefix(open)
+                && let Some((tag, rest)) = rest.split_once(close)
+                && ReleaseInfo::parse_date(tag.trim()).is_some()
+                && !rest.trim().is_empty()
+            {
+                return rest.trim_start_matches([' ', '_', '-']);
+            }
+        }
+        stem
+    }
+
+    /// Remove a `[YYMMDD]` release date after the title, returning the input if there is none
+    fn strip_trailing_date_tag(stem: &str) -> &str {
+        let stem = stem.trim_end();
+        for (open, close) in DATE_BRACKETS {
+            if let Some(rest) = stem.strip_suffix(close)
+                && let Some((rest, tag)) = rest.rsplit_once(open)
+                && ReleaseInfo::parse_date(tag.trim()).is_some()
+            {
+                let rest = rest.trim_end_matches([' ', '_', '-']);
+                if !rest.is_empty() {
+                    return rest;
+                }
+            }
+        }
+        stem
+    }
+
+    /// Strip the separator in front of a tag; a tag glued to a word is part of the title
+    fn strip_separator(rest: &str) -> Option<&str> {
+        let trimmed = rest.trim_end_matches([' ', '_', '-', '+', '(', '[']);
+        (trimmed.len() < rest.len() && !trimmed.is_empty()).then_some(trimmed)
+    }
+
+    fn is_digits(text: &str) -> bool {
+        !text.is_empty() && text.chars().all(|c| c.is_ascii_digit())
+    }
+}
+
+impl std::fmt::Display for ReleaseKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
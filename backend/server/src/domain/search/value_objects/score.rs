@@ -1 +1,30 @@
-// Search score value object
+use serde::{Deserialize, Serialize};
+
+/// Value object for a fuzzy match score
+///
+/// Follows the Fuse convention: `0.0` is a perfect match and `1.0` a complete mismatch.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Score(f64);
+
+impl Score {
+    pub const MISMATCH: Self = Self(1.0);
+
+    /// Create a score, clamped into `0.0..=1.0`
+    pub fn new(value: f64) -> Self {
+        if value.is_nan() {
+            Self::MISMATCH
+        } else {
+            Self(value.clamp(0.0, 1.0))
+        }
+    }
+
+    pub fn value(self) -> f64 {
+        self.0
+    }
+}
+
+impl From<f64> for Score {
+    fn from(value: f64) -> Self {
+        Self::new(value)
+    }
+}
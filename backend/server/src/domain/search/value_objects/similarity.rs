@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// Weight of the title overlap in [`Similarity::score`]
+pub const TITLE_WEIGHT: f64 = 0.6;
+/// Weight of a shared brand in [`Similarity::score`]
+pub const BRAND_WEIGHT: f64 = 0.25;
+/// Weight of a shared parent folder in [`Similarity::score`]
+pub const FOLDER_WEIGHT: f64 = 0.15;
+
+/// How closely one file resembles another
+///
+/// Combines the overlap of the two titles with whether both files come from
+/// the same brand and sit in the same folder. The score runs from 0 for
+/// unrelated files to 1 for a file with the same title, brand and folder.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Similarity {
+    /// Weighted sum of the signals below
+    pub score: f64,
+    /// Share of title bigrams in common, `0..=1`
+    pub title_overlap: f64,
+    pub same_brand: bool,
+    pub same_folder: bool,
+}
+
+impl Similarity {
+    pub fn new(title_overlap: f64, same_brand: bool, same_folder: bool) -> Self {
+        let flag = |shared: bool| if shared { 1.0 } else { 0.0 };
+        Self {
+            score: TITLE_WEIGHT * title_overlap
+                + BRAND_WEIGHT * flag(same_brand)
+                + FOLDER_WEIGHT * flag(same_folder),
+            title_overlap,
+            same_brand,
+            same_folder,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// One query variant of a combined search with its relative weight
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeightedQuery {
+    /// Search query string
+    pub text: String,
+    /// Relative influence of this variant on the fused ranking, positive
+    pub weight: f64,
+}
+
+impl WeightedQuery {
+    pub fn new(text: impl Into<String>, weight: f64) -> Self {
+        Self {
+            text: text.into(),
+            weight,
+        }
+    }
+
+    /// A variant with the default weight of 1
+    pub fn unweighted(text: impl Into<String>) -> Self {
+        Self::new(text, 1.0)
+    }
+}
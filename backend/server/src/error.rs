@@ -10,8 +10,15 @@ use thiserror::Error;
 pub enum AppError {
     #[error("Bad request: {0}")]
     BadRequest(String),
+    /// Bad request with a machine-readable `code` for clients to act on
+    #[error("Bad request: {message}")]
+    InvalidInput { code: &'static str, message: String },
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
     #[error("Not found: {0}")]
     NotFound(String),
+    #[error("Service unavailable: {0}")]
+    Unavailable(String),
     #[error("Internal error: {0}")]
     Internal(String),
     #[error("configuration error: {0}")]
@@ -28,19 +35,31 @@ pub enum AppError {
 
 #[derive(Serialize)]
 struct ErrorResponse<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<&'a str>,
     message: &'a str,
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let (status, msg) = match &self {
-            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
-            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg.clone()),
-            AppError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
-            AppError::Network(e) => (StatusCode::BAD_GATEWAY, e.to_string()),
-            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        let (status, code, msg) = match &self {
+            AppError::BadRequest(msg) => {
+                (StatusCode::BAD_REQUEST, Some("bad_request"), msg.clone())
+            }
+            AppError::InvalidInput { code, message } => {
+                (StatusCode::BAD_REQUEST, Some(*code), message.clone())
+            }
+            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, None, msg.clone()),
+            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, None, msg.clone()),
+            AppError::Unavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, None, msg.clone()),
+            AppError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, None, msg.clone()),
+            AppError::Network(e) => (StatusCode::BAD_GATEWAY, None, e.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, None, self.to_string()),
         };
-        let body = Json(ErrorResponse { message: &msg });
+        let body = Json(ErrorResponse {
+            code,
+            message: &msg,
+        });
         (status, body).into_response()
     }
 }
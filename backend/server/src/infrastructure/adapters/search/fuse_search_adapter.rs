@@ -1,18 +1,51 @@
+use crate::domain::search::entities::fused_result::FusedResult;
 use crate::domain::search::entities::search_item::{SearchItem, SearchList};
+use crate::domain::search::entities::search_result::SearchResult;
 use crate::domain::search::repositories::fuzzy_search_repository::FuzzySearchRepository;
+use crate::domain::search::services::rank_fusion_service::RankFusionService;
+use crate::domain::search::value_objects::cancellation::Cancellation;
+use crate::domain::search::value_objects::fusion_strategy::FusionStrategy;
+use crate::domain::search::value_objects::score::Score;
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
+use crate::error::AppError;
+use fuse_lib::budget::SearchBudget;
 use fuse_lib::config::Fuse;
 use fuse_lib::fuseable::Fuseable;
 use fuse_lib::types::FuseProperty;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Field weights of [`FuseableSearchItem`]
+///
+/// fuse-lib scales the score of a field weighted below 1 by `1 - weight` and
+/// averages the matching fields, so a title match counts twice as much as the
+/// same match of the path or brand, which keep their plain score.
+const PATH_FIELD_WEIGHT: f64 = 1.0;
+const TITLE_FIELD_WEIGHT: f64 = 0.5;
+const BRAND_FIELD_WEIGHT: f64 = 1.0;
+
+/// Accepted values of the tunable [`FuseConfig`] fields
+pub const THRESHOLD_RANGE: RangeInclusive<f64> = 0.0..=1.0;
+pub const DISTANCE_RANGE: RangeInclusive<usize> = 1..=10_000;
+/// Fuse matches patterns through a 64-bit mask
+pub const MAX_PATTERN_LENGTH_RANGE: RangeInclusive<usize> = 1..=64;
 
 /// Configuration for the Fuse search engine
-#[derive(Debug, Clone)]
+///
+/// Deserializes from a partial table, missing fields keep their defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct FuseConfig {
     pub threshold: f64,
     pub distance: usize,
     pub max_pattern_length: usize,
     pub is_case_sensitive: bool,
     pub tokenize: bool,
+    /// Match the file name only, ignoring folders, the parsed title and the brand
+    pub file_name_only: bool,
 }
 
 impl Default for FuseConfig {
@@ -23,7 +56,84 @@ impl Default for FuseConfig {
             max_pattern_length: 32,
             is_case_sensitive: false,
             tokenize: true,
+            file_name_only: false,
+        }
+    }
+}
+
+/// A [`FuseConfig`] field set outside its accepted range
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("`{field}` must be within {range}")]
+pub struct FuseConfigError {
+    pub field: &'static str,
+    pub range: String,
+}
+
+impl From<FuseConfigError> for AppError {
+    fn from(err: FuseConfigError) -> Self {
+        AppError::InvalidInput {
+            code: "tuning_out_of_range",
+            message: err.to_string(),
+        }
+    }
+}
+
+impl FuseConfig {
+    /// Check the numeric fields against their accepted ranges
+    ///
+    /// # Errors
+    ///
+    /// Returns the first field that is out of range
+    pub fn validate(&self) -> Result<(), FuseConfigError> {
+        let out_of_range = |field, range: String| Err(FuseConfigError { field, range });
+        if !THRESHOLD_RANGE.contains(&self.threshold) {
+            return out_of_range("threshold", format!("{THRESHOLD_RANGE:?}"));
         }
+        if !DISTANCE_RANGE.contains(&self.distance) {
+            return out_of_range("distance", format!("{DISTANCE_RANGE:?}"));
+        }
+        if !MAX_PATTERN_LENGTH_RANGE.contains(&self.max_pattern_length) {
+            return out_of_range(
+                "max_pattern_length",
+                format!("{MAX_PATTERN_LENGTH_RANGE:?}"),
+            );
+        }
+        Ok(())
+    }
+
+    /// Compact description of a non-default configuration for cache keys,
+    /// `None` for the defaults
+    pub fn label(&self) -> Option<String> {
+        (*self != Self::default()).then(|| serde_json::to_string(self).unwrap_or_default())
+    }
+}
+
+/// Individual [`FuseConfig`] fields to replace, e.g. from an admin request
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FuseOverrides {
+    pub threshold: Option<f64>,
+    pub distance: Option<usize>,
+    pub max_pattern_length: Option<usize>,
+    pub is_case_sensitive: Option<bool>,
+    pub tokenize: Option<bool>,
+    pub file_name_only: Option<bool>,
+}
+
+impl FuseOverrides {
+    /// `config` with the given fields replaced
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a replaced field is out of its accepted range
+    pub fn apply(&self, mut config: FuseConfig) -> Result<FuseConfig, FuseConfigError> {
+        config.threshold = self.threshold.unwrap_or(config.threshold);
+        config.distance = self.distance.unwrap_or(config.distance);
+        config.max_pattern_length = self.max_pattern_length.unwrap_or(config.max_pattern_length);
+        config.is_case_sensitive = self.is_case_sensitive.unwrap_or(config.is_case_sensitive);
+        config.tokenize = self.tokenize.unwrap_or(config.tokenize);
+        config.file_name_only = self.file_name_only.unwrap_or(config.file_name_only);
+        config.validate()?;
+        Ok(config)
     }
 }
 
@@ -34,15 +144,40 @@ impl Default for FuseConfig {
 /// external dependencies.
 pub struct FuseSearchAdapter {
     config: FuseConfig,
+    cancellation: Cancellation,
+    time_budget: Option<Duration>,
+    partial: AtomicBool,
 }
 
 impl FuseSearchAdapter {
     pub fn new(config: FuseConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            cancellation: Cancellation::new(),
+            time_budget: None,
+            partial: AtomicBool::new(false),
+        }
     }
 
-    pub fn with_default_config() -> Self {
-        Self::new(FuseConfig::default())
+    /// Stop scanning once `cancellation` is set, keeping what was found so far
+    pub fn with_cancellation(mut self, cancellation: Cancellation) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Stop scanning `budget` after a search starts, keeping what was found so far
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Budget of one search call, shared by all of its scans
+    fn budget(&self) -> SearchBudget {
+        let budget = SearchBudget::unlimited().with_cancel_flag(self.cancellation.flag());
+        match self.time_budget {
+            Some(timeout) => budget.with_timeout(timeout),
+            None => budget,
+        }
     }
 
     /// Create a Fuse instance with the current configuration
@@ -56,78 +191,126 @@ impl FuseSearchAdapter {
             ..Default::default()
         }
     }
+
+    /// Indices and scores of the matching items, best first
+    ///
+    /// Only items scanned before `budget` runs out are considered; a cut-short
+    /// scan marks the search as partial.
+    fn search_list(
+        &self,
+        fuse: &Fuse,
+        query: &str,
+        items: &[FuseableSearchItem],
+        budget: &SearchBudget,
+    ) -> Vec<(usize, f64)> {
+        let scan = fuse.search_text_in_fuse_list_within(query, items, budget);
+        if scan.partial {
+            self.partial.store(true, Ordering::Relaxed);
+        }
+
+        scan.results
+            .into_iter()
+            .map(|result| (result.index, result.score))
+            .collect()
+    }
+
+    /// Wrap the items for fuse-lib's list search
+    fn fuseable<'a>(&self, items: &'a SearchList) -> Vec<FuseableSearchItem<'a>> {
+        items
+            .iter()
+            .map(|item| FuseableSearchItem {
+                item,
+                file_name_only: self.config.file_name_only,
+            })
+            .collect()
+    }
 }
 
 /// Wrapper to make SearchItem compatible with Fuse library
 ///
 /// This wrapper implements the Fuseable trait required by the fuse library,
 /// keeping this external dependency concern isolated in the infrastructure layer.
-struct FuseableSearchItem<'a>(&'a SearchItem);
+/// Items are searched by their full path, parsed title and brand, or by the
+/// last path segment alone with `file_name_only`.
+struct FuseableSearchItem<'a> {
+    item: &'a SearchItem,
+    file_name_only: bool,
+}
 
-impl<'a> Fuseable for FuseableSearchItem<'a> {
+impl Fuseable for FuseableSearchItem<'_> {
     fn properties(&self) -> Vec<FuseProperty> {
-        vec![FuseProperty::init("id")]
+        if self.file_name_only {
+            return vec![FuseProperty::init("name")];
+        }
+        vec![
+            FuseProperty {
+                value: String::from("id"),
+                weight: PATH_FIELD_WEIGHT,
+            },
+            FuseProperty {
+                value: String::from("title"),
+                weight: TITLE_FIELD_WEIGHT,
+            },
+            FuseProperty {
+                value: String::from("brand"),
+                weight: BRAND_FIELD_WEIGHT,
+            },
+        ]
     }
 
     fn lookup(&self, key: &str) -> Option<&str> {
-        if key == "id" { Some(&self.0.id) } else { None }
+        let release = &self.item.release;
+        match key {
+            "id" => Some(&self.item.id),
+            "name" => self.item.info.file_path.rsplit('/').next(),
+            "title" => Some(release.title.as_str()).filter(|title| !title.is_empty()),
+            "brand" => release.brand.as_deref(),
+            _ => None,
+        }
     }
 }
 
 impl FuzzySearchRepository for FuseSearchAdapter {
-    fn search(&self, query: &str, items: &SearchList) -> SearchList {
+    fn search_scored(&self, query: &str, items: &SearchList) -> Vec<SearchResult> {
         let fuse = self.create_fuse();
+        let fuseable = self.fuseable(items);
 
-        // Convert SearchItems to FuseableSearchItems for the fuse library
-        let fuseable_items: Vec<FuseableSearchItem> =
-            items.iter().map(FuseableSearchItem).collect();
-
-        let results = fuse.search_text_in_fuse_list(query, &fuseable_items);
-
-        // Convert results back to SearchItems
-        results
+        self.search_list(&fuse, query, &fuseable, &self.budget())
             .into_iter()
-            .map(|r| items[r.index].clone())
+            .map(|(idx, score)| SearchResult::new(items[idx].clone(), Score::new(score)))
             .collect()
     }
 
-    fn combined_search(&self, q1: &str, q2: &str, limit: usize, items: &SearchList) -> SearchList {
+    fn fused_search(
+        &self,
+        queries: &[WeightedQuery],
+        strategy: FusionStrategy,
+        limit: usize,
+        items: &SearchList,
+    ) -> Vec<FusedResult> {
         let fuse = self.create_fuse();
+        let budget = self.budget();
+        let fuseable = self.fuseable(items);
 
-        // Convert SearchItems to FuseableSearchItems
-        let fuseable_items: Vec<FuseableSearchItem> =
-            items.iter().map(FuseableSearchItem).collect();
-
-        let q1_results = fuse.search_text_in_fuse_list(q1, &fuseable_items);
-        let q2_results = fuse.search_text_in_fuse_list(q2, &fuseable_items);
-
-        let mut scores: HashMap<usize, f64> = HashMap::new();
-
-        // Process first query results
-        for result in q1_results {
-            scores.insert(result.index, result.score);
-        }
-
-        // Process second query results and combine scores
-        for result in q2_results {
-            scores
-                .entry(result.index)
-                .and_modify(|s| *s = (*s + result.score) / 2.0)
-                .or_insert(result.score);
-        }
-
-        // Sort by score and take top results
-        let mut scored_items: Vec<(SearchItem, f64)> = scores
-            .into_iter()
-            .map(|(idx, score)| (items[idx].clone(), score))
+        let lists: Vec<(f64, Vec<(usize, f64)>)> = queries
+            .iter()
+            .map(|query| {
+                let results = self.search_list(&fuse, &query.text, &fuseable, &budget);
+                (query.weight, results)
+            })
             .collect();
 
-        scored_items.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
-
-        scored_items
+        RankFusionService::new()
+            .fuse(&lists, strategy)
             .into_iter()
             .take(limit)
-            .map(|(item, _)| item)
+            .map(|(idx, query, value)| {
+                FusedResult::new(items[idx].clone(), query, Score::new(value))
+            })
             .collect()
     }
+
+    fn is_partial(&self) -> bool {
+        self.partial.load(Ordering::Relaxed)
+    }
 }
@@ -0,0 +1,82 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Circuit breaker guarding calls to a flaky dependency
+///
+/// After `failure_threshold` consecutive failures the circuit opens and calls
+/// are skipped for `cooldown`. The first call after the cool-down is let
+/// through as a trial: success closes the circuit, failure opens it again.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    /// A trial call after the cool-down is in flight
+    half_open: bool,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    /// Whether a call may be made now
+    pub fn allow(&self) -> bool {
+        let mut state = self.lock();
+        match state.open_until {
+            Some(until) if Instant::now() < until => false,
+            Some(_) => {
+                // Half-open: let one trial through and hold the rest back until it reports
+                state.open_until = Some(Instant::now() + self.cooldown);
+                state.half_open = true;
+                true
+            }
+            None => true,
+        }
+    }
+
+    pub fn record_success(&self) {
+        *self.lock() = BreakerState::default();
+    }
+
+    /// Count a failure, returning `true` if it tripped the circuit open
+    pub fn record_failure(&self) -> bool {
+        let mut state = self.lock();
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        let trips = if state.half_open {
+            true
+        } else {
+            // Failures of calls started before the circuit opened don't trip it again
+            state.open_until.is_none() && state.consecutive_failures >= self.failure_threshold
+        };
+        if trips {
+            state.open_until = Some(Instant::now() + self.cooldown);
+            state.half_open = false;
+        }
+        trips
+    }
+
+    /// Whether the circuit is currently open
+    pub fn is_open(&self) -> bool {
+        self.lock()
+            .open_until
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        // The state stays consistent even if a holder panicked
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
@@ -2,3 +2,6 @@
 //
 // This module is intended for adapters to external HTTP APIs,
 // third-party services, and remote systems.
+
+pub mod circuit_breaker;
+pub mod name_service_client;
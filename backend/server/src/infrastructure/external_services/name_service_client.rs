@@ -0,0 +1,194 @@
+use crate::infrastructure::external_services::circuit_breaker::CircuitBreaker;
+use redis::aio::ConnectionManager;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Configuration of the AI name service client
+#[derive(Debug, Clone)]
+pub struct NameServiceConfig {
+    /// Base URL of the Python service exposing `/findname`
+    pub base_url: String,
+    /// Timeout of one `/findname` request
+    pub timeout: Duration,
+    /// How long answers stay cached
+    pub cache_ttl: Duration,
+    /// Consecutive failures that open the circuit
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a trial call
+    pub cooldown: Duration,
+}
+
+impl Default for NameServiceConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "http://127.0.0.1:2998".to_string(),
+            timeout: Duration::from_secs(5),
+            cache_ttl: Duration::from_secs(24 * 60 * 60),
+            failure_threshold: 3,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Counters of the name service client
+#[derive(Debug, Default)]
+pub struct NameServiceMetrics {
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    failures: AtomicU64,
+    breaker_trips: AtomicU64,
+    breaker_skips: AtomicU64,
+}
+
+/// Point-in-time copy of [`NameServiceMetrics`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct NameServiceMetricsSnapshot {
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    /// Failed calls: network errors, timeouts, error statuses and bad bodies
+    pub failures: u64,
+    /// Times the circuit opened
+    pub breaker_trips: u64,
+    /// Calls skipped because the circuit was open
+    pub breaker_skips: u64,
+    pub breaker_open: bool,
+}
+
+#[derive(Deserialize)]
+struct FindNameResponse {
+    ans: Vec<String>,
+}
+
+/// Client for the Python `/findname` endpoint with caching and a circuit breaker
+///
+/// Lookups never fail: on a cache miss with the circuit open, or when the
+/// call fails, the client answers with no candidates so search can go on
+/// with the raw query.
+pub struct NameServiceClient {
+    http: reqwest::Client,
+    config: NameServiceConfig,
+    redis: Option<ConnectionManager>,
+    breaker: CircuitBreaker,
+    metrics: NameServiceMetrics,
+}
+
+impl NameServiceClient {
+    /// Create a client
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be built
+    pub fn new(
+        config: NameServiceConfig,
+        redis: Option<ConnectionManager>,
+    ) -> Result<Self, reqwest::Error> {
+        let http = reqwest::Client::builder().timeout(config.timeout).build()?;
+        let breaker = CircuitBreaker::new(config.failure_threshold, config.cooldown);
+        Ok(Self {
+            http,
+            config,
+            redis,
+            breaker,
+            metrics: NameServiceMetrics::default(),
+        })
+    }
+
+    /// Canonical names for `query`, best candidate first
+    ///
+    /// Returns `None` if the service could not be asked, because the circuit
+    /// is open or the call failed.
+    pub async fn find_names(&self, query: &str) -> Option<Vec<String>> {
+        let key = Self::cache_key(query);
+
+        if let Some(names) = self.cache_get(&key).await {
+            self.metrics.cache_hits.fetch_add(1, Ordering::Relaxed);
+            return Some(names);
+        }
+        self.metrics.cache_misses.fetch_add(1, Ordering::Relaxed);
+
+        if !self.breaker.allow() {
+            self.metrics.breaker_skips.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+
+        match self.fetch(query).await {
+            Ok(names) => {
+                self.breaker.record_success();
+                self.cache_set(&key, &names).await;
+                Some(names)
+            }
+            Err(e) => {
+                tracing::warn!("/findname error: {e}");
+                self.metrics.failures.fetch_add(1, Ordering::Relaxed);
+                if self.breaker.record_failure() {
+                    tracing::warn!("/findname circuit opened for {:?}", self.config.cooldown);
+                    self.metrics.breaker_trips.fetch_add(1, Ordering::Relaxed);
+                }
+                None
+            }
+        }
+    }
+
+    pub fn metrics(&self) -> NameServiceMetricsSnapshot {
+        let metrics = &self.metrics;
+        NameServiceMetricsSnapshot {
+            cache_hits: metrics.cache_hits.load(Ordering::Relaxed),
+            cache_misses: metrics.cache_misses.load(Ordering::Relaxed),
+            failures: metrics.failures.load(Ordering::Relaxed),
+            breaker_trips: metrics.breaker_trips.load(Ordering::Relaxed),
+            breaker_skips: metrics.breaker_skips.load(Ordering::Relaxed),
+            breaker_open: self.breaker.is_open(),
+        }
+    }
+
+    /// Cache key of a query: trimmed, lowercased, inner whitespace collapsed
+    pub fn cache_key(query: &str) -> String {
+        let normalized: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        format!("cache:findname:{}", normalized.join(" "))
+    }
+
+    async fn fetch(&self, query: &str) -> Result<Vec<String>, reqwest::Error> {
+        let body: FindNameResponse = self
+            .http
+            .get(format!("{}/findname", self.config.base_url))
+            .query(&[("name", query)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(body.ans)
+    }
+
+    async fn cache_get(&self, key: &str) -> Option<Vec<String>> {
+        let mut con = self.redis.clone()?;
+        let raw: Option<String> = match redis::cmd("GET").arg(key).query_async(&mut con).await {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::error!("Redis GET {key} error: {e}");
+                return None;
+            }
+        };
+        serde_json::from_str(&raw?).ok()
+    }
+
+    async fn cache_set(&self, key: &str, names: &[String]) {
+        let Some(mut con) = self.redis.clone() else {
+            return;
+        };
+        let Ok(value) = serde_json::to_string(names) else {
+            return;
+        };
+        let result: redis::RedisResult<()> = redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("EX")
+            .arg(self.config.cache_ttl.as_secs().max(1))
+            .query_async(&mut con)
+            .await;
+        if let Err(e) = result {
+            tracing::error!("Redis SET {key} error: {e}");
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use tokio::fs;
+
+/// Load an alias dictionary file: canonical names mapped to their aliases.
+///
+/// Files ending in `.json` hold a JSON object, anything else is read as TOML:
+///
+/// ```toml
+/// "魔法使いの夜" = ["魔夜", "mahoyo", "Witch on the Holy Night"]
+/// ```
+///
+/// A missing file is an empty dictionary.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The file exists but cannot be read
+/// - The JSON or TOML parsing fails
+pub async fn load_alias_file(path: &str) -> Result<BTreeMap<String, Vec<String>>> {
+    let raw = match fs::read_to_string(path).await {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            tracing::warn!("Alias file {path} not found, starting without aliases");
+            return Ok(BTreeMap::new());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if path.ends_with(".json") {
+        Ok(serde_json::from_str(&raw)?)
+    } else {
+        Ok(toml::from_str(&raw)?)
+    }
+}
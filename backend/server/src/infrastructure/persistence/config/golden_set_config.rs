@@ -0,0 +1,75 @@
+use crate::domain::files::entities::file_info::FileInfo;
+use crate::domain::search::entities::golden_query::GoldenQuery;
+use crate::domain::search::entities::relevance_report::RelevanceReport;
+use anyhow::{Result, bail};
+use serde::Deserialize;
+use std::io::ErrorKind;
+use tokio::fs;
+
+#[derive(Deserialize)]
+struct GoldenFile {
+    #[serde(default)]
+    query: Vec<GoldenQuery>,
+}
+
+/// Load a golden query set from TOML:
+///
+/// ```toml
+/// [[query]]
+/// q = "summer pockets"
+/// relevant = ["zd/1001-1500/[Key] Summer Pockets.rar"]
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The file cannot be read
+/// - The TOML parsing fails
+/// - A query lists no relevant files, which would score it 0 whatever the ranking
+pub async fn load_golden_set(path: &str) -> Result<Vec<GoldenQuery>> {
+    let raw = fs::read_to_string(path).await?;
+    let queries = toml::from_str::<GoldenFile>(&raw)?.query;
+    if let Some(query) = queries.iter().find(|query| query.relevant.is_empty()) {
+        bail!("golden query '{}' has no relevant files", query.query);
+    }
+    Ok(queries)
+}
+
+/// Load the files a golden set is evaluated on, in the bucket file format
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The file cannot be read
+/// - The JSON parsing fails
+pub async fn load_fixture(path: &str) -> Result<Vec<FileInfo>> {
+    let raw = fs::read_to_string(path).await?;
+    Ok(serde_json::from_str(&raw)?)
+}
+
+/// Load the report of a baseline run, `None` if there is none yet
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The file exists but cannot be read
+/// - The JSON parsing fails
+pub async fn load_baseline(path: &str) -> Result<Option<RelevanceReport>> {
+    match fs::read_to_string(path).await {
+        Ok(raw) => Ok(Some(serde_json::from_str(&raw)?)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store a report as the baseline of later runs
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written
+pub async fn save_baseline(path: &str, report: &RelevanceReport) -> Result<()> {
+    let mut raw = serde_json::to_string_pretty(report)?;
+    raw.push('\n');
+    fs::write(path, raw).await?;
+    Ok(())
+}
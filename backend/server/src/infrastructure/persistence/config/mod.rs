@@ -1 +1,4 @@
+pub mod alias_config;
 pub mod database_config;
+pub mod golden_set_config;
+pub mod preset_config;
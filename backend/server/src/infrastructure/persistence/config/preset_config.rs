@@ -0,0 +1,33 @@
+use crate::infrastructure::adapters::search::fuse_search_adapter::FuseConfig;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use tokio::fs;
+
+/// Load search presets: names mapped to partial fuse configurations.
+///
+/// ```toml
+/// [strict]
+/// threshold = 0.3
+/// distance = 200
+/// ```
+///
+/// Fields a preset leaves out keep their defaults. A missing file means no
+/// presets.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The file exists but cannot be read
+/// - The TOML parsing fails
+pub async fn load_preset_file(path: &str) -> Result<BTreeMap<String, FuseConfig>> {
+    let raw = match fs::read_to_string(path).await {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            tracing::warn!("Preset file {path} not found, starting without presets");
+            return Ok(BTreeMap::new());
+        }
+        Err(e) => return Err(e.into()),
+    };
+    Ok(toml::from_str(&raw)?)
+}
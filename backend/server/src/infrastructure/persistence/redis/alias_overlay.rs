@@ -0,0 +1,30 @@
+use redis::aio::ConnectionManager;
+use std::collections::{BTreeMap, HashMap};
+
+/// Load the runtime alias overlay from a Redis hash.
+///
+/// Fields are canonical names, values JSON arrays of aliases, e.g.
+/// `HSET search:aliases 魔法使いの夜 '["魔夜","mahoyo"]'`. An empty array
+/// removes the group from the file dictionary. Malformed values are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the Redis command fails
+pub async fn load_alias_overlay(
+    con: &ConnectionManager,
+    key: &str,
+) -> redis::RedisResult<BTreeMap<String, Vec<String>>> {
+    let mut con = con.clone();
+    let raw: HashMap<String, String> = redis::cmd("HGETALL").arg(key).query_async(&mut con).await?;
+
+    Ok(raw
+        .into_iter()
+        .filter_map(|(canonical, value)| match serde_json::from_str(&value) {
+            Ok(aliases) => Some((canonical, aliases)),
+            Err(e) => {
+                tracing::warn!("Skipping alias overlay entry {canonical}: {e}");
+                None
+            }
+        })
+        .collect())
+}
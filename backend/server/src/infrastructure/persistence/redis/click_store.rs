@@ -0,0 +1,203 @@
+use redis::Script;
+use redis::aio::ConnectionManager;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Fold the counters of the previous era (`KEYS[2]`), scaled by `ARGV[1]`,
+/// into those of the current one (`KEYS[1]`); a no-op once that is done
+static REBASE: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('EXISTS', KEYS[2]) == 0 then
+            return 0
+        end
+        redis.call('ZUNIONSTORE', KEYS[1], 2, KEYS[1], KEYS[2], 'WEIGHTS', 1, ARGV[1])
+        redis.call('DEL', KEYS[2])
+        if ARGV[2] then
+            redis.call('EXPIRE', KEYS[1], ARGV[2])
+        end
+        return 1
+        ",
+    )
+});
+
+/// Redis sorted sets of decaying download click counters
+///
+/// `{prefix}:files:{era}` ranks every opened file; `{prefix}:query:{era}:{query}`
+/// ranks the files opened from one query and expires when the query goes
+/// unused. Each era has its own sets, see [`Self::rebase`].
+/// `{prefix}:seen:{client}:{path}` marks a file as already counted for a client.
+#[derive(Clone)]
+pub struct ClickStore {
+    con: ConnectionManager,
+    prefix: String,
+    query_ttl: Duration,
+    dedupe_window: Duration,
+}
+
+impl ClickStore {
+    pub fn new(
+        con: ConnectionManager,
+        prefix: impl Into<String>,
+        query_ttl: Duration,
+        dedupe_window: Duration,
+    ) -> Self {
+        Self {
+            con,
+            prefix: prefix.into(),
+            query_ttl,
+            dedupe_window,
+        }
+    }
+
+    /// Add `increment` to the `era` counters of `file_path`, overall and for `query`
+    ///
+    /// Only the first click of `client` on a file within the dedupe window
+    /// counts; without a client every click does. Returns whether this click
+    /// counted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a Redis command fails
+    pub async fn record(
+        &self,
+        era: u64,
+        file_path: &str,
+        query: Option<&str>,
+        client: Option<&str>,
+        increment: f64,
+    ) -> redis::RedisResult<bool> {
+        let mut con = self.con.clone();
+        if let Some(client) = client {
+            let first: Option<String> = redis::cmd("SET")
+                .arg(format!("{}:seen:{client}:{file_path}", self.prefix))
+                .arg(1)
+                .arg("NX")
+                .arg("EX")
+                .arg(self.dedupe_window.as_secs().max(1))
+                .query_async(&mut con)
+                .await?;
+            if first.is_none() {
+                return Ok(false);
+            }
+        }
+
+        let mut pipe = redis::pipe();
+        pipe.cmd("ZINCRBY")
+            .arg(self.files_key(era))
+            .arg(increment)
+            .arg(file_path)
+            .ignore();
+        if let Some(query) = query {
+            let key = self.query_key(era, query);
+            pipe.cmd("ZINCRBY")
+                .arg(&key)
+                .arg(increment)
+                .arg(file_path)
+                .ignore()
+                .cmd("EXPIRE")
+                .arg(&key)
+                .arg(self.query_ttl.as_secs().max(1))
+                .ignore();
+        }
+
+        let () = pipe.query_async(&mut con).await?;
+        Ok(true)
+    }
+
+    /// Carry the counters of the era before `era` over into `era`, scaled by
+    /// `factor`, for all files or only those of `query`
+    ///
+    /// Clicks written to the older sets after they were carried over are
+    /// picked up by the next call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a Redis command fails
+    pub async fn rebase(
+        &self,
+        era: u64,
+        query: Option<&str>,
+        factor: f64,
+    ) -> redis::RedisResult<()> {
+        let Some(previous) = era.checked_sub(1) else {
+            return Ok(());
+        };
+        let mut con = self.con.clone();
+        let mut invocation = REBASE.prepare_invoke();
+        match query {
+            Some(query) => invocation
+                .key(self.query_key(era, query))
+                .key(self.query_key(previous, query))
+                .arg(factor)
+                .arg(self.query_ttl.as_secs().max(1)),
+            None => invocation
+                .key(self.files_key(era))
+                .key(self.files_key(previous))
+                .arg(factor),
+        };
+        let _: i64 = invocation.invoke_async(&mut con).await?;
+        Ok(())
+    }
+
+    /// The `max` most clicked files with their stored `era` counters
+    ///
+    /// Less clicked files beyond `max` are dropped from Redis on the way.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a Redis command fails
+    pub async fn top_files(&self, era: u64, max: usize) -> redis::RedisResult<Vec<(String, f64)>> {
+        if max == 0 {
+            return Ok(Vec::new());
+        }
+        let mut con = self.con.clone();
+        let last = isize::try_from(max).unwrap_or(isize::MAX);
+        let (top,): (Vec<(String, f64)>,) = redis::pipe()
+            .cmd("ZREVRANGE")
+            .arg(self.files_key(era))
+            .arg(0)
+            .arg(last - 1)
+            .arg("WITHSCORES")
+            .cmd("ZREMRANGEBYRANK")
+            .arg(self.files_key(era))
+            .arg(0)
+            .arg(-last - 1)
+            .ignore()
+            .query_async(&mut con)
+            .await?;
+        Ok(top)
+    }
+
+    /// The `max` files most clicked from `query` with their stored `era` counters
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a Redis command fails
+    pub async fn top_query_files(
+        &self,
+        era: u64,
+        query: &str,
+        max: usize,
+    ) -> redis::RedisResult<Vec<(String, f64)>> {
+        if max == 0 {
+            return Ok(Vec::new());
+        }
+        let mut con = self.con.clone();
+        redis::cmd("ZREVRANGE")
+            .arg(self.query_key(era, query))
+            .arg(0)
+            .arg(isize::try_from(max).unwrap_or(isize::MAX) - 1)
+            .arg("WITHSCORES")
+            .query_async(&mut con)
+            .await
+    }
+
+    fn query_key(&self, era: u64, query: &str) -> String {
+        format!("{}:query:{era}:{query}", self.prefix)
+    }
+
+    fn files_key(&self, era: u64) -> String {
+        format!("{}:files:{era}", self.prefix)
+    }
+}
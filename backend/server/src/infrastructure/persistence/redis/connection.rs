@@ -6,6 +6,8 @@ use tokio::fs;
 #[derive(Debug, Clone, Deserialize)]
 pub struct Settings {
     pub redis: RedisConfig,
+    #[serde(default)]
+    pub admin: AdminConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +19,13 @@ pub struct RedisConfig {
     pub database: u32,
 }
 
+/// Access to the `/admin` routes
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AdminConfig {
+    /// Bearer token admin requests must send; without one the admin API is closed
+    pub token: Option<String>,
+}
+
 /// Load configuration from a TOML file.
 ///
 /// # Errors
@@ -29,17 +38,14 @@ pub async fn load_config(path: &str) -> Result<Settings> {
     Ok(toml::from_str::<Settings>(&raw)?)
 }
 
-/// Establish a new Redis connection using `config.toml`.
+/// Establish a new Redis connection with the given settings.
 ///
 /// # Errors
 ///
 /// Returns an error if:
-/// - Configuration loading fails
 /// - Redis client creation fails
 /// - Connection manager creation fails
-pub async fn connect_redis() -> Result<ConnectionManager> {
-    let settings = load_config("config.toml").await?;
-    let cfg = &settings.redis;
+pub async fn connect(cfg: &RedisConfig) -> Result<ConnectionManager> {
     let url = match &cfg.password {
         Some(pw) => format!("redis://:{}@{}:{}/{}", pw, cfg.host, cfg.port, cfg.database),
         None => format!("redis://{}:{}/{}", cfg.host, cfg.port, cfg.database),
@@ -1 +1,4 @@
+pub mod alias_overlay;
+pub mod click_store;
 pub mod connection;
+pub mod search_event_store;
@@ -0,0 +1,82 @@
+use crate::domain::analytics::entities::search_event::SearchEvent;
+use redis::aio::ConnectionManager;
+use redis::streams::StreamRangeReply;
+use std::time::Duration;
+
+/// Redis stream of recorded searches
+///
+/// Entry IDs are assigned by Redis from its clock as events are added, which
+/// is within moments of the search timestamps, so a time window is a plain
+/// ID range. Entries older than the retention are trimmed as new ones are added.
+#[derive(Clone)]
+pub struct SearchEventStore {
+    con: ConnectionManager,
+    key: String,
+    retention: Duration,
+}
+
+impl SearchEventStore {
+    pub fn new(con: ConnectionManager, key: impl Into<String>, retention: Duration) -> Self {
+        Self {
+            con,
+            key: key.into(),
+            retention,
+        }
+    }
+
+    /// Append an event to the stream
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event cannot be serialized or the Redis command fails
+    pub async fn append(&self, event: &SearchEvent) -> anyhow::Result<()> {
+        let value = serde_json::to_string(event)?;
+        let retention = u64::try_from(self.retention.as_millis()).unwrap_or(u64::MAX);
+        let min_id = event.timestamp.saturating_sub(retention);
+
+        let mut con = self.con.clone();
+        let _: String = redis::cmd("XADD")
+            .arg(&self.key)
+            .arg("MINID")
+            .arg("~")
+            .arg(min_id)
+            .arg("*")
+            .arg("event")
+            .arg(value)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    /// Events recorded between `from` and `to` (milliseconds, inclusive)
+    ///
+    /// If there are more than `max`, the newest `max` are returned. Events
+    /// come newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Redis command fails
+    pub async fn range(
+        &self,
+        from: u64,
+        to: u64,
+        max: usize,
+    ) -> redis::RedisResult<Vec<SearchEvent>> {
+        let mut con = self.con.clone();
+        let reply: StreamRangeReply = redis::cmd("XREVRANGE")
+            .arg(&self.key)
+            .arg(to)
+            .arg(from)
+            .arg("COUNT")
+            .arg(max)
+            .query_async(&mut con)
+            .await?;
+
+        Ok(reply
+            .ids
+            .iter()
+            .filter_map(|entry| entry.get::<String>("event"))
+            .filter_map(|raw| serde_json::from_str(&raw).ok())
+            .collect())
+    }
+}
@@ -0,0 +1,124 @@
+use crate::application::search::services::relevance_evaluator::RelevanceEvaluator;
+use crate::domain::search::services::relevance_metrics_service::RelevanceMetricsService;
+use crate::domain::search::services::search_index_service::SearchIndexService;
+use crate::infrastructure::adapters::search::fuse_search_adapter::{FuseConfig, FuseSearchAdapter};
+use crate::infrastructure::persistence::config::golden_set_config::{
+    load_baseline, load_fixture, load_golden_set, save_baseline,
+};
+use anyhow::{Context, Result, bail};
+
+/// Metric drops up to this size are not reported as regressions
+pub const DEFAULT_TOLERANCE: f64 = 0.005;
+
+/// Options of `shinnku-com-backend evaluate`
+///
+/// ```text
+/// evaluate [--golden eval/golden.toml] [--fixture eval/fixture.json]
+///          [--baseline eval/baseline.json] [--write-baseline [--force]]
+///          [--tolerance 0.005] [--threshold 0.6] [--distance 800] [--no-tokenize]
+/// ```
+#[derive(Debug, Clone)]
+pub struct EvaluateArgs {
+    pub golden: String,
+    pub fixture: String,
+    pub baseline: String,
+    /// Store this run as the new baseline instead of comparing against it
+    pub write_baseline: bool,
+    /// Overwrite the baseline even if this run regresses against it
+    pub force: bool,
+    pub tolerance: f64,
+    /// Fuse settings under evaluation
+    pub fuse: FuseConfig,
+}
+
+impl Default for EvaluateArgs {
+    fn default() -> Self {
+        Self {
+            golden: "eval/golden.toml".into(),
+            fixture: "eval/fixture.json".into(),
+            baseline: "eval/baseline.json".into(),
+            write_baseline: false,
+            force: false,
+            tolerance: DEFAULT_TOLERANCE,
+            fuse: FuseConfig::default(),
+        }
+    }
+}
+
+impl EvaluateArgs {
+    /// Parse the arguments following `evaluate`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on an unknown option, a missing value or a value that
+    /// does not parse
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .with_context(|| format!("missing value for {arg}"))
+            };
+            match arg.as_str() {
+                "--golden" => parsed.golden = value()?,
+                "--fixture" => parsed.fixture = value()?,
+                "--baseline" => parsed.baseline = value()?,
+                "--write-baseline" => parsed.write_baseline = true,
+                "--force" => parsed.force = true,
+                "--tolerance" => parsed.tolerance = value()?.parse()?,
+                "--threshold" => parsed.fuse.threshold = value()?.parse()?,
+                "--distance" => parsed.fuse.distance = value()?.parse()?,
+                "--no-tokenize" => parsed.fuse.tokenize = false,
+                _ => bail!("unknown option {arg}"),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Evaluate the golden set on the fixture, print the report and its diff
+/// against the baseline, and tell whether the run is free of regressions
+///
+/// With `write_baseline`, the run replaces the baseline instead, unless it
+/// regresses against it and `force` is not set.
+///
+/// # Errors
+///
+/// Returns an error if the golden set, fixture or baseline cannot be loaded,
+/// or the baseline cannot be written
+pub async fn run(args: EvaluateArgs) -> Result<bool> {
+    let golden = load_golden_set(&args.golden).await?;
+    let fixture = load_fixture(&args.fixture).await?;
+    let items = SearchIndexService::new().build_index(&[fixture]);
+
+    let evaluator = RelevanceEvaluator::new(FuseSearchAdapter::new(args.fuse));
+    let report = evaluator.evaluate(&golden, &items);
+    println!("{report}");
+
+    let baseline = load_baseline(&args.baseline).await?;
+    let diff = baseline
+        .map(|baseline| RelevanceMetricsService::new().compare(&report, &baseline, args.tolerance));
+    match &diff {
+        Some(diff) => println!("{diff}"),
+        None => println!("no baseline at {}, nothing to compare", args.baseline),
+    }
+    let regressed = diff.is_some_and(|diff| diff.has_regressions());
+
+    if args.write_baseline {
+        if regressed && !args.force {
+            println!(
+                "not overwriting {} with a regressed run, pass --force to do so",
+                args.baseline
+            );
+            return Ok(false);
+        }
+        save_baseline(&args.baseline, &report).await?;
+        println!("baseline written to {}", args.baseline);
+        return Ok(true);
+    }
+
+    Ok(!regressed)
+}
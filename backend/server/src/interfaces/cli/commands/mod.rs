@@ -1 +1,2 @@
 // CLI commands
+pub mod evaluate;
@@ -0,0 +1,232 @@
+use crate::domain::analytics::services::search_stats_service::{LatencyPercentiles, QueryCount};
+use crate::domain::search::entities::duplicate_index::DuplicateGroup;
+use crate::error::AppError;
+use crate::infrastructure::adapters::search::fuse_search_adapter::{FuseConfig, FuseOverrides};
+use crate::interfaces::http::controllers::search_controller::run_search;
+use crate::interfaces::http::dto::admin_dto::{AnalyticsQuery, DuplicatesQuery};
+use crate::interfaces::http::dto::search_dto::SearchQuery;
+use crate::state::AppState;
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+const DEFAULT_WINDOW_HOURS: u64 = 24;
+const DEFAULT_TOP_QUERIES: usize = 50;
+const MAX_TOP_QUERIES: usize = 1000;
+const DEFAULT_DUPLICATE_GROUPS: usize = 100;
+
+#[derive(Serialize)]
+struct TopQueriesResponse {
+    window_hours: u64,
+    /// Searches recorded in the window
+    searches: usize,
+    queries: Vec<QueryCount>,
+}
+
+#[derive(Serialize)]
+struct LatencyResponse {
+    window_hours: u64,
+    /// Percentiles per endpoint, `all` covering every endpoint
+    endpoints: BTreeMap<String, LatencyPercentiles>,
+}
+
+#[derive(Serialize)]
+struct PresetsResponse<'a> {
+    default: FuseConfig,
+    presets: &'a BTreeMap<String, FuseConfig>,
+}
+
+#[derive(Serialize)]
+struct DuplicatesResponse<'a> {
+    /// Groups of files stored more than once
+    groups: usize,
+    /// Files that have at least one duplicate
+    files: usize,
+    /// Bytes that removing every duplicate would free
+    wasted_bytes: u64,
+    /// The `n` groups wasting the most bytes
+    duplicates: &'a [DuplicateGroup],
+}
+
+#[derive(Serialize)]
+struct AliasesResponse<'a> {
+    groups: usize,
+    aliases: &'a BTreeMap<String, Vec<String>>,
+}
+
+/// The alias dictionary currently used for query expansion.
+///
+/// # Errors
+///
+/// This function does not fail
+pub async fn list_aliases(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let dictionary = state.aliases.dictionary();
+    let body = AliasesResponse {
+        groups: dictionary.len(),
+        aliases: dictionary.groups(),
+    };
+    Ok((StatusCode::OK, Json(body)).into_response())
+}
+
+/// Reload the alias dictionary from its file and the Redis overlay.
+///
+/// # Errors
+///
+/// Returns an error if the file or the overlay cannot be loaded; the previous
+/// dictionary then stays in use
+pub async fn reload_aliases(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let dictionary = state.aliases.reload().await?;
+    let body = AliasesResponse {
+        groups: dictionary.len(),
+        aliases: dictionary.groups(),
+    };
+    Ok((StatusCode::OK, Json(body)).into_response())
+}
+
+/// The search presets selectable with `?preset=`, next to the default configuration.
+///
+/// # Errors
+///
+/// This function does not fail
+pub async fn list_presets(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let body = PresetsResponse {
+        default: FuseConfig::default(),
+        presets: state.presets.presets(),
+    };
+    Ok((StatusCode::OK, Json(body)).into_response())
+}
+
+/// `/search` with individual fuzzy matching settings overridden.
+///
+/// Takes every `/search` parameter plus any of `threshold`, `distance`,
+/// `max_pattern_length`, `is_case_sensitive`, `tokenize` and
+/// `file_name_only`, applied on top of `preset` or the defaults.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - An override is out of its accepted range (400 with a `code`)
+/// - The search itself fails, see [`search`](super::search_controller::search)
+pub async fn search_with_overrides(
+    State(state): State<AppState>,
+    Query(params): Query<SearchQuery>,
+    Query(overrides): Query<FuseOverrides>,
+) -> Result<impl IntoResponse, AppError> {
+    let fuse = overrides.apply(state.presets.resolve(params.preset.as_deref())?)?;
+    run_search(state, params, fuse).await
+}
+
+/// Files stored more than once, found when the index was built.
+///
+/// Lists the `n` groups (default 100) that waste the most space, each with
+/// the paths of all its copies, after totals over every group.
+///
+/// # Errors
+///
+/// This function does not fail
+pub async fn list_duplicates(
+    State(state): State<AppState>,
+    Query(params): Query<DuplicatesQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let duplicates = &state.root.duplicates;
+    let groups = duplicates.groups();
+    let limit = params
+        .n
+        .unwrap_or(DEFAULT_DUPLICATE_GROUPS)
+        .min(groups.len());
+    let body = DuplicatesResponse {
+        groups: groups.len(),
+        files: duplicates.files(),
+        wasted_bytes: duplicates.wasted_bytes(),
+        duplicates: &groups[..limit],
+    };
+    Ok((StatusCode::OK, Json(body)).into_response())
+}
+
+/// Most searched queries over the last `hours` (default 24).
+///
+/// # Errors
+///
+/// Returns an error if the search log cannot be read
+pub async fn top_queries(
+    State(state): State<AppState>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let (window_hours, window) = analytics_window(&state, params.hours);
+    let limit = params.n.unwrap_or(DEFAULT_TOP_QUERIES).min(MAX_TOP_QUERIES);
+    let (searches, queries) = state.analytics.top_queries(window, limit).await?;
+    let body = TopQueriesResponse {
+        window_hours,
+        searches,
+        queries,
+    };
+    Ok((StatusCode::OK, Json(body)).into_response())
+}
+
+/// Most searched queries that returned nothing over the last `hours`.
+///
+/// # Errors
+///
+/// Returns an error if the search log cannot be read
+pub async fn top_zero_result_queries(
+    State(state): State<AppState>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let (window_hours, window) = analytics_window(&state, params.hours);
+    let limit = params.n.unwrap_or(DEFAULT_TOP_QUERIES).min(MAX_TOP_QUERIES);
+    let (searches, queries) = state
+        .analytics
+        .top_zero_result_queries(window, limit)
+        .await?;
+    let body = TopQueriesResponse {
+        window_hours,
+        searches,
+        queries,
+    };
+    Ok((StatusCode::OK, Json(body)).into_response())
+}
+
+/// Search latency percentiles per endpoint over the last `hours`.
+///
+/// # Errors
+///
+/// Returns an error if the search log cannot be read
+pub async fn search_latency(
+    State(state): State<AppState>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let (window_hours, window) = analytics_window(&state, params.hours);
+    let endpoints = state.analytics.latency(window).await?;
+    let body = LatencyResponse {
+        window_hours,
+        endpoints,
+    };
+    Ok((StatusCode::OK, Json(body)).into_response())
+}
+
+/// Counters of the AI name service client: cache hits and misses, failures
+/// and circuit breaker trips.
+///
+/// # Errors
+///
+/// This function does not fail
+pub async fn name_service_metrics(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok((StatusCode::OK, Json(state.name_service.metrics())).into_response())
+}
+
+/// Requested report window in hours, clamped to the log retention
+fn analytics_window(state: &AppState, hours: Option<u64>) -> (u64, Duration) {
+    let max_hours = state.analytics.retention().as_secs() / 3600;
+    let hours = hours
+        .unwrap_or(DEFAULT_WINDOW_HOURS)
+        .clamp(1, max_hours.max(1));
+    (hours, Duration::from_secs(hours * 3600))
+}
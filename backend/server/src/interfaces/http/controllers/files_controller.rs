@@ -1,4 +1,7 @@
 use crate::application::files::queries::get_file_tree_query::GetFileTreeQuery;
+use crate::application::search::queries::validated_query::ValidatedQuery;
+use crate::application::shared::dto::common::resolve_offset;
+use crate::domain::files::entities::tree_node::NavigationResult;
 use crate::error::AppError;
 use crate::state::AppState;
 use crate::{
@@ -7,42 +10,66 @@ use crate::{
 };
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
 };
 
 /// Get a file or directory node by path.
 ///
+/// Folder entries come sorted, folders first, see [`files_dto::ListingQuery`]
+/// for the sorting and paging parameters.
+///
 /// # Errors
 ///
-/// Returns an error if the path is not found in the tree.
+/// Returns an error if:
+/// - The path is not found in the tree
+/// - `limit` is too large (400 with a `code`)
+/// - Both `offset` and `cursor` are given, or the cursor is malformed
 pub async fn get_node(
     Path(path): Path<String>,
+    Query(params): Query<files_dto::ListingQuery>,
     State(state): State<AppState>,
 ) -> Result<impl IntoResponse, AppError> {
-    let query = GetFileTreeQuery::new(path);
-    let handler = GetFileTreeHandler::new();
-    let domain_result = handler.handle(&query, &state.tree)?;
-
-    let dto_result = Option::<files_dto::Inode>::from(domain_result)
-        .ok_or_else(|| AppError::NotFound("Resource not found".to_string()))?;
-
-    Ok((StatusCode::OK, Json(dto_result)).into_response())
+    let query = listing_query(GetFileTreeQuery::new(path), params)?;
+    node_response(&query, &state)
 }
 
 /// Get the root directory node.
 ///
 /// # Errors
 ///
-/// Returns an error if the root path cannot be accessed.
-pub async fn get_node_root(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
-    let query = GetFileTreeQuery::root();
-    let handler = GetFileTreeHandler::new();
-    let domain_result = handler.handle(&query, &state.tree)?;
+/// Returns an error if:
+/// - The root path cannot be accessed
+/// - `limit` is too large (400 with a `code`)
+/// - Both `offset` and `cursor` are given, or the cursor is malformed
+pub async fn get_node_root(
+    Query(params): Query<files_dto::ListingQuery>,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let query = listing_query(GetFileTreeQuery::root(), params)?;
+    node_response(&query, &state)
+}
 
-    let dto_result = Option::<files_dto::Inode>::from(domain_result)
-        .ok_or_else(|| AppError::NotFound("Resource not found".to_string()))?;
+/// Apply the sorting and paging parameters to `query`
+fn listing_query(
+    query: GetFileTreeQuery,
+    params: files_dto::ListingQuery,
+) -> Result<GetFileTreeQuery, AppError> {
+    let limit = ValidatedQuery::check_limit(params.limit, "limit")?;
+    let offset = resolve_offset(params.offset, params.cursor.as_deref())?;
+    Ok(query
+        .with_sort(params.sort.unwrap_or_default(), params.order)
+        .with_page(offset, limit))
+}
+
+fn node_response(query: &GetFileTreeQuery, state: &AppState) -> Result<Response, AppError> {
+    let handler = GetFileTreeHandler::new();
+    let dto_result = match handler.handle(query, &state.tree)? {
+        NavigationResult::Folder(folder) => files_dto::Inode::folder(handler.list(query, folder)),
+        domain_result => Option::<files_dto::Inode>::from(domain_result)
+            .ok_or_else(|| AppError::NotFound("Resource not found".to_string()))?,
+    };
 
     Ok((StatusCode::OK, Json(dto_result)).into_response())
 }
@@ -1,3 +1,4 @@
+pub mod admin_controller;
 pub mod files_controller;
 pub mod search_controller;
 pub mod wiki_controller;
@@ -1,79 +1,191 @@
+use crate::application::files::services::file_tree_service::FileTreeService;
+use crate::application::search::dto::search_response::SearchResponse;
 use crate::application::search::handlers::combined_search_handler::CombinedSearchHandler;
+use crate::application::search::handlers::get_related_handler::GetRelatedHandler;
+use crate::application::search::handlers::get_suggestions_handler::GetSuggestionsHandler;
 use crate::application::search::handlers::search_files_handler::SearchFilesHandler;
 use crate::application::search::queries::combined_search_query::CombinedSearchQuery;
+use crate::application::search::queries::get_related_query::GetRelatedQuery;
+use crate::application::search::queries::get_suggestions_query::GetSuggestionsQuery;
 use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::application::search::queries::validated_query::{DEFAULT_LIMIT, ValidatedQuery};
+use crate::application::search::services::candidate_expansion_service::CandidateExpansionService;
+use crate::application::search::services::query_parser::QueryParser;
+use crate::application::shared::dto::common::resolve_offset;
+use crate::domain::files::entities::tree_node::{NavigationResult, TreeNode};
+use crate::domain::search::value_objects::cancellation::Cancellation;
+use crate::domain::search::value_objects::fusion_strategy::FusionStrategy;
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
 use crate::error::AppError;
-use crate::infrastructure::adapters::search::fuse_search_adapter::FuseSearchAdapter;
-use crate::interfaces::http::dto::search_dto::{AiSearchQuery, CombineSearchQuery, SearchQuery};
+use crate::infrastructure::adapters::search::fuse_search_adapter::{FuseConfig, FuseSearchAdapter};
+use crate::interfaces::http::dto::search_dto::{
+    AiSearchQuery, ClickBody, CombineSearchBody, CombineSearchQuery, RelatedQuery, SearchQuery,
+    SearchStreamEvent, StreamFormat, SuggestQuery,
+};
 use crate::state::AppState;
 use axum::{
     Json,
+    body::{Body, Bytes},
     extract::{Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{HeaderMap, StatusCode, header},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, Sse},
+    },
 };
-use lazy_static::lazy_static;
-use serde::Deserialize;
-use std::time::Duration;
-use tokio::task::spawn_blocking;
-
-const AI_SERVICE_URL: &str = "http://127.0.0.1:2998";
-
-lazy_static! {
-    static ref AI_CLIENT: reqwest::Client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(10))
-        .build()
-        .expect("failed to build AI service HTTP client");
-}
+use std::convert::Infallible;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::mpsc;
 
-#[derive(Deserialize)]
-struct FindNameResponse {
-    ans: Vec<String>,
-}
+const DEFAULT_AI_LIMIT: usize = 200;
+const DEFAULT_SUGGESTIONS: usize = 10;
+const MAX_SUGGESTIONS: usize = 50;
+const DEFAULT_RELATED: usize = 10;
+const MAX_RELATED: usize = 50;
+/// Events buffered between a streamed search and a slow client
+const STREAM_BUFFER: usize = 32;
 
 /// Search for files using a single query string.
 ///
+/// `q` may contain field predicates such as `bucket:galgame0 ext:rar size>1GB
+/// after:2023-01`; see [`QueryParser`] for the syntax. Pages hold `n` hits,
+/// [`DEFAULT_LIMIT`] if it is omitted.
+///
 /// # Errors
 ///
 /// Returns an error if:
 /// - The query parameter `q` is missing
+/// - `q` is blank or too long, or `n` is too large (400 with a `code`)
+/// - `q` contains a malformed field predicate (400 with a `code`)
+/// - `preset` names no known preset (400 with a `code`)
+/// - `scope` is not a folder of the file tree (404)
+/// - Both `offset` and `cursor` are given, or the cursor is malformed
+/// - Too many searches are already running or queued (503)
 /// - Task spawning fails
 /// - Search execution fails
+///
+/// With `group=true`, split archives and versions of one release come back as
+/// a single hit listing its member files. With `scope=shinnku/zd/1001-1500`,
+/// only files under that folder are searched. With `popular=true`, the best
+/// results are re-ranked by how often they are downloaded. Known aliases of
+/// names in the query are searched too; hits found through one report it in
+/// `matched_query`.
+/// With `stream=ndjson` or `stream=sse`, the finished page is sent as events,
+/// see [`SearchStreamEvent`]. With `preset=strict`, the fuzzy matching uses
+/// the named preset from `presets.toml`.
 pub async fn search(
     State(state): State<AppState>,
     Query(params): Query<SearchQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let q = params
+    let fuse = state.presets.resolve(params.preset.as_deref())?;
+    run_search(state, params, fuse).await
+}
+
+/// Run a `/search` request with the given fuzzy matching configuration
+///
+/// # Errors
+///
+/// Returns the errors listed for [`search`], apart from an unknown preset
+pub async fn run_search(
+    state: AppState,
+    params: SearchQuery,
+    fuse: FuseConfig,
+) -> Result<Response, AppError> {
+    let started = Instant::now();
+    let raw = params
         .q
         .ok_or_else(|| AppError::BadRequest("missing `q` query param".into()))?;
+    let input = ValidatedQuery::new(&raw, params.n)?;
+    let q = input.text().to_string();
+
+    let limit = Some(input.limit());
+    let offset = resolve_offset(params.offset, params.cursor.as_deref())?;
+    // Parsed as sent, so syntax error positions point into the client's input
+    let mut parsed = QueryParser::parse(&raw)?;
+    if let Some(scope) = &params.scope {
+        parsed.filters.scope = FileTreeService::resolve_scope(&state.tree, scope)?;
+    }
+    let aliases = state.aliases.dictionary().expand(&parsed.text);
+    let mut query = SearchFilesQuery::new(parsed.text, limit, offset)
+        .with_filters(parsed.filters)
+        .with_grouping(params.group)
+        .with_dedupe(params.dedupe)
+        .with_aliases(aliases)
+        .with_tuning(fuse.label());
+    let popularity = state.popularity.snapshot();
+    let mut query_popularity = None;
+    if params.popular {
+        query = query.with_popularity(popularity.generation);
+        query_popularity = state.popularity.query_snapshot(&q).await;
+    }
 
+    let cache_key = state.search_cache.files_key(&query);
     let search_index = state.root.search_index.clone();
-    let limit = params.n;
-    let query = SearchFilesQuery::new(q, limit);
 
     // Create adapter and handler
-    let adapter = FuseSearchAdapter::with_default_config();
-    let handler = SearchFilesHandler::new(adapter);
+    let cancellation = Cancellation::new();
+    let adapter = FuseSearchAdapter::new(fuse)
+        .with_cancellation(cancellation.clone())
+        .with_time_budget(state.search_executor.time_budget());
+    let config = state.popularity.config();
+    let mut handler = SearchFilesHandler::new(adapter)
+        .with_vocabulary(state.root.vocabulary.clone())
+        .with_duplicates(state.root.duplicates.clone())
+        .with_popularity(popularity, config.weight, config.window);
+    if let Some(clicks) = query_popularity {
+        handler = handler.with_query_popularity(clicks, config.query_weight);
+    }
 
-    let results = spawn_blocking(move || handler.handle(&query, &search_index))
-        .await
-        .map_err(|e| AppError::Internal(e.to_string()))?;
+    let run = async move {
+        // Every page of the search is cut from the same cached match set
+        let (offset, limit) = (query.offset, query.limit);
+        let query = query.unpaged();
+        let results = state
+            .search_executor
+            .run(cache_key, move || handler.handle(&query, &search_index))
+            .await?;
+        let results = Arc::new(results.page(offset, limit));
+        // Later pages of a search are not searches of their own
+        if offset == 0 {
+            state
+                .analytics
+                .record("search", &q, results.page.total, started.elapsed(), false);
+        }
+        Ok(results)
+    };
 
-    Ok((StatusCode::OK, Json(results)).into_response())
+    match params.stream {
+        Some(format) => Ok(stream_search(format, cancellation, run)),
+        None => {
+            // Dropped with the request when the client disconnects
+            let _cancel = cancellation.cancel_on_drop();
+            Ok((StatusCode::OK, Json(run.await?.as_ref())).into_response())
+        }
+    }
 }
 
 /// Search for files using two combined query strings.
 ///
+/// Results of `q1` and `q2` are fused with `strategy` (`average` by default,
+/// `min` or `rrf`).
+///
 /// # Errors
 ///
 /// Returns an error if:
 /// - Either query parameter `q1` or `q2` is missing
+/// - Either query is blank or too long, or `n` is too large (400 with a `code`)
+/// - `preset` names no known preset (400 with a `code`)
+/// - Both `offset` and `cursor` are given, or the cursor is malformed
+/// - Too many searches are already running or queued (503)
 /// - Task spawning fails
 /// - Combined search execution fails
 pub async fn search_combined(
     State(state): State<AppState>,
     Query(params): Query<CombineSearchQuery>,
 ) -> Result<impl IntoResponse, AppError> {
+    let started = Instant::now();
     let (q1, q2) = match (params.q1, params.q2) {
         (Some(q1), Some(q2)) => (q1, q2),
         _ => {
@@ -83,69 +195,392 @@ pub async fn search_combined(
         }
     };
 
+    let q1 = ValidatedQuery::new(&q1, params.n)?;
+    let q2 = ValidatedQuery::new(&q2, params.n)?;
+
+    let limit = q1.limit();
+    let offset = resolve_offset(params.offset, params.cursor.as_deref())?;
+    let queries = vec![
+        WeightedQuery::unweighted(q1.text().to_string()),
+        WeightedQuery::unweighted(q2.text().to_string()),
+    ];
+    let query = CombinedSearchQuery::new(queries, limit, offset)
+        .with_strategy(params.strategy.unwrap_or_default())
+        .with_grouping(params.group);
+
+    let fuse = state.presets.resolve(params.preset.as_deref())?;
+    let cancellation = Cancellation::new();
+    let _cancel = cancellation.cancel_on_drop();
+    let results = run_combined_search(&state, &query, fuse, cancellation).await?;
+    record_combined(&state, "combinesearch", &query, &results, started);
+    Ok((StatusCode::OK, Json(results.as_ref())).into_response())
+}
+
+/// Search for files using any number of weighted query strings.
+///
+/// Body: `{"queries": [{"q": "...", "weight": 2.0}, ...], "strategy": "rrf"}`
+/// plus the usual `n`, `offset`, `cursor`, `group` and `preset` fields. Weights default
+/// to 1, the strategy to `average`.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - No query is given, too many are given, or a weight is not positive
+/// - A query is blank or too long, or `n` is too large (400 with a `code`)
+/// - `preset` names no known preset (400 with a `code`)
+/// - Both `offset` and `cursor` are given, or the cursor is malformed
+/// - Task spawning fails
+pub async fn search_combined_post(
+    State(state): State<AppState>,
+    Json(body): Json<CombineSearchBody>,
+) -> Result<impl IntoResponse, AppError> {
+    let started = Instant::now();
+    let limit = ValidatedQuery::check_limit(body.n, "n")?.unwrap_or(DEFAULT_LIMIT);
+    let offset = resolve_offset(body.offset, body.cursor.as_deref())?;
+    let queries = body
+        .queries
+        .into_iter()
+        .map(|query| {
+            let text = ValidatedQuery::new(&query.q, None)?;
+            Ok(WeightedQuery::new(
+                text.text().to_string(),
+                query.weight.unwrap_or(1.0),
+            ))
+        })
+        .collect::<Result<_, AppError>>()?;
+    let query = CombinedSearchQuery::new(queries, limit, offset)
+        .with_strategy(body.strategy.unwrap_or_default())
+        .with_grouping(body.group);
+
+    let fuse = state.presets.resolve(body.preset.as_deref())?;
+    let cancellation = Cancellation::new();
+    let _cancel = cancellation.cancel_on_drop();
+    let results = run_combined_search(&state, &query, fuse, cancellation).await?;
+    record_combined(&state, "combinesearch", &query, &results, started);
+    Ok((StatusCode::OK, Json(results.as_ref())).into_response())
+}
+
+/// Validate and run a combined search through the search executor
+async fn run_combined_search(
+    state: &AppState,
+    query: &CombinedSearchQuery,
+    fuse: FuseConfig,
+    cancellation: Cancellation,
+) -> Result<Arc<SearchResponse>, AppError> {
+    query.validate()?;
+
+    let query = query.clone().with_tuning(fuse.label());
+    let cache_key = state.search_cache.combined_key(&query);
     let search_index = state.root.search_index.clone();
-    let limit = params.n.unwrap_or(100);
-    let query = CombinedSearchQuery::new(q1, q2, limit);
 
     // Create adapter and handler
-    let adapter = FuseSearchAdapter::with_default_config();
+    let adapter = FuseSearchAdapter::new(fuse)
+        .with_cancellation(cancellation)
+        .with_time_budget(state.search_executor.time_budget());
     let handler = CombinedSearchHandler::new(adapter);
 
-    let results = spawn_blocking(move || handler.handle(&query, &search_index))
-        .await
-        .map_err(|e| AppError::Internal(e.to_string()))?;
+    // Every page of the search is cut from the same cached match set
+    let (offset, limit) = (query.offset, query.limit);
+    let query = query.unpaged();
+    let results = state
+        .search_executor
+        .run(cache_key, move || handler.handle(&query, &search_index))
+        .await?;
+    Ok(Arc::new(results.page(offset, Some(limit))))
+}
 
-    Ok((StatusCode::OK, Json(results)).into_response())
+/// Record the first page of a combined search for analytics, its variants
+/// joined by ` | `
+fn record_combined(
+    state: &AppState,
+    endpoint: &str,
+    query: &CombinedSearchQuery,
+    results: &SearchResponse,
+    started: Instant,
+) {
+    if query.offset > 0 {
+        return;
+    }
+    let text: Vec<&str> = query.queries.iter().map(|q| q.text.as_str()).collect();
+    state.analytics.record(
+        endpoint,
+        &text.join(" | "),
+        results.page.total,
+        started.elapsed(),
+        false,
+    );
 }
 
-/// One-shot AI search: hits the Python `/findname` to canonicalize the query
-/// name, then runs a combined fuse search using (canonical_name, raw_query).
+/// One-shot AI search: hits the Python `/findname` for canonical names of the
+/// query, then runs a combined fuse search over the raw query and every
+/// candidate, weighted by candidate rank and fused with reciprocal rank
+/// fusion. Aliases of the query from the alias dictionary come before the AI
+/// candidates, at the alias weight. Each hit reports the variant that found
+/// it in `matched_query`. Takes `stream` and `preset` like [`search`].
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// - The query parameter `q` is missing
+/// - `q` is blank or too long, or `n` is too large (400 with a `code`)
+/// - `preset` names no known preset (400 with a `code`)
+/// - Both `offset` and `cursor` are given, or the cursor is malformed
 /// - Task spawning fails
 pub async fn ai_search(
     State(state): State<AppState>,
     Query(params): Query<AiSearchQuery>,
 ) -> Result<impl IntoResponse, AppError> {
+    let started = Instant::now();
     let q = params
         .q
         .ok_or_else(|| AppError::BadRequest("missing `q` query param".into()))?;
-    let limit = params.n.unwrap_or(200);
-
-    // Best-effort name canonicalization via the AI service. On any failure
-    // (network / timeout / parse) we degrade to an empty q1 so the fuse
-    // search still runs on the raw user query.
-    let q1 = match AI_CLIENT
-        .get(format!("{AI_SERVICE_URL}/findname"))
-        .query(&[("name", q.as_str())])
-        .send()
-        .await
-    {
-        Ok(resp) => match resp.json::<FindNameResponse>().await {
-            Ok(body) => body.ans.into_iter().next().unwrap_or_default(),
-            Err(e) => {
-                tracing::warn!("/findname parse error: {e}");
-                String::new()
+    let input = ValidatedQuery::new(&q, params.n.or(Some(DEFAULT_AI_LIMIT)))?;
+    let q = input.text().to_string();
+    let limit = input.limit();
+    let offset = resolve_offset(params.offset, params.cursor.as_deref())?;
+    let fuse = state.presets.resolve(params.preset.as_deref())?;
+
+    // Best-effort name canonicalization via the AI service, cached and
+    // behind a circuit breaker. On any failure we get no candidates and the
+    // fuse search still runs on the raw user query.
+    let names = state.name_service.find_names(&q).await;
+    let ai = names.is_some();
+    let aliases = state.aliases.dictionary().expand(&q);
+
+    let queries = CandidateExpansionService::expand(&q, aliases, names.unwrap_or_default());
+    let query = CombinedSearchQuery::new(queries, limit, offset)
+        .with_strategy(FusionStrategy::Rrf)
+        .with_grouping(params.group);
+
+    let cancellation = Cancellation::new();
+    let run = {
+        let cancellation = cancellation.clone();
+        async move {
+            let results = run_combined_search(&state, &query, fuse, cancellation).await?;
+            // Logged once per search under the user's query, not the expanded variants
+            if offset == 0 {
+                state
+                    .analytics
+                    .record("aisearch", &q, results.page.total, started.elapsed(), ai);
             }
-        },
-        Err(e) => {
-            tracing::warn!("/findname request error: {e}");
-            String::new()
+            Ok(results)
         }
     };
 
-    let search_index = state.root.search_index.clone();
-    let query = CombinedSearchQuery::new(q1, q.clone(), limit);
+    match params.stream {
+        Some(format) => Ok(stream_search(format, cancellation, run)),
+        None => {
+            // Dropped with the request when the client disconnects
+            let _cancel = cancellation.cancel_on_drop();
+            Ok((StatusCode::OK, Json(run.await?.as_ref())).into_response())
+        }
+    }
+}
 
-    let adapter = FuseSearchAdapter::with_default_config();
-    let handler = CombinedSearchHandler::new(adapter);
+/// Respond at once and stream the events of `search` once it has finished
+fn stream_search<F>(format: StreamFormat, cancellation: Cancellation, search: F) -> Response
+where
+    F: Future<Output = Result<Arc<SearchResponse>, AppError>> + Send + 'static,
+{
+    let mut events = spawn_search_events(cancellation, search);
+    let events = futures_util::stream::poll_fn(move |cx| events.poll_recv(cx));
+
+    match format {
+        StreamFormat::Ndjson => {
+            let lines = futures_util::StreamExt::map(events, |event| {
+                let mut line = serde_json::to_vec(&event).unwrap_or_default();
+                line.push(b'\n');
+                Ok::<_, Infallible>(Bytes::from(line))
+            });
+            (
+                [(header::CONTENT_TYPE, "application/x-ndjson")],
+                Body::from_stream(lines),
+            )
+                .into_response()
+        }
+        StreamFormat::Sse => Sse::new(futures_util::StreamExt::map(events, |event| {
+            let data = match &event {
+                SearchStreamEvent::Hit(hit) => serde_json::to_string(hit),
+                SearchStreamEvent::Done(summary) => serde_json::to_string(summary),
+                SearchStreamEvent::Error(error) => serde_json::to_string(error),
+            };
+            Ok::<_, Infallible>(
+                Event::default()
+                    .event(event.name())
+                    .data(data.unwrap_or_default()),
+            )
+        }))
+        .into_response(),
+    }
+}
+
+/// Run `search` in the background and send its events to the returned receiver
+///
+/// Dropping the receiver, as the response body is when the client disconnects,
+/// sets `cancellation` so the scan behind `search` stops early. The cancelled
+/// result is never awaited, so it does not reach the cache.
+pub fn spawn_search_events<F>(
+    cancellation: Cancellation,
+    search: F,
+) -> mpsc::Receiver<SearchStreamEvent>
+where
+    F: Future<Output = Result<Arc<SearchResponse>, AppError>> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+    tokio::spawn(async move {
+        let result = tokio::select! {
+            result = search => result,
+            () = tx.closed() => {
+                cancellation.cancel();
+                return;
+            }
+        };
+        for event in SearchStreamEvent::sequence(result.as_deref()) {
+            if tx.send(event).await.is_err() {
+                return;
+            }
+        }
+    });
+    rx
+}
 
-    let results = spawn_blocking(move || handler.handle(&query, &search_index))
-        .await
-        .map_err(|e| AppError::Internal(e.to_string()))?;
+/// Complete a partially typed query with game titles and folder names.
+///
+/// Served from the prefix index built at bootstrap, without a fuzzy scan.
+///
+/// # Errors
+///
+/// Returns an error if the query parameter `q` is missing
+pub async fn suggest(
+    State(state): State<AppState>,
+    Query(params): Query<SuggestQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let q = params
+        .q
+        .ok_or_else(|| AppError::BadRequest("missing `q` query param".into()))?;
+    let limit = params.n.unwrap_or(DEFAULT_SUGGESTIONS).min(MAX_SUGGESTIONS);
+
+    let query = GetSuggestionsQuery::new(q, limit);
+    let handler = GetSuggestionsHandler::new();
+    let suggestions = handler.handle(&query, &state.root.suggest_index);
+
+    Ok((StatusCode::OK, Json(suggestions)).into_response())
+}
 
-    Ok((StatusCode::OK, Json(results)).into_response())
+/// Files similar to the file at `path`: similar titles, the same brand and
+/// the same folder.
+///
+/// Computed over the search index without a fuzzy scan, under the same limits
+/// and time budget as searches; `n` (default 10, at most 50) caps the number
+/// of files returned.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The query parameter `path` is missing
+/// - `path` is not a file of the index (404)
+/// - Too many searches are already running or queued (503)
+/// - Task spawning fails
+pub async fn related(
+    State(state): State<AppState>,
+    Query(params): Query<RelatedQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let path = params
+        .path
+        .ok_or_else(|| AppError::BadRequest("missing `path` query param".into()))?;
+    let limit = params.n.unwrap_or(DEFAULT_RELATED).min(MAX_RELATED);
+
+    // The longest list is cached once per file and cut to the requested length
+    let key = state.search_cache.related_key(&path);
+    let hits = match state.search_cache.get_related(&key) {
+        Some(hits) => hits,
+        None => {
+            let cancellation = Cancellation::new();
+            let handler = GetRelatedHandler::new()
+                .with_cancellation(cancellation.clone())
+                .with_time_budget(state.search_executor.time_budget());
+            let query = GetRelatedQuery::new(path, MAX_RELATED);
+            let search_index = state.root.search_index.clone();
+
+            // Dropped with the request when the client disconnects
+            let _cancel = cancellation.cancel_on_drop();
+            let related = state
+                .search_executor
+                .run_blocking(move || handler.handle(&query, &search_index))
+                .await??;
+
+            // A partial list depends on load at the time, so it is not reused
+            let hits = Arc::new(related.hits);
+            if !related.partial {
+                state.search_cache.put_related(key, hits.clone());
+            }
+            hits
+        }
+    };
+    let related = &hits[..limit.min(hits.len())];
+
+    Ok((StatusCode::OK, Json(related)).into_response())
+}
+
+/// Record that a file was opened from the search results.
+///
+/// Body: `{"path": "<info.file_path of the hit>", "q": "<query>"}`. Clicks
+/// feed the `popular=true` ranking of `/search`, those made from the same
+/// query count extra. Clients are told apart by [`client_address`], and
+/// repeated clicks of one client on a file only count once per dedupe window;
+/// clicks without a client address are not deduplicated.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - `q` is empty or too long
+/// - `path` is not a file of the index (404)
+/// - The click cannot be stored
+pub async fn record_click(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<ClickBody>,
+) -> Result<impl IntoResponse, AppError> {
+    let query = body
+        .q
+        .as_deref()
+        .map(|q| ValidatedQuery::new(q, None))
+        .transpose()?;
+    let tree_path = FileTreeService::tree_path(&body.path);
+    if !matches!(
+        state.tree.navigate(&TreeNode::path_segments(&tree_path)),
+        NavigationResult::File { .. }
+    ) {
+        return Err(AppError::NotFound(format!(
+            "file '{}' not found",
+            body.path
+        )));
+    }
+
+    let client = client_address(&headers).map(|ip| ip.to_string());
+    state
+        .popularity
+        .record_click(
+            &body.path,
+            query.as_ref().map(ValidatedQuery::text),
+            client.as_deref(),
+        )
+        .await?;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+/// Address of the client a request was made for
+///
+/// The last `X-Forwarded-For` entry is the one added by the proxy in front of
+/// the backend, earlier ones can be forged by the client.
+pub fn client_address(headers: &HeaderMap) -> Option<IpAddr> {
+    headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .next_back()?
+        .trim()
+        .parse()
+        .ok()
 }
@@ -0,0 +1,15 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct AnalyticsQuery {
+    /// Length of the report window ending now, in hours
+    pub hours: Option<u64>,
+    #[serde(alias = "limit")]
+    pub n: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct DuplicatesQuery {
+    #[serde(alias = "limit")]
+    pub n: Option<usize>,
+}
@@ -1,20 +1,75 @@
+use crate::application::shared::dto::common::Page;
 use crate::domain::files::entities::file_info::FileInfo;
-use serde::Serialize;
+use crate::domain::files::entities::tree_node::NodeType;
+use crate::domain::files::value_objects::listing_order::{ListingSort, SortOrder};
+use crate::domain::search::value_objects::release_info::ReleaseInfo;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct ListingQuery {
+    /// `name` (default), `size` or `date`; folders always come first
+    pub sort: Option<ListingSort>,
+    /// `asc` or `desc`, by default ascending for names and descending otherwise
+    pub order: Option<SortOrder>,
+    pub offset: Option<usize>,
+    pub cursor: Option<String>,
+    #[serde(alias = "n")]
+    pub limit: Option<usize>,
+}
 
 #[derive(Serialize, Clone)]
 #[serde(tag = "type")]
 pub enum Node {
     #[serde(rename = "file")]
-    File { name: String, info: FileInfo },
+    File {
+        name: String,
+        info: FileInfo,
+        release: ReleaseInfo,
+    },
     #[serde(rename = "folder")]
     Folder { name: String },
 }
 
+impl From<(&String, &NodeType)> for Node {
+    fn from((name, node): (&String, &NodeType)) -> Self {
+        match node {
+            NodeType::File(info) => Self::File {
+                name: name.clone(),
+                info: info.clone(),
+                release: ReleaseInfo::parse(name),
+            },
+            NodeType::Node(_) => Self::Folder { name: name.clone() },
+        }
+    }
+}
+
 #[derive(Serialize)]
 #[serde(tag = "type")]
 pub enum Inode {
     #[serde(rename = "folder")]
-    Folder { data: Vec<Node> },
+    Folder {
+        /// Entries on this page, folders first
+        data: Vec<Node>,
+        /// Number of entries in the folder across all pages
+        total: usize,
+        /// Cursor of the next page, `None` on the last page
+        next_cursor: Option<String>,
+    },
     #[serde(rename = "file")]
-    File { name: String, info: FileInfo },
+    File {
+        name: String,
+        info: FileInfo,
+        release: ReleaseInfo,
+    },
+}
+
+impl Inode {
+    /// Folder response for one page of its sorted entries
+    pub fn folder(page: Page<(&String, &NodeType)>) -> Self {
+        Self::Folder {
+            data: page.results.into_iter().map(Node::from).collect(),
+            total: page.total,
+            next_cursor: page.next_cursor,
+        }
+    }
 }
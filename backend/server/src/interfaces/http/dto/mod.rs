@@ -1,3 +1,4 @@
+pub mod admin_dto;
 pub mod files_dto;
 pub mod search_dto;
 pub mod wiki_dto;
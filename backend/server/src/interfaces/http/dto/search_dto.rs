@@ -1,20 +1,180 @@
-use serde::Deserialize;
+use crate::application::search::dto::search_hit::SearchHit;
+use crate::application::search::dto::search_response::SearchResponse;
+use crate::application::search::services::facet_service::SearchFacets;
+use crate::domain::search::value_objects::fusion_strategy::FusionStrategy;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize)]
 pub struct SearchQuery {
     pub q: Option<String>,
+    #[serde(alias = "limit")]
     pub n: Option<usize>,
+    pub offset: Option<usize>,
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub group: bool,
+    /// Fold copies of the same file in several places into one hit
+    #[serde(default)]
+    pub dedupe: bool,
+    /// Folder of the combined tree to search in, e.g. `shinnku/zd`
+    pub scope: Option<String>,
+    /// Blend download popularity into the ranking
+    #[serde(default)]
+    pub popular: bool,
+    /// Send the finished page as events instead of one JSON document
+    pub stream: Option<StreamFormat>,
+    /// Named fuzzy matching preset, see `presets.toml`
+    pub preset: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct CombineSearchQuery {
     pub q1: Option<String>,
     pub q2: Option<String>,
+    pub strategy: Option<FusionStrategy>,
+    #[serde(alias = "limit")]
     pub n: Option<usize>,
+    pub offset: Option<usize>,
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub group: bool,
+    pub preset: Option<String>,
+}
+
+/// JSON body of `POST /combinesearch`
+#[derive(Deserialize)]
+pub struct CombineSearchBody {
+    pub queries: Vec<WeightedQueryBody>,
+    pub strategy: Option<FusionStrategy>,
+    #[serde(alias = "limit")]
+    pub n: Option<usize>,
+    pub offset: Option<usize>,
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub group: bool,
+    pub preset: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct WeightedQueryBody {
+    pub q: String,
+    pub weight: Option<f64>,
 }
 
 #[derive(Deserialize)]
 pub struct AiSearchQuery {
+    pub q: Option<String>,
+    #[serde(alias = "limit")]
+    pub n: Option<usize>,
+    pub offset: Option<usize>,
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub group: bool,
+    /// Send the finished page as events instead of one JSON document
+    pub stream: Option<StreamFormat>,
+    pub preset: Option<String>,
+}
+
+/// Wire format of a streamed search, see [`SearchStreamEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamFormat {
+    /// One JSON event per line, `application/x-ndjson`
+    Ndjson,
+    /// Server-sent events named after the event, `text/event-stream`
+    Sse,
+}
+
+/// One event of a streamed search
+///
+/// Hits come first, in rank order, followed by a single `done` event; a
+/// search that fails sends a single `error` event instead. As NDJSON each
+/// event is an object keyed by its name, e.g. `{"hit": {...}}`.
+///
+/// The events are sent once the whole page has been computed, the stream
+/// does not deliver hits while the scan is still running. What it adds over
+/// JSON is that headers go out at once and a disconnect cancels the scan.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchStreamEvent {
+    Hit(SearchHit),
+    Done(SearchStreamSummary),
+    Error(SearchStreamError),
+}
+
+/// Everything of a search response but its hits
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SearchStreamSummary {
+    pub total: usize,
+    pub next_cursor: Option<String>,
+    pub facets: SearchFacets,
+    pub did_you_mean: Vec<String>,
+    pub partial: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SearchStreamError {
+    pub message: String,
+}
+
+impl SearchStreamEvent {
+    /// Events streaming the outcome of a search
+    pub fn sequence(result: Result<&SearchResponse, &AppError>) -> Vec<Self> {
+        let response = match result {
+            Ok(response) => response,
+            Err(e) => {
+                return vec![Self::Error(SearchStreamError {
+                    message: e.to_string(),
+                })];
+            }
+        };
+
+        response
+            .page
+            .results
+            .iter()
+            .cloned()
+            .map(Self::Hit)
+            .chain(std::iter::once(Self::Done(SearchStreamSummary {
+                total: response.page.total,
+                next_cursor: response.page.next_cursor.clone(),
+                facets: response.facets.clone(),
+                did_you_mean: response.did_you_mean.clone(),
+                partial: response.partial,
+            })))
+            .collect()
+    }
+
+    /// Event name used for server-sent events
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Hit(_) => "hit",
+            Self::Done(_) => "done",
+            Self::Error(_) => "error",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RelatedQuery {
+    /// Bucket path of the file, as in `info.file_path` of a search hit
+    pub path: Option<String>,
+    #[serde(alias = "limit")]
+    pub n: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct SuggestQuery {
     pub q: Option<String>,
     pub n: Option<usize>,
 }
+
+/// JSON body of `POST /click`
+#[derive(Deserialize)]
+pub struct ClickBody {
+    /// Bucket path of the opened file, as in `info.file_path` of a search hit
+    pub path: String,
+    /// Query the file was found with
+    pub q: Option<String>,
+}
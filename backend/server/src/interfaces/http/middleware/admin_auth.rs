@@ -0,0 +1,70 @@
+use crate::error::AppError;
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, header},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+
+/// Bearer token guarding the `/admin` routes
+///
+/// Without a configured token every admin request is rejected, so a missing
+/// `[admin]` section in `config.toml` cannot leave the admin API open.
+#[derive(Debug, Clone, Default)]
+pub struct AdminAuth {
+    token: Option<Arc<str>>,
+}
+
+impl AdminAuth {
+    pub fn new(token: Option<String>) -> Self {
+        Self {
+            token: token.filter(|token| !token.is_empty()).map(Arc::from),
+        }
+    }
+
+    /// Whether any admin request can be let through
+    pub fn is_enabled(&self) -> bool {
+        self.token.is_some()
+    }
+
+    /// Check the `Authorization: Bearer <token>` header of a request
+    ///
+    /// # Errors
+    ///
+    /// Returns `Unauthorized` if no token is configured, the header is
+    /// missing or the token does not match
+    pub fn check(&self, headers: &HeaderMap) -> Result<(), AppError> {
+        let Some(expected) = &self.token else {
+            return Err(AppError::Unauthorized("admin API is disabled".into()));
+        };
+        let given = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| AppError::Unauthorized("missing admin token".into()))?;
+        if !Self::constant_time_eq(given.trim().as_bytes(), expected.as_bytes()) {
+            return Err(AppError::Unauthorized("invalid admin token".into()));
+        }
+        Ok(())
+    }
+
+    /// Compare without returning early, so timing does not leak the token
+    fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+    }
+}
+
+/// Middleware letting only requests with the admin token through
+///
+/// # Errors
+///
+/// Returns `Unauthorized` if the request fails [`AdminAuth::check`]
+pub async fn require_admin(
+    State(auth): State<AdminAuth>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    auth.check(request.headers())?;
+    Ok(next.run(request).await)
+}
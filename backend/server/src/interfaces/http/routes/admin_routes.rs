@@ -0,0 +1,26 @@
+use crate::interfaces::http::controllers::admin_controller::{
+    list_aliases, list_duplicates, list_presets, name_service_metrics, reload_aliases,
+    search_latency, search_with_overrides, top_queries, top_zero_result_queries,
+};
+use crate::interfaces::http::middleware::admin_auth::{AdminAuth, require_admin};
+use crate::state::AppState;
+use axum::{
+    Router,
+    middleware::from_fn_with_state,
+    routing::{get, post},
+};
+
+/// Admin routes, all behind the bearer token of `auth`
+pub fn admin_router(auth: AdminAuth) -> Router<AppState> {
+    Router::new()
+        .route("/aliases", get(list_aliases))
+        .route("/aliases/reload", post(reload_aliases))
+        .route("/duplicates", get(list_duplicates))
+        .route("/analytics/top-queries", get(top_queries))
+        .route("/analytics/zero-results", get(top_zero_result_queries))
+        .route("/analytics/latency", get(search_latency))
+        .route("/aisearch/metrics", get(name_service_metrics))
+        .route("/presets", get(list_presets))
+        .route("/search", get(search_with_overrides))
+        .route_layer(from_fn_with_state(auth, require_admin))
+}
@@ -1,20 +1,34 @@
 use crate::infrastructure::web::http::proxy_service::ProxyService;
 use crate::interfaces::http::controllers::{
-    search_controller::{ai_search, search, search_combined},
+    search_controller::{
+        ai_search, record_click, related, search, search_combined, search_combined_post, suggest,
+    },
     wiki_controller::wiki_search_picture,
 };
+use crate::interfaces::http::middleware::admin_auth::AdminAuth;
+use crate::interfaces::http::routes::admin_routes::admin_router;
 use crate::interfaces::http::routes::files_routes::files_router;
 use crate::state::AppState;
-use axum::{Router, routing::get};
+use axum::{
+    Router,
+    routing::{get, post},
+};
 
-pub fn app_router() -> Router<AppState> {
+pub fn app_router(admin: AdminAuth) -> Router<AppState> {
     let proxy = ProxyService::new("http://127.0.0.1:2998");
     Router::new()
         .route_service("/intro", proxy.clone())
         .route_service("/findname", proxy)
         .route("/search", get(search))
-        .route("/combinesearch", get(search_combined))
+        .route(
+            "/combinesearch",
+            get(search_combined).post(search_combined_post),
+        )
         .route("/aisearch", get(ai_search))
+        .route("/suggest", get(suggest))
+        .route("/related", get(related))
+        .route("/click", post(record_click))
         .route("/wikisearchpicture", get(wiki_search_picture))
         .nest("/files", files_router())
+        .nest("/admin", admin_router(admin))
 }
@@ -1,3 +1,4 @@
+pub mod admin_routes;
 pub mod app_router;
 pub mod files_routes;
 pub mod search_routes;
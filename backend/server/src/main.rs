@@ -9,9 +9,30 @@ mod state;
 #[cfg(test)]
 mod tests;
 
+use crate::application::analytics::services::search_analytics_service::{
+    AnalyticsConfig, SearchAnalyticsService,
+};
+use crate::application::search::services::alias_service::{AliasConfig, AliasService};
+use crate::application::search::services::popularity_tracker::{
+    PopularityConfig, PopularityTracker,
+};
+use crate::application::search::services::preset_service::{PresetConfig, PresetService};
+use crate::application::search::services::search_cache_service::{
+    SearchCacheConfig, SearchCacheService,
+};
+use crate::application::search::services::search_execution_service::{
+    SearchExecutionConfig, SearchExecutionService,
+};
 use crate::application::shared::services::application_bootstrap_service::ApplicationBootstrapService;
+use crate::infrastructure::external_services::name_service_client::{
+    NameServiceClient, NameServiceConfig,
+};
+use crate::infrastructure::persistence::redis::connection::{self, load_config};
+use crate::interfaces::cli::commands::evaluate::{self, EvaluateArgs};
+use crate::interfaces::http::middleware::admin_auth::AdminAuth;
 use crate::interfaces::http::routes::app_router::app_router;
 use state::AppState;
+use std::sync::Arc;
 use tower_http::trace::TraceLayer;
 use tracing::info;
 use tracing_subscriber::fmt;
@@ -21,16 +42,54 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     color_eyre::install()?;
     fmt::init();
 
-    let redis = infrastructure::persistence::redis::connection::connect_redis().await?;
+    // `shinnku-com-backend evaluate ...` scores search relevance and exits
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() == Some("evaluate") {
+        if !evaluate::run(EvaluateArgs::parse(args)?).await? {
+            return Err("search relevance regressed against the baseline".into());
+        }
+        return Ok(());
+    }
+
+    let settings = load_config("config.toml").await?;
+    let redis = connection::connect(&settings.redis).await?;
     let bootstrap_service = ApplicationBootstrapService::new();
     let root = bootstrap_service.initialize().await?;
+    let name_service = NameServiceClient::new(NameServiceConfig::default(), Some(redis.clone()))?;
+    let search_cache = SearchCacheService::new(
+        root.index_version.clone(),
+        SearchCacheConfig::default(),
+        Some(redis.clone()),
+    );
+    let aliases = AliasService::load(AliasConfig::default(), Some(redis.clone())).await;
+    let analytics = SearchAnalyticsService::new(AnalyticsConfig::default(), Some(redis.clone()));
+    let popularity = Arc::new(PopularityTracker::new(
+        PopularityConfig::default(),
+        Some(redis.clone()),
+    ));
+    popularity.clone().spawn_refresh();
+    let presets = PresetService::load(PresetConfig::default()).await;
+    let search_cache = Arc::new(search_cache);
+    let search_executor =
+        SearchExecutionService::new(search_cache.clone(), SearchExecutionConfig::default());
     let state = AppState {
         redis,
         root: root.clone(),
         tree: root.combined_tree,
+        name_service: Arc::new(name_service),
+        search_cache,
+        search_executor: Arc::new(search_executor),
+        aliases: Arc::new(aliases),
+        analytics: Arc::new(analytics),
+        popularity,
+        presets: Arc::new(presets),
     };
 
-    let app = app_router()
+    let admin = AdminAuth::new(settings.admin.token);
+    if !admin.is_enabled() {
+        tracing::warn!("No admin token in config.toml, the /admin routes are closed");
+    }
+    let app = app_router(admin)
         .with_state(state)
         .layer(TraceLayer::new_for_http());
 
@@ -1,12 +1,27 @@
+use crate::application::analytics::services::search_analytics_service::SearchAnalyticsService;
+use crate::application::search::services::alias_service::AliasService;
+use crate::application::search::services::popularity_tracker::PopularityTracker;
+use crate::application::search::services::preset_service::PresetService;
+use crate::application::search::services::search_cache_service::SearchCacheService;
+use crate::application::search::services::search_execution_service::SearchExecutionService;
+use crate::infrastructure::external_services::name_service_client::NameServiceClient;
 use crate::{
     application::shared::services::application_bootstrap_service::ApplicationData,
     domain::files::entities::tree_node::TreeNode,
 };
 use redis::aio::ConnectionManager;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct AppState {
     pub redis: ConnectionManager,
     pub root: ApplicationData,
     pub tree: TreeNode,
+    pub name_service: Arc<NameServiceClient>,
+    pub search_cache: Arc<SearchCacheService>,
+    pub search_executor: Arc<SearchExecutionService>,
+    pub aliases: Arc<AliasService>,
+    pub analytics: Arc<SearchAnalyticsService>,
+    pub popularity: Arc<PopularityTracker>,
+    pub presets: Arc<PresetService>,
 }
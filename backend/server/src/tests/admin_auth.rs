@@ -0,0 +1,56 @@
+use crate::error::AppError;
+use crate::infrastructure::persistence::redis::connection::Settings;
+use crate::interfaces::http::middleware::admin_auth::AdminAuth;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::IntoResponse;
+
+fn bearer(token: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::AUTHORIZATION,
+        format!("Bearer {token}").parse().unwrap(),
+    );
+    headers
+}
+
+#[test]
+fn test_admin_token_checked() {
+    let auth = AdminAuth::new(Some("s3cret".into()));
+    assert!(auth.check(&bearer("s3cret")).is_ok());
+
+    for headers in [HeaderMap::new(), bearer("s3cre"), bearer("s3cret2")] {
+        let err = auth.check(&headers).unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let mut basic = HeaderMap::new();
+    basic.insert(header::AUTHORIZATION, "Basic s3cret".parse().unwrap());
+    assert!(matches!(auth.check(&basic), Err(AppError::Unauthorized(_))));
+}
+
+#[test]
+fn test_admin_closed_without_token() {
+    for auth in [AdminAuth::new(None), AdminAuth::new(Some(String::new()))] {
+        assert!(!auth.is_enabled());
+        assert!(auth.check(&bearer("")).is_err());
+        assert!(auth.check(&HeaderMap::new()).is_err());
+    }
+}
+
+#[test]
+fn test_admin_token_from_config() {
+    let raw = r#"
+        [redis]
+        host = "127.0.0.1"
+        port = 6379
+        database = 0
+
+        [admin]
+        token = "s3cret"
+    "#;
+    let settings: Settings = toml::from_str(raw).unwrap();
+    assert_eq!(settings.admin.token.as_deref(), Some("s3cret"));
+
+    let without: Settings = toml::from_str(raw.split("[admin]").next().unwrap()).unwrap();
+    assert!(without.admin.token.is_none());
+}
@@ -0,0 +1,108 @@
+use crate::application::search::handlers::combined_search_handler::CombinedSearchHandler;
+use crate::application::search::queries::combined_search_query::CombinedSearchQuery;
+use crate::application::search::services::candidate_expansion_service::{
+    CandidateExpansionService, MAX_CANDIDATES,
+};
+use crate::domain::search::entities::alias_dictionary::ALIAS_WEIGHT;
+use crate::domain::search::value_objects::fusion_strategy::FusionStrategy;
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
+use crate::tests::support;
+
+#[test]
+fn test_expand_dedupes_and_decays() {
+    let queries = CandidateExpansionService::expand(
+        "サマポケ",
+        Vec::new(),
+        vec![
+            "Summer Pockets".into(),
+            "summer pockets".into(),
+            "  ".into(),
+            "サマポケ".into(),
+            "Sabbat of the Witch".into(),
+            "Kanon".into(),
+        ],
+    );
+
+    let texts: Vec<&str> = queries.iter().map(|q| q.text.as_str()).collect();
+    assert_eq!(
+        texts,
+        vec!["サマポケ", "Summer Pockets", "Sabbat of the Witch", "Kanon"]
+    );
+    assert_eq!(queries[0].weight, 1.0);
+    assert_eq!(queries[1].weight, 1.0);
+    assert!(queries[2].weight < queries[1].weight);
+    assert!(queries[3].weight < queries[2].weight);
+}
+
+#[test]
+fn test_expand_caps_candidates() {
+    let candidates = (0..20).map(|i| format!("name {i}")).collect();
+    let queries = CandidateExpansionService::expand("raw", Vec::new(), candidates);
+    assert_eq!(queries.len(), MAX_CANDIDATES + 1);
+
+    // No candidates: only the raw query
+    assert_eq!(
+        CandidateExpansionService::expand("raw", Vec::new(), Vec::new()).len(),
+        1
+    );
+}
+
+#[test]
+fn test_expand_keeps_aliases_ahead_of_candidates() {
+    let aliases = vec![
+        WeightedQuery::new("Summer Pockets", ALIAS_WEIGHT),
+        WeightedQuery::new("サマポケ", ALIAS_WEIGHT),
+    ];
+    let candidates = (0..20).map(|i| format!("name {i}")).collect();
+    let queries = CandidateExpansionService::expand("サマポケ", aliases, candidates);
+
+    // The alias repeating the raw query is dropped, the other one survives a full AI quota
+    assert_eq!(queries.len(), MAX_CANDIDATES + 2);
+    assert_eq!(queries[1].text, "Summer Pockets");
+    assert_eq!(queries[1].weight, ALIAS_WEIGHT);
+    assert_eq!(queries[2].text, "name 0");
+    assert_eq!(queries[2].weight, 1.0);
+}
+
+#[test]
+fn test_hits_report_matched_candidate() {
+    // The first candidate is wrong, the right name comes second
+    let queries = CandidateExpansionService::expand(
+        "サマポケ",
+        Vec::new(),
+        vec!["Kanon".into(), "summer pockets".into(), "hulotte".into()],
+    );
+    let query = CombinedSearchQuery::new(queries, 10, 0).with_strategy(FusionStrategy::Rrf);
+    let handler = CombinedSearchHandler::new(support::adapter());
+
+    let response = handler.handle(&query, &support::index(support::CATALOG));
+    let summer = response
+        .page
+        .results
+        .iter()
+        .find(|hit| hit.item.id == "0/win/summer pockets.7z")
+        .unwrap();
+    assert_eq!(summer.matched_query.as_deref(), Some("summer pockets"));
+
+    let json = serde_json::to_value(summer).unwrap();
+    assert_eq!(json["matched_query"], "summer pockets");
+}
+
+#[test]
+fn test_raw_query_hits_report_no_matched_query() {
+    let queries =
+        CandidateExpansionService::expand("summer pockets", Vec::new(), vec!["hulotte".into()]);
+    let query = CombinedSearchQuery::new(queries, 10, 0).with_strategy(FusionStrategy::Rrf);
+    let response = CombinedSearchHandler::new(support::adapter())
+        .handle(&query, &support::index(support::CATALOG));
+    let matched = |id: &str| {
+        let hit = response.page.results.iter().find(|hit| hit.item.id == id);
+        hit.unwrap().matched_query.clone()
+    };
+
+    assert_eq!(matched("0/win/summer pockets.7z"), None);
+    assert_eq!(
+        matched("zd/[181026][hulotte] 出会って5分は俺のもの！.rar").as_deref(),
+        Some("hulotte")
+    );
+}
@@ -0,0 +1,188 @@
+use crate::application::search::handlers::search_files_handler::SearchFilesHandler;
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::application::search::services::alias_service::{AliasConfig, AliasService};
+use crate::domain::search::entities::alias_dictionary::{
+    ALIAS_WEIGHT, AliasDictionary, MAX_ALIAS_VARIANTS,
+};
+use crate::domain::search::services::search_index_service::SearchIndexService;
+use crate::infrastructure::persistence::config::alias_config::load_alias_file;
+use crate::tests::support;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+fn groups(entries: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+    entries
+        .iter()
+        .map(|(canonical, aliases)| {
+            let aliases = aliases.iter().map(|alias| (*alias).to_string()).collect();
+            ((*canonical).to_string(), aliases)
+        })
+        .collect()
+}
+
+fn dictionary() -> AliasDictionary {
+    AliasDictionary::new(groups(&[
+        ("魔法使いの夜", &["魔夜", "mahoyo"]),
+        ("fan disc", &["FD"]),
+    ]))
+}
+
+fn texts(query: &str) -> Vec<String> {
+    dictionary()
+        .expand(query)
+        .into_iter()
+        .map(|variant| variant.text)
+        .collect()
+}
+
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("shinnku-{}-{name}", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_expand_swaps_names() {
+    assert_eq!(texts("魔夜"), ["魔法使いの夜", "mahoyo"]);
+    assert_eq!(texts("Mahoyo  HD"), ["魔法使いの夜 hd", "魔夜 hd"]);
+    assert!(
+        dictionary()
+            .expand("魔夜")
+            .iter()
+            .all(|variant| variant.weight == ALIAS_WEIGHT)
+    );
+}
+
+#[test]
+fn test_expand_whole_words_only() {
+    assert_eq!(texts("summer pockets FD"), ["summer pockets fan disc"]);
+    assert!(texts("FDX").is_empty());
+    assert!(texts("mahoyoverse").is_empty());
+
+    // CJK names have no word boundaries to respect
+    assert_eq!(texts("魔夜汉化"), ["魔法使いの夜汉化", "mahoyo汉化"]);
+}
+
+#[test]
+fn test_expand_without_match() {
+    assert!(texts("kanon").is_empty());
+    assert!(texts("").is_empty());
+    assert!(AliasDictionary::default().expand("魔夜").is_empty());
+}
+
+#[test]
+fn test_expand_caps_variants() {
+    let aliases: Vec<String> = (0..20).map(|i| format!("name{i}")).collect();
+    let aliases: Vec<&str> = aliases.iter().map(String::as_str).collect();
+    let dictionary = AliasDictionary::new(groups(&[("canonical", &aliases)]));
+    assert_eq!(dictionary.expand("canonical").len(), MAX_ALIAS_VARIANTS);
+}
+
+#[test]
+fn test_overlay() {
+    let dictionary = dictionary().with_overlay(groups(&[
+        ("魔法使いの夜", &["魔法使之夜"]),
+        ("fan disc", &[]),
+        ("Summer Pockets", &["サマポケ"]),
+    ]));
+
+    assert_eq!(dictionary.len(), 2);
+    assert_eq!(dictionary.groups()["魔法使いの夜"], ["魔法使之夜"]);
+    assert!(dictionary.expand("FD").is_empty());
+    assert_eq!(dictionary.expand("サマポケ")[0].text, "Summer Pockets");
+}
+
+#[tokio::test]
+async fn test_load_alias_file() {
+    let toml = temp_file("aliases.toml", "\"魔法使いの夜\" = [\"魔夜\"]\n");
+    let json = temp_file("aliases.json", r#"{"魔法使いの夜": ["魔夜"]}"#);
+
+    let expected = groups(&[("魔法使いの夜", &["魔夜"])]);
+    assert_eq!(
+        load_alias_file(toml.to_str().unwrap()).await.unwrap(),
+        expected
+    );
+    assert_eq!(
+        load_alias_file(json.to_str().unwrap()).await.unwrap(),
+        expected
+    );
+    assert!(
+        load_alias_file("missing-aliases.toml")
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let broken = temp_file("broken.toml", "not = [toml");
+    assert!(load_alias_file(broken.to_str().unwrap()).await.is_err());
+
+    for path in [toml, json, broken] {
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_reload_swaps_dictionary() {
+    let path = temp_file("reload.toml", "\"魔法使いの夜\" = [\"魔夜\"]\n");
+    let config = AliasConfig {
+        path: path.to_str().unwrap().to_string(),
+        ..AliasConfig::default()
+    };
+    let service = AliasService::load(config, None).await;
+    assert_eq!(service.dictionary().len(), 1);
+
+    std::fs::write(&path, "broken = [").unwrap();
+    assert!(service.reload().await.is_err());
+    assert_eq!(
+        service.dictionary().len(),
+        1,
+        "keeps the previous dictionary"
+    );
+
+    std::fs::write(&path, "\"a\" = [\"b\"]\n\"c\" = [\"d\"]\n").unwrap();
+    assert_eq!(service.reload().await.unwrap().len(), 2);
+    assert_eq!(service.dictionary().len(), 2);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_search_finds_alias() {
+    let files = support::files(&["zd/魔法使いの夜.rar", "zd/kanon.rar"]);
+    let index = SearchIndexService::new().build_index(&[files]);
+    let handler = SearchFilesHandler::new(support::adapter());
+
+    let plain = handler.handle(&SearchFilesQuery::new("mahoyo".into(), None, 0), &index);
+    assert_eq!(plain.page.total, 0);
+
+    let query =
+        SearchFilesQuery::new("mahoyo".into(), None, 0).with_aliases(dictionary().expand("mahoyo"));
+    let expanded = handler.handle(&query, &index);
+    assert_eq!(expanded.page.total, 1);
+    let hit = &expanded.page.results[0];
+    assert_eq!(&*hit.item.info.file_path, "zd/魔法使いの夜.rar");
+    assert_eq!(hit.matched_query.as_deref(), Some("魔法使いの夜"));
+}
+
+#[test]
+fn test_raw_query_hits_report_no_matched_query() {
+    let files = support::files(&["zd/魔法使いの夜.rar", "zd/mahoyo.rar"]);
+    let index = SearchIndexService::new().build_index(&[files]);
+    let query =
+        SearchFilesQuery::new("mahoyo".into(), None, 0).with_aliases(dictionary().expand("mahoyo"));
+    let response = SearchFilesHandler::new(support::adapter()).handle(&query, &index);
+    let matched = |path: &str| {
+        let hit = response
+            .page
+            .results
+            .iter()
+            .find(|hit| &*hit.item.info.file_path == path);
+        hit.unwrap().matched_query.clone()
+    };
+
+    assert_eq!(matched("zd/mahoyo.rar"), None);
+    assert_eq!(
+        matched("zd/魔法使いの夜.rar").as_deref(),
+        Some("魔法使いの夜")
+    );
+}
@@ -1,4 +1,4 @@
-use crate::infrastructure::persistence::redis::connection::connect_redis;
+use crate::infrastructure::persistence::redis::connection::{connect, load_config};
 
 // This test requires a running Redis instance and a valid config.toml.
 // It checks that the REDIS connection manager can be initialized and used.
@@ -9,7 +9,8 @@ async fn test_redis_get_set() {
         tracing::warn!("Skipping redis test: config.toml not found");
         return;
     }
-    let mut con = connect_redis().await.unwrap();
+    let settings = load_config("config.toml").await.unwrap();
+    let mut con = connect(&settings.redis).await.unwrap();
     let key = "img:wiki:zh:5406655";
     let res: String = ::redis::cmd("GET")
         .arg(key)
@@ -0,0 +1,35 @@
+use crate::application::search::handlers::search_files_handler::SearchFilesHandler;
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::domain::search::entities::query_vocabulary::QueryVocabulary;
+use crate::tests::support;
+use std::sync::Arc;
+
+#[test]
+fn test_vocabulary_corrections() {
+    let vocabulary = QueryVocabulary::build(&support::index(support::CATALOG));
+
+    assert_eq!(vocabulary.corrections("hulote", 3)[0], "hulotte");
+    assert_eq!(vocabulary.corrections("witchh", 3)[0], "witch");
+    assert!(vocabulary.corrections("zzzzzzzz", 3).is_empty());
+
+    assert_eq!(
+        vocabulary.suggest_queries("Sumer Pockets", 3),
+        vec!["summer pockets"]
+    );
+    // Nothing to correct
+    assert!(vocabulary.suggest_queries("summer pockets", 3).is_empty());
+}
+
+#[test]
+fn test_did_you_mean_only_on_poor_results() {
+    let index = support::index(support::CATALOG);
+    let handler = SearchFilesHandler::new(support::adapter())
+        .with_vocabulary(Arc::new(QueryVocabulary::build(&index)));
+
+    let response = handler.handle(&SearchFilesQuery::new("hulotte".into(), None, 0), &index);
+    assert!(response.page.total > 0);
+    assert!(response.did_you_mean.is_empty());
+
+    let response = handler.handle(&SearchFilesQuery::new("hulotteqqq".into(), None, 0), &index);
+    assert!(response.did_you_mean.contains(&"hulotte".to_string()));
+}
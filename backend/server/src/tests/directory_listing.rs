@@ -0,0 +1,109 @@
+use crate::application::files::handlers::get_file_tree_handler::GetFileTreeHandler;
+use crate::application::files::queries::get_file_tree_query::GetFileTreeQuery;
+use crate::domain::files::entities::tree_node::{NavigationResult, TreeNode};
+use crate::domain::files::services::directory_listing_service::DirectoryListingService;
+use crate::domain::files::value_objects::listing_order::{ListingSort, SortOrder};
+use crate::interfaces::http::dto::files_dto::Inode;
+use crate::tests::support::files_with;
+use std::cmp::Ordering;
+
+fn tree() -> TreeNode {
+    let files = files_with(&[
+        ("zd/vol10.rar", 300, 3),
+        ("zd/vol2.rar", 100, 2),
+        ("zd/Vol1.rar", 200, 1),
+        ("zd/10001-10500/a.rar", 1, 1),
+        ("zd/1001-1500/b.rar", 1, 1),
+        ("zd/a-side/c.rar", 1, 1),
+    ]);
+    TreeNode::from(files.as_slice())
+}
+
+fn list(query: GetFileTreeQuery) -> (Vec<String>, usize, Option<String>) {
+    let tree = tree();
+    let handler = GetFileTreeHandler::new();
+    let NavigationResult::Folder(folder) = handler.handle(&query, &tree).unwrap() else {
+        panic!("not a folder");
+    };
+    let page = handler.list(&query, folder);
+    let names = page
+        .results
+        .iter()
+        .map(|(name, _)| (*name).clone())
+        .collect();
+    (names, page.total, page.next_cursor)
+}
+
+#[test]
+fn test_natural_cmp() {
+    let cmp = DirectoryListingService::natural_cmp;
+    assert_eq!(cmp("vol2", "vol10"), Ordering::Less);
+    assert_eq!(cmp("1001-1500", "10001-10500"), Ordering::Less);
+    assert_eq!(cmp("Vol1", "vol2"), Ordering::Less);
+    assert_eq!(cmp("part007", "part7"), Ordering::Equal);
+    assert_eq!(cmp("abc", "ab"), Ordering::Greater);
+}
+
+#[test]
+fn test_default_listing_folders_first_natural_order() {
+    let (names, total, next_cursor) = list(GetFileTreeQuery::new("zd".into()));
+    assert_eq!(
+        names,
+        [
+            "1001-1500",
+            "10001-10500",
+            "a-side",
+            "Vol1.rar",
+            "vol2.rar",
+            "vol10.rar"
+        ]
+    );
+    assert_eq!(total, 6);
+    assert_eq!(next_cursor, None);
+
+    let (names, ..) = list(
+        GetFileTreeQuery::new("zd".into()).with_sort(ListingSort::Name, Some(SortOrder::Desc)),
+    );
+    assert_eq!(names[..3], ["a-side", "10001-10500", "1001-1500"]);
+    assert_eq!(names[3], "vol10.rar");
+}
+
+#[test]
+fn test_listing_by_size_and_date() {
+    let (names, ..) = list(GetFileTreeQuery::new("zd".into()).with_sort(ListingSort::Size, None));
+    assert_eq!(names[3..], ["vol10.rar", "Vol1.rar", "vol2.rar"]);
+
+    let (names, ..) =
+        list(GetFileTreeQuery::new("zd".into()).with_sort(ListingSort::Date, Some(SortOrder::Asc)));
+    assert_eq!(names[..3], ["1001-1500", "10001-10500", "a-side"]);
+    assert_eq!(names[3..], ["Vol1.rar", "vol2.rar", "vol10.rar"]);
+}
+
+#[test]
+fn test_listing_pagination() {
+    let (names, total, next_cursor) =
+        list(GetFileTreeQuery::new("zd".into()).with_page(2, Some(2)));
+    assert_eq!(names, ["a-side", "Vol1.rar"]);
+    assert_eq!(total, 6);
+    assert!(next_cursor.is_some());
+
+    let (names, _, next_cursor) = list(GetFileTreeQuery::new("zd".into()).with_page(4, Some(10)));
+    assert_eq!(names, ["vol2.rar", "vol10.rar"]);
+    assert_eq!(next_cursor, None);
+}
+
+#[test]
+fn test_folder_response_has_total() {
+    let tree = tree();
+    let query = GetFileTreeQuery::new("zd".into()).with_page(0, Some(1));
+    let handler = GetFileTreeHandler::new();
+    let NavigationResult::Folder(folder) = handler.handle(&query, &tree).unwrap() else {
+        panic!("not a folder");
+    };
+    let json = serde_json::to_value(Inode::folder(handler.list(&query, folder))).unwrap();
+    assert_eq!(json["type"], "folder");
+    assert_eq!(json["total"], 6);
+    assert_eq!(json["data"][0]["name"], "1001-1500");
+    assert_eq!(json["data"].as_array().unwrap().len(), 1);
+    assert!(json["next_cursor"].is_string());
+}
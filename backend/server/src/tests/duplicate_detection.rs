@@ -0,0 +1,116 @@
+use crate::application::search::handlers::search_files_handler::SearchFilesHandler;
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::domain::search::entities::duplicate_index::DuplicateIndex;
+use crate::domain::search::entities::search_item::SearchList;
+use crate::tests::support::{self, files_with};
+use std::sync::Arc;
+
+const GB: u64 = 1 << 30;
+
+fn index() -> SearchList {
+    let shinnku = files_with(&[
+        ("zd/1001-1500/[180629][Key] Summer Pockets.rar", 4 * GB, 0),
+        ("zd/1001-1500/kanon.rar", GB, 0),
+        ("zd/1501-2000/kanon.rar", GB + 1, 0),
+        ("zd/2001-2500/white album.rar", 2 * GB, 0),
+        ("zd/2001-2500/empty.txt", 0, 0),
+    ]);
+    let galgame0 = files_with(&[
+        (
+            "合集系列/浮士德galgame游戏合集/2018/summer_pockets.rar",
+            4 * GB,
+            0,
+        ),
+        (
+            "合集系列/浮士德galgame游戏合集/2020/Summer Pockets.rar",
+            4 * GB,
+            0,
+        ),
+        (
+            "合集系列/浮士德galgame游戏合集/2020/summer pockets.7z",
+            4 * GB,
+            0,
+        ),
+        (
+            "合集系列/浮士德galgame游戏合集/2010/white album.rar",
+            2 * GB,
+            0,
+        ),
+        ("合集系列/浮士德galgame游戏合集/2010/empty.txt", 0, 0),
+    ]);
+    support::bucket_index(shinnku, galgame0)
+}
+
+#[test]
+fn test_duplicates_by_name_and_size() {
+    let duplicates = DuplicateIndex::build(&index());
+    let groups = duplicates.groups();
+    assert_eq!(groups.len(), 2);
+
+    // Most wasted bytes first
+    assert_eq!(groups[0].file_size, 4 * GB);
+    assert_eq!(
+        groups[0].locations,
+        [
+            "zd/1001-1500/[180629][Key] Summer Pockets.rar",
+            "合集系列/浮士德galgame游戏合集/2018/summer_pockets.rar",
+            "合集系列/浮士德galgame游戏合集/2020/Summer Pockets.rar",
+        ]
+    );
+    assert_eq!(groups[0].name, "[180629][Key] Summer Pockets.rar");
+    assert_eq!(groups[1].locations.len(), 2);
+
+    assert_eq!(duplicates.files(), 5);
+    assert_eq!(duplicates.wasted_bytes(), 8 * GB + 2 * GB);
+}
+
+#[test]
+fn test_different_size_or_extension_not_duplicate() {
+    let duplicates = DuplicateIndex::build(&index());
+    for path in [
+        "zd/1001-1500/kanon.rar",
+        "合集系列/浮士德galgame游戏合集/2020/summer pockets.7z",
+        "zd/2001-2500/empty.txt",
+    ] {
+        assert!(duplicates.group_of(path).is_none(), "{path}");
+    }
+}
+
+#[test]
+fn test_dedupe_search_collapses_copies() {
+    let index = index();
+    let handler = SearchFilesHandler::new(support::adapter())
+        .with_duplicates(Arc::new(DuplicateIndex::build(&index)));
+
+    let query = SearchFilesQuery::new("summer pockets".into(), None, 0);
+    let plain = handler.handle(&query, &index);
+    assert!(plain.page.results.iter().all(|hit| hit.locations.is_none()));
+
+    let deduped = handler.handle(&query.with_dedupe(true), &index);
+    assert!(deduped.page.total < plain.page.total);
+    // The three copies of Summer Pockets became one hit listing all of them
+    let copies: Vec<_> = deduped
+        .page
+        .results
+        .iter()
+        .filter(|hit| {
+            hit.item
+                .info
+                .file_path
+                .to_lowercase()
+                .ends_with("pockets.rar")
+        })
+        .collect();
+    assert_eq!(copies.len(), 1);
+    let locations = copies[0].locations.as_ref().unwrap();
+    assert_eq!(locations.len(), 3);
+    assert!(locations.contains(&copies[0].item.info.file_path.to_string()));
+
+    let json = serde_json::to_value(&deduped.page.results).unwrap();
+    assert!(
+        json.as_array()
+            .unwrap()
+            .iter()
+            .any(|hit| hit["locations"].is_array())
+    );
+}
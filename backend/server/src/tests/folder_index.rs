@@ -0,0 +1,195 @@
+use crate::application::files::services::file_tree_service::FileTreeService;
+use crate::application::search::handlers::search_files_handler::SearchFilesHandler;
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::application::search::queries::search_filters::SearchFilters;
+use crate::application::search::services::facet_service::FacetService;
+use crate::domain::files::entities::file_info::FileInfo;
+use crate::domain::files::entities::tree_node::TreeNode;
+use crate::domain::search::entities::search_item::{SearchItem, SearchList};
+use crate::domain::search::services::search_index_service::SearchIndexService;
+use crate::domain::search::value_objects::item_kind::ItemKind;
+use crate::tests::support::{self, files_with};
+
+fn shinnku() -> Vec<FileInfo> {
+    files_with(&[
+        ("zd/1001-1500/Summer Pockets/sp.part1.rar", 100, 1),
+        ("zd/1001-1500/Summer Pockets/sp.part2.rar", 50, 3),
+        ("zd/1001-1500/Summer Pockets/patch/sp_patch.zip", 5, 2),
+        ("zd/1001-1500/kanon.rar", 10, 1),
+    ])
+}
+
+fn galgame0() -> Vec<FileInfo> {
+    files_with(&[("合集系列/浮士德galgame游戏合集/2020/summer.rar", 7, 1)])
+}
+
+fn index() -> SearchList {
+    support::folder_index(shinnku(), galgame0())
+}
+
+fn folder<'a>(index: &'a SearchList, path: &str) -> Option<&'a SearchItem> {
+    index
+        .iter()
+        .find(|item| item.kind == ItemKind::Folder && &*item.info.file_path == path)
+}
+
+#[test]
+fn test_folder_order_is_stable() {
+    let service = SearchIndexService::new();
+    let tree = TreeNode::from(shinnku().as_slice());
+    let paths = |index: SearchList| -> Vec<String> {
+        index
+            .iter()
+            .map(|item| item.info.file_path.to_string())
+            .collect()
+    };
+
+    let first = paths(service.build_folder_index(&[("", &tree)]));
+    assert_eq!(
+        first,
+        [
+            "zd/1001-1500/Summer Pockets/patch",
+            "zd/1001-1500/Summer Pockets",
+            "zd/1001-1500",
+            "zd",
+        ]
+    );
+    // Rebuilt trees hash differently, the index does not change with them
+    for _ in 0..5 {
+        let tree = TreeNode::from(shinnku().as_slice());
+        assert_eq!(paths(service.build_folder_index(&[("", &tree)])), first);
+    }
+}
+
+#[test]
+fn test_folder_aggregates() {
+    let index = index();
+
+    let game = folder(&index, "zd/1001-1500/Summer Pockets").unwrap();
+    assert_eq!(game.file_count, Some(3));
+    assert_eq!(game.info.file_size, 155);
+    assert_eq!(game.info.upload_timestamp, 3);
+    assert_eq!(game.release.title, "Summer Pockets");
+    assert_eq!(game.release.extension, None);
+
+    let range = folder(&index, "zd/1001-1500").unwrap();
+    assert_eq!(range.file_count, Some(4));
+    assert_eq!(range.info.file_size, 165);
+
+    assert_eq!(folder(&index, "zd").unwrap().file_count, Some(4));
+    assert!(folder(&index, "zd/1001-1500/Summer Pockets/patch").is_some());
+}
+
+#[test]
+fn test_folders_use_bucket_paths() {
+    let index = index();
+
+    // galgame0 folders are mounted below its collection root, which itself is not indexed
+    let year = folder(&index, "合集系列/浮士德galgame游戏合集/2020").unwrap();
+    assert_eq!(year.id, "浮士德galgame游戏合集/2020");
+    assert_eq!(
+        FileTreeService::tree_path(&year.info.file_path),
+        "galgame0/2020"
+    );
+    assert!(folder(&index, "合集系列/浮士德galgame游戏合集").is_none());
+    assert!(folder(&index, "合集系列").is_none());
+
+    let folders = index
+        .iter()
+        .filter(|item| item.kind == ItemKind::Folder)
+        .count();
+    assert_eq!(folders, 5);
+}
+
+#[test]
+fn test_folder_hit_ranks_first() {
+    let handler = SearchFilesHandler::new(support::adapter());
+    let response = handler.handle(
+        &SearchFilesQuery::new("summer pockets".into(), None, 0),
+        &index(),
+    );
+    let best = &response.page.results[0].item;
+    assert_eq!(best.kind, ItemKind::Folder);
+    assert_eq!(&*best.info.file_path, "zd/1001-1500/Summer Pockets");
+
+    // Grouping leaves folders alone even when they share a release key with files
+    let grouped = handler.handle(
+        &SearchFilesQuery::new("summer pockets".into(), None, 0).with_grouping(true),
+        &index(),
+    );
+    assert!(
+        grouped
+            .page
+            .results
+            .iter()
+            .filter(|hit| hit.item.kind == ItemKind::Folder)
+            .all(|hit| hit.group.is_none())
+    );
+}
+
+#[test]
+fn test_facets_skip_folders() {
+    let facets = FacetService::count(&index());
+    assert_eq!(facets.buckets.values().sum::<usize>(), 5);
+    assert_eq!(facets.years.values().sum::<usize>(), 5);
+
+    // A dotted folder name is not an extension
+    let tree = TreeNode::from(files_with(&[("zd/ver.1.0/game.rar", 1, 1)]).as_slice());
+    let folders = SearchIndexService::new().build_folder_index(&[("", &tree)]);
+    assert!(
+        folders
+            .iter()
+            .any(|item| &*item.info.file_path == "zd/ver.1.0")
+    );
+    assert!(FacetService::count(&folders).extensions.is_empty());
+}
+
+#[test]
+fn test_size_and_extension_filters_skip_folders() {
+    let handler = SearchFilesHandler::new(support::adapter());
+    let search = |filters: SearchFilters| {
+        handler
+            .handle(
+                &SearchFilesQuery::new("summer pockets".into(), None, 0).with_filters(filters),
+                &index(),
+            )
+            .page
+            .results
+    };
+
+    // The Summer Pockets folder holds 155 bytes but is no file of that size
+    let results = search(SearchFilters {
+        min_size: Some(50),
+        ..SearchFilters::default()
+    });
+    assert!(!results.is_empty());
+    assert!(results.iter().all(|hit| hit.item.kind == ItemKind::File));
+
+    let tree = TreeNode::from(files_with(&[("zd/summer pockets.rar/setup.exe", 1, 1)]).as_slice());
+    let mut index = SearchIndexService::new().build_folder_index(&[("", &tree)]);
+    index.retain(|item| item.kind == ItemKind::Folder);
+    let filters = SearchFilters {
+        extensions: vec!["rar".into()],
+        ..SearchFilters::default()
+    };
+    assert!(index.iter().all(|item| !filters.matches(item)));
+}
+
+#[test]
+fn test_type_discriminator() {
+    let index = index();
+
+    let folder = serde_json::to_value(folder(&index, "zd/1001-1500").unwrap()).unwrap();
+    assert_eq!(folder["type"], "folder");
+    assert_eq!(folder["file_count"], 4);
+
+    let file = serde_json::to_value(&index[0]).unwrap();
+    assert_eq!(file["type"], "file");
+    assert!(file.get("file_count").is_none());
+
+    // Items cached before folders existed still deserialize as files
+    let mut legacy = file;
+    legacy.as_object_mut().unwrap().remove("type");
+    let legacy: SearchItem = serde_json::from_value(legacy).unwrap();
+    assert_eq!(legacy.kind, ItemKind::File);
+}
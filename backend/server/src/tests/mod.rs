@@ -1,4 +1,31 @@
+mod admin_auth;
+mod ai_candidates;
+mod alias_dictionary;
 mod config;
+mod did_you_mean;
+mod directory_listing;
+mod duplicate_detection;
+mod folder_index;
+mod name_service_client;
+mod popularity;
+mod query_parser;
+mod query_validation;
+mod rank_fusion;
+mod related_files;
+mod release_grouping;
+mod release_info;
+mod relevance_eval;
 mod root_functions;
+mod search_analytics;
+mod search_budget;
+mod search_cache;
+mod search_execution;
+mod search_facets;
 mod search_functions;
 mod search_handlers;
+mod search_pagination;
+mod search_presets;
+mod search_scope;
+mod search_streaming;
+mod suggest_index;
+mod support;
@@ -0,0 +1,126 @@
+use crate::infrastructure::external_services::circuit_breaker::CircuitBreaker;
+use crate::infrastructure::external_services::name_service_client::{
+    NameServiceClient, NameServiceConfig,
+};
+use axum::{Json, Router, extract::State, http::StatusCode, routing::get};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Start a mock `/findname` server, returning its base URL and a call counter
+async fn mock_server(status: StatusCode) -> (String, Arc<AtomicUsize>) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let app = Router::new()
+        .route(
+            "/findname",
+            get(move |State(calls): State<Arc<AtomicUsize>>| async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                let body: Value = json!({"ans": ["Summer Pockets", "Sabbat of the Witch"]});
+                (status, Json(body))
+            }),
+        )
+        .with_state(calls.clone());
+
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+        .await
+        .unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    (format!("http://{addr}"), calls)
+}
+
+fn client(base_url: String, cooldown: Duration) -> NameServiceClient {
+    let config = NameServiceConfig {
+        base_url,
+        timeout: Duration::from_secs(2),
+        failure_threshold: 2,
+        cooldown,
+        ..NameServiceConfig::default()
+    };
+    NameServiceClient::new(config, None).unwrap()
+}
+
+#[test]
+fn test_cache_key_normalization() {
+    assert_eq!(
+        NameServiceClient::cache_key("  Summer   Pockets "),
+        NameServiceClient::cache_key("summer pockets")
+    );
+    assert_eq!(
+        NameServiceClient::cache_key("サマポケ"),
+        "cache:findname:サマポケ"
+    );
+}
+
+#[test]
+fn test_circuit_breaker_trips_and_recovers() {
+    let breaker = CircuitBreaker::new(2, Duration::from_millis(20));
+    assert!(breaker.allow());
+    assert!(!breaker.record_failure());
+    assert!(breaker.record_failure());
+    assert!(breaker.is_open());
+    assert!(!breaker.allow());
+
+    std::thread::sleep(Duration::from_millis(30));
+    // One trial call after the cool-down, the rest wait for its outcome
+    assert!(breaker.allow());
+    assert!(!breaker.allow());
+    // A failed trial opens the circuit again
+    assert!(breaker.record_failure());
+
+    std::thread::sleep(Duration::from_millis(30));
+    assert!(breaker.allow());
+    breaker.record_success();
+    assert!(!breaker.is_open());
+    assert!(breaker.allow());
+}
+
+#[tokio::test]
+async fn test_find_names_without_cache() {
+    let (base_url, calls) = mock_server(StatusCode::OK).await;
+    let client = client(base_url, Duration::from_secs(30));
+
+    let names = client.find_names("Summer Pockets").await.unwrap();
+    assert_eq!(names, vec!["Summer Pockets", "Sabbat of the Witch"]);
+    let names = client.find_names("  summer pockets").await.unwrap();
+    assert_eq!(names.len(), 2);
+
+    // Without Redis every lookup asks the service
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    let metrics = client.metrics();
+    assert_eq!(metrics.cache_hits, 0);
+    assert_eq!(metrics.cache_misses, 2);
+    assert_eq!(metrics.failures, 0);
+}
+
+#[tokio::test]
+async fn test_find_names_breaker_skips_calls() {
+    let (base_url, calls) = mock_server(StatusCode::INTERNAL_SERVER_ERROR).await;
+    let client = client(base_url, Duration::from_millis(100));
+
+    for query in ["a", "b", "c", "d"] {
+        assert!(client.find_names(query).await.is_none());
+    }
+    // Two failures opened the circuit, the other lookups never left the process
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    let metrics = client.metrics();
+    assert_eq!(metrics.failures, 2);
+    assert_eq!(metrics.breaker_trips, 1);
+    assert_eq!(metrics.breaker_skips, 2);
+    assert!(metrics.breaker_open);
+
+    // After the cool-down a trial call goes out again
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(client.find_names("e").await.is_none());
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert_eq!(client.metrics().breaker_trips, 2);
+}
+
+#[tokio::test]
+async fn test_find_names_unreachable_service() {
+    // Nothing listens on the discard port
+    let client = client("http://127.0.0.1:9".to_string(), Duration::from_secs(30));
+    assert!(client.find_names("kanon").await.is_none());
+    assert_eq!(client.metrics().failures, 1);
+}
@@ -0,0 +1,226 @@
+use crate::application::search::handlers::search_files_handler::SearchFilesHandler;
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::application::search::services::popularity_tracker::{
+    PopularityConfig, PopularityTracker,
+};
+use crate::domain::search::entities::alias_dictionary::ALIAS_WEIGHT;
+use crate::domain::search::entities::popularity_snapshot::PopularitySnapshot;
+use crate::domain::search::entities::search_result::SearchResult;
+use crate::domain::search::services::popularity_service::{
+    DECAY_EPOCH_MS, ERA_HALF_LIVES, PopularityService,
+};
+use crate::domain::search::value_objects::score::Score;
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
+use crate::infrastructure::adapters::search::fuse_search_adapter::FuseSearchAdapter;
+use crate::interfaces::http::controllers::search_controller::client_address;
+use crate::tests::support::{self, index};
+use axum::http::HeaderMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+const NOW_MS: u64 = 1_767_225_600_000;
+
+fn results(scored: &[(&str, f64)]) -> Vec<SearchResult> {
+    let paths: Vec<&str> = scored.iter().map(|(path, _)| *path).collect();
+    index(&paths)
+        .into_iter()
+        .zip(scored)
+        .map(|(item, (_, score))| SearchResult::new(item, Score::new(*score)))
+        .collect()
+}
+
+fn snapshot(scores: &[(&str, f64)]) -> PopularitySnapshot {
+    let scores: HashMap<String, f64> = scores
+        .iter()
+        .map(|(path, score)| ((*path).to_string(), *score))
+        .collect();
+    PopularitySnapshot::new(1, scores)
+}
+
+fn paths(results: &[SearchResult]) -> Vec<&str> {
+    results
+        .iter()
+        .map(|result| &*result.item.info.file_path)
+        .collect()
+}
+
+#[test]
+fn test_click_decay() {
+    let service = PopularityService::new(Duration::from_millis(7 * DAY_MS));
+
+    let click = service.click_increment(NOW_MS);
+    assert!((service.decayed(click, NOW_MS) - 1.0).abs() < 1e-9);
+    assert!((service.decayed(click, NOW_MS + 7 * DAY_MS) - 0.5).abs() < 1e-9);
+    assert!((service.decayed(click, NOW_MS + 14 * DAY_MS) - 0.25).abs() < 1e-9);
+
+    // A fresh click outweighs one from a half-life ago
+    let old = service.click_increment(NOW_MS - 7 * DAY_MS);
+    assert!((service.click_increment(NOW_MS) / old - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_click_decay_across_eras() {
+    let half_life = 7 * DAY_MS;
+    let service = PopularityService::new(Duration::from_millis(half_life));
+    let era_ms = half_life * u64::from(ERA_HALF_LIVES);
+
+    // Increments stay bounded however far from the first era
+    for years in [0, 20, 100, 1000] {
+        let increment = service.click_increment(NOW_MS + years * 365 * DAY_MS);
+        assert!((1.0..f64::from(ERA_HALF_LIVES).exp2()).contains(&increment));
+    }
+
+    // A click a half-life before an era ends, carried over into the next era
+    let era = service.era(NOW_MS);
+    let click_at = DECAY_EPOCH_MS + (era + 1) * era_ms - half_life;
+    assert_eq!(service.era(click_at), era);
+    let carried = service.click_increment(click_at) * PopularityService::rebase_factor();
+    let read_at = click_at + 2 * half_life;
+    assert_eq!(service.era(read_at), era + 1);
+    assert!((service.decayed(carried, read_at) - 0.25).abs() < 1e-9);
+}
+
+#[test]
+fn test_rerank_prefers_popular_among_equals() {
+    let mut scored = results(&[("a.rar", 0.10), ("b.rar", 0.11), ("c.rar", 0.12)]);
+    let popularity = snapshot(&[("c.rar", 500.0), ("b.rar", 5.0)]);
+
+    PopularityService::rerank(&mut scored, &popularity, 0.15, 100);
+    assert_eq!(paths(&scored), ["c.rar", "b.rar", "a.rar"]);
+}
+
+#[test]
+fn test_rerank_keeps_relevance_gaps() {
+    // A popular file that barely matches does not overtake a near-perfect match
+    let mut scored = results(&[("exact.rar", 0.0), ("vague.rar", 0.5)]);
+    PopularityService::rerank(&mut scored, &snapshot(&[("vague.rar", 1e6)]), 0.15, 100);
+    assert_eq!(paths(&scored), ["exact.rar", "vague.rar"]);
+}
+
+#[test]
+fn test_rerank_window() {
+    let mut scored = results(&[("a.rar", 0.1), ("b.rar", 0.1), ("c.rar", 0.1)]);
+    PopularityService::rerank(&mut scored, &snapshot(&[("c.rar", 100.0)]), 0.15, 2);
+    assert_eq!(paths(&scored), ["a.rar", "b.rar", "c.rar"]);
+
+    PopularityService::rerank(&mut scored, &PopularitySnapshot::default(), 0.15, 3);
+    assert_eq!(paths(&scored), ["a.rar", "b.rar", "c.rar"]);
+}
+
+#[test]
+fn test_search_with_popularity() {
+    let index = index(&[
+        "zd/hulotte/game one.rar",
+        "zd/hulotte/game two.rar",
+        "zd/hulotte/game three.rar",
+    ]);
+    let popularity = Arc::new(snapshot(&[("zd/hulotte/game three.rar", 1000.0)]));
+    let handler =
+        SearchFilesHandler::new(support::adapter()).with_popularity(popularity, 0.15, 100);
+    let first = |query: &SearchFilesQuery| {
+        handler.handle(query, &index).page.results[0]
+            .item
+            .info
+            .file_path
+            .to_string()
+    };
+
+    let query = SearchFilesQuery::new("hulotte".into(), None, 0);
+    assert_eq!(first(&query), "zd/hulotte/game one.rar");
+    assert_eq!(
+        first(&query.with_popularity(1)),
+        "zd/hulotte/game three.rar"
+    );
+}
+
+#[test]
+fn test_search_with_aliases_and_popularity() {
+    let index = index(&[
+        "zd/hulotte/game one.rar",
+        "zd/hulotte/game two.rar",
+        "zd/hulotte/game three.rar",
+    ]);
+    let popularity = Arc::new(snapshot(&[("zd/hulotte/game three.rar", 1000.0)]));
+    let handler =
+        SearchFilesHandler::new(support::adapter()).with_popularity(popularity, 0.15, 100);
+    let first = |query: &SearchFilesQuery| {
+        handler.handle(query, &index).page.results[0]
+            .item
+            .info
+            .file_path
+            .to_string()
+    };
+
+    let query = SearchFilesQuery::new("hulotte".into(), None, 0)
+        .with_aliases(vec![WeightedQuery::new("ハロット", ALIAS_WEIGHT)]);
+    assert_eq!(first(&query), "zd/hulotte/game one.rar");
+    assert_eq!(
+        first(&query.with_popularity(1)),
+        "zd/hulotte/game three.rar"
+    );
+}
+
+#[test]
+fn test_search_with_query_popularity() {
+    let index = index(&[
+        "zd/hulotte/game one.rar",
+        "zd/hulotte/game two.rar",
+        "zd/hulotte/game three.rar",
+    ]);
+    let popularity = Arc::new(snapshot(&[
+        ("zd/hulotte/game three.rar", 100.0),
+        ("zd/hulotte/game two.rar", 60.0),
+    ]));
+    // Fewer clicks overall, but most of those made from this query
+    let clicks = Arc::new(snapshot(&[("zd/hulotte/game two.rar", 50.0)]));
+
+    let blended = popularity.blend(&clicks, 1.0, ["zd/hulotte/game two.rar"]);
+    assert_eq!(blended.score("zd/hulotte/game two.rar"), 110.0);
+    assert_eq!(blended.len(), 1);
+
+    let query = SearchFilesQuery::new("hulotte".into(), None, 0).with_popularity(1);
+    let first = |handler: SearchFilesHandler<FuseSearchAdapter>| {
+        handler.handle(&query, &index).page.results[0]
+            .item
+            .info
+            .file_path
+            .to_string()
+    };
+    let handler =
+        SearchFilesHandler::new(support::adapter()).with_popularity(popularity.clone(), 0.15, 100);
+    assert_eq!(first(handler), "zd/hulotte/game three.rar");
+    let handler = SearchFilesHandler::new(support::adapter())
+        .with_popularity(popularity, 0.15, 100)
+        .with_query_popularity(clicks, 1.0);
+    assert_eq!(first(handler), "zd/hulotte/game two.rar");
+}
+
+#[test]
+fn test_click_client_address() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(client_address(&headers), None);
+
+    headers.insert("x-forwarded-for", "10.0.0.1, 203.0.113.7".parse().unwrap());
+    assert_eq!(client_address(&headers), Some([203, 0, 113, 7].into()));
+    headers.append("x-forwarded-for", "2001:db8::1".parse().unwrap());
+    assert_eq!(client_address(&headers), "2001:db8::1".parse().ok());
+
+    headers.insert("x-forwarded-for", "not an address".parse().unwrap());
+    assert_eq!(client_address(&headers), None);
+}
+
+#[tokio::test]
+async fn test_tracker_without_redis() {
+    let tracker = PopularityTracker::new(PopularityConfig::default(), None);
+    tracker
+        .record_click("zd/a.rar", Some("a"), Some("203.0.113.7"))
+        .await
+        .unwrap();
+    assert!(tracker.query_snapshot("a").await.is_none());
+
+    let snapshot = tracker.refresh().await.unwrap();
+    assert_eq!(snapshot.generation, 0);
+    assert_eq!(snapshot.score("zd/a.rar"), 0.0);
+}
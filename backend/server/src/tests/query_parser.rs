@@ -0,0 +1,85 @@
+use crate::application::search::handlers::search_files_handler::SearchFilesHandler;
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::application::search::services::query_parser::QueryParser;
+use crate::domain::search::services::search_index_service::SearchIndexService;
+use crate::error::AppError;
+use crate::tests::support::{self, file_with};
+
+const GIB: u64 = 1024 * 1024 * 1024;
+
+#[test]
+fn test_parse_predicates_and_text() {
+    let parsed =
+        QueryParser::parse("bucket:galgame0 ext:rar size>1GB after:2023-01 hulotte").unwrap();
+
+    assert_eq!(parsed.text, "hulotte");
+    assert_eq!(parsed.filters.buckets, vec!["galgame0"]);
+    assert_eq!(parsed.filters.extensions, vec!["rar"]);
+    assert_eq!(parsed.filters.min_size, Some(GIB + 1));
+    // 2023-01-01T00:00:00Z
+    assert_eq!(parsed.filters.uploaded_after, Some(1_672_531_200_000));
+}
+
+#[test]
+fn test_parse_keeps_unknown_fields_as_text() {
+    let parsed = QueryParser::parse("Re:Zero  ext:.7Z,zip").unwrap();
+    assert_eq!(parsed.text, "Re:Zero");
+    assert_eq!(parsed.filters.extensions, vec!["7z", "zip"]);
+}
+
+#[test]
+fn test_parse_errors_report_position() {
+    let err = QueryParser::parse("hulotte size>1XB").unwrap_err();
+    assert_eq!(err.position, 13);
+
+    let err = QueryParser::parse("出会って bucket:shinnku,foo").unwrap_err();
+    assert_eq!(err.position, 20);
+
+    let err = QueryParser::parse("after:2023-13").unwrap_err();
+    assert_eq!(err.position, 6);
+
+    let err = QueryParser::parse("a ext>rar").unwrap_err();
+    assert_eq!(err.position, 2);
+
+    assert!(matches!(
+        AppError::from(QueryParser::parse("size:1GB").unwrap_err()),
+        AppError::InvalidInput { code: "invalid_query_syntax", message }
+            if message.ends_with("at position 0")
+    ));
+}
+
+#[test]
+fn test_parse_positions_point_into_raw_input() {
+    // NFKC turns the full-width title and the ideographic space into fewer
+    // characters, the reported position still counts the input as sent
+    let raw = "Ｓｕｍｍｅｒ\u{3000}\u{3000}Ｐｏｃｋｅｔｓ  size>1XB";
+    let err = QueryParser::parse(raw).unwrap_err();
+    assert_eq!(err.position, 22);
+    assert_eq!(raw.chars().skip(err.position).collect::<String>(), "1XB");
+
+    let parsed = QueryParser::parse("Ｓｕｍｍｅｒ\u{3000}Ｐｏｃｋｅｔｓ ext:rar").unwrap();
+    assert_eq!(parsed.text, "Summer Pockets");
+    assert_eq!(parsed.filters.extensions, vec!["rar"]);
+}
+
+#[test]
+fn test_filters_applied_to_search() {
+    let files = vec![
+        file_with("zd/hulotte small.rar", 10, 0),
+        file_with("zd/hulotte big.rar", 2 * GIB, 0),
+        file_with("zd/hulotte big.7z", 2 * GIB, 0),
+    ];
+    let index = SearchIndexService::new().build_index(&[files]);
+    let handler = SearchFilesHandler::new(support::adapter());
+
+    let parsed = QueryParser::parse("ext:rar size>1GB hulotte").unwrap();
+    let query = SearchFilesQuery::new(parsed.text, None, 0).with_filters(parsed.filters);
+    let response = handler.handle(&query, &index);
+    assert_eq!(response.page.total, 1);
+    assert_eq!(response.page.results[0].item.id, "zd/hulotte big.rar");
+
+    // Predicates alone list every matching file
+    let parsed = QueryParser::parse("size>1GB").unwrap();
+    let query = SearchFilesQuery::new(parsed.text, None, 0).with_filters(parsed.filters);
+    assert_eq!(handler.handle(&query, &index).page.total, 2);
+}
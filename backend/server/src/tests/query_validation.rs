@@ -0,0 +1,72 @@
+use crate::application::search::queries::validated_query::{
+    DEFAULT_LIMIT, MAX_LIMIT, MAX_QUERY_CHARS, QueryViolation, ValidatedQuery,
+};
+use crate::error::AppError;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+
+#[test]
+fn test_normalize_applies_nfkc_and_collapses_whitespace() {
+    assert_eq!(
+        ValidatedQuery::normalize("  Ｓｕｍｍｅｒ\tＰｏｃｋｅｔｓ \n"),
+        "Summer Pockets"
+    );
+    assert_eq!(ValidatedQuery::normalize("ｻﾉﾊﾞｳｨｯﾁ"), "サノバウィッチ");
+    assert_eq!(
+        ValidatedQuery::normalize("ka\u{0}non\u{7f} rar"),
+        "kanon rar"
+    );
+}
+
+#[test]
+fn test_blank_query_rejected() {
+    assert_eq!(ValidatedQuery::new("", None), Err(QueryViolation::Empty));
+    assert_eq!(
+        ValidatedQuery::new(" \t\u{3000}\u{1b} ", None),
+        Err(QueryViolation::Empty)
+    );
+}
+
+#[test]
+fn test_length_and_limit_bounds() {
+    let longest = "あ".repeat(MAX_QUERY_CHARS);
+    let query = ValidatedQuery::new(&longest, Some(MAX_LIMIT)).unwrap();
+    assert_eq!(query.text(), longest);
+    assert_eq!(query.limit(), MAX_LIMIT);
+
+    assert_eq!(
+        ValidatedQuery::new(&format!("{longest}あ"), None),
+        Err(QueryViolation::TooLong)
+    );
+    assert_eq!(
+        ValidatedQuery::new("kanon", Some(100_000_000)),
+        Err(QueryViolation::LimitTooLarge("n"))
+    );
+    assert_eq!(ValidatedQuery::check_limit(None, "limit"), Ok(None));
+    assert_eq!(
+        ValidatedQuery::check_limit(Some(MAX_LIMIT + 1), "limit"),
+        Err(QueryViolation::LimitTooLarge("limit"))
+    );
+}
+
+#[test]
+fn test_omitted_limit_defaults() {
+    let query = ValidatedQuery::new("kanon", None).unwrap();
+    assert_eq!(query.limit(), DEFAULT_LIMIT);
+}
+
+#[tokio::test]
+async fn test_violation_response_carries_code() {
+    let response = AppError::from(QueryViolation::LimitTooLarge("limit")).into_response();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["code"], "limit_too_large");
+    assert_eq!(
+        body["message"],
+        format!("`limit` must be at most {MAX_LIMIT}")
+    );
+}
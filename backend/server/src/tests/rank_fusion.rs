@@ -0,0 +1,160 @@
+use crate::application::search::handlers::combined_search_handler::CombinedSearchHandler;
+use crate::application::search::queries::combined_search_query::{
+    CombinedSearchQuery, MAX_COMBINED_QUERIES,
+};
+use crate::domain::search::repositories::fuzzy_search_repository::FuzzySearchRepository;
+use crate::domain::search::services::rank_fusion_service::RankFusionService;
+use crate::domain::search::value_objects::fusion_strategy::FusionStrategy;
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
+use crate::tests::support;
+
+fn indices(fused: Vec<(usize, usize, f64)>) -> Vec<usize> {
+    fused.into_iter().map(|(idx, ..)| idx).collect()
+}
+
+#[test]
+fn test_fuse_average() {
+    let service = RankFusionService::new();
+    let lists = vec![
+        (1.0, vec![(0, 0.1), (1, 0.5)]),
+        (3.0, vec![(1, 0.1), (2, 0.2)]),
+    ];
+    // 0: 0.1, 1: (0.5 + 0.3) / 4 = 0.2, 2: 0.2; ties keep index order
+    assert_eq!(
+        indices(service.fuse(&lists, FusionStrategy::Average)),
+        vec![0, 1, 2]
+    );
+}
+
+#[test]
+fn test_fuse_min() {
+    let service = RankFusionService::new();
+    let lists = vec![
+        (1.0, vec![(0, 0.1), (1, 0.5)]),
+        (4.0, vec![(2, 0.2), (1, 0.3)]),
+    ];
+    // 0: 0.1, 1: min(0.5, 0.075), 2: 0.05
+    assert_eq!(
+        indices(service.fuse(&lists, FusionStrategy::Min)),
+        vec![2, 1, 0]
+    );
+}
+
+#[test]
+fn test_fuse_rrf_ignores_raw_scores() {
+    let service = RankFusionService::new();
+    // Item 1 is second in both lists, items 0 and 2 first in one each
+    let lists = vec![
+        (1.0, vec![(0, 0.0), (1, 0.9)]),
+        (1.0, vec![(2, 0.8), (1, 0.85)]),
+    ];
+    assert_eq!(
+        indices(service.fuse(&lists, FusionStrategy::Rrf)),
+        vec![1, 0, 2]
+    );
+
+    // A heavy variant dominates
+    let lists = vec![(1.0, vec![(0, 0.0)]), (5.0, vec![(2, 0.9)])];
+    assert_eq!(
+        indices(service.fuse(&lists, FusionStrategy::Rrf)),
+        vec![2, 0]
+    );
+}
+
+#[test]
+fn test_fused_search_many_queries() {
+    let index = support::index(&["foo.txt", "bar.txt", "baz.txt"]);
+    let adapter = support::adapter();
+    let queries = [
+        WeightedQuery::unweighted("foo"),
+        WeightedQuery::unweighted("bar"),
+        WeightedQuery::new("baz", 2.0),
+    ];
+
+    for strategy in [
+        FusionStrategy::Average,
+        FusionStrategy::Min,
+        FusionStrategy::Rrf,
+    ] {
+        let results = adapter.fused_search(&queries, strategy, 10, &index);
+        assert_eq!(results.len(), 3, "{strategy:?}");
+    }
+
+    let results = adapter.fused_search(&queries, FusionStrategy::Rrf, 1, &index);
+    assert_eq!(results[0].item.id, "baz.txt");
+    assert_eq!(results[0].query, 2);
+}
+
+#[test]
+fn test_combined_query_validation() {
+    let query = |queries| CombinedSearchQuery::new(queries, 10, 0);
+
+    assert!(
+        query(vec![WeightedQuery::unweighted("foo")])
+            .validate()
+            .is_ok()
+    );
+    assert!(query(Vec::new()).validate().is_err());
+    assert!(
+        query(vec![WeightedQuery::new("foo", 0.0)])
+            .validate()
+            .is_err()
+    );
+    assert!(
+        query(vec![WeightedQuery::new("foo", f64::NAN)])
+            .validate()
+            .is_err()
+    );
+    assert!(
+        query(vec![
+            WeightedQuery::unweighted("foo");
+            MAX_COMBINED_QUERIES + 1
+        ])
+        .validate()
+        .is_err()
+    );
+}
+
+#[test]
+fn test_combined_handler_uses_strategy() {
+    let index = support::index(&["foo.txt", "bar.txt", "baz.txt"]);
+    let handler = CombinedSearchHandler::new(support::adapter());
+    let query = CombinedSearchQuery::new(
+        vec![
+            WeightedQuery::unweighted("foo"),
+            WeightedQuery::new("bar", 10.0),
+        ],
+        10,
+        0,
+    )
+    .with_strategy(FusionStrategy::Rrf);
+
+    let response = handler.handle(&query, &index);
+    assert_eq!(response.page.results[0].item.id, "bar.txt");
+}
+
+#[test]
+fn test_strategy_deserialization() {
+    let strategy: FusionStrategy = serde_json::from_str("\"rrf\"").unwrap();
+    assert_eq!(strategy, FusionStrategy::Rrf);
+    assert!(serde_json::from_str::<FusionStrategy>("\"median\"").is_err());
+}
+
+#[test]
+fn test_fuse_reports_best_variant() {
+    let service = RankFusionService::new();
+    let lists = vec![(1.0, vec![(0, 0.1), (1, 0.5)]), (1.0, vec![(1, 0.05)])];
+    for strategy in [
+        FusionStrategy::Average,
+        FusionStrategy::Min,
+        FusionStrategy::Rrf,
+    ] {
+        let fused: Vec<(usize, usize)> = service
+            .fuse(&lists, strategy)
+            .into_iter()
+            .map(|(idx, variant, _)| (idx, variant))
+            .collect();
+        assert!(fused.contains(&(0, 0)), "{strategy:?}");
+        assert!(fused.contains(&(1, 1)), "{strategy:?}");
+    }
+}
@@ -0,0 +1,126 @@
+use crate::application::search::handlers::get_related_handler::GetRelatedHandler;
+use crate::application::search::queries::get_related_query::GetRelatedQuery;
+use crate::application::search::services::search_cache_service::{
+    SearchCacheConfig, SearchCacheService,
+};
+use crate::domain::search::entities::search_item::SearchList;
+use crate::domain::search::value_objects::cancellation::Cancellation;
+use crate::error::AppError;
+use crate::tests::support;
+use std::sync::Arc;
+use std::time::Duration;
+
+const FILES: &[&str] = &[
+    "zd/1001-1500/[180629][Key] Summer Pockets.rar",
+    "zd/1001-1500/kanon.rar",
+    "zd/1501-2000/[200625][Key] Summer Pockets Reflection Blue.rar",
+    "zd/1501-2000/[041126][Key] CLANNAD.rar",
+    "zd/2001-2500/[201218][Navel] 月に寄りそう乙女の作法.rar",
+    "zd/2001-2500/月に寄りそう乙女の作法2.rar",
+    "zd/2001-2500/white album.rar",
+];
+
+fn index() -> SearchList {
+    support::folder_index(support::files(FILES), Vec::new())
+}
+
+fn related(path: &str, limit: usize) -> Vec<String> {
+    GetRelatedHandler::new()
+        .handle(&GetRelatedQuery::new(path.into(), limit), &index())
+        .unwrap()
+        .hits
+        .into_iter()
+        .map(|hit| hit.item.info.file_path.to_string())
+        .collect()
+}
+
+#[test]
+fn test_related_ranks_title_brand_and_folder() {
+    let results = related(FILES[0], 10);
+    assert_eq!(
+        results,
+        [
+            "zd/1501-2000/[200625][Key] Summer Pockets Reflection Blue.rar",
+            "zd/1501-2000/[041126][Key] CLANNAD.rar",
+            "zd/1001-1500/kanon.rar",
+        ]
+    );
+}
+
+#[test]
+fn test_related_matches_cjk_titles() {
+    let hits = GetRelatedHandler::new()
+        .handle(&GetRelatedQuery::new(FILES[4].into(), 10), &index())
+        .unwrap()
+        .hits;
+    let sequel = &hits[0];
+    assert_eq!(&*sequel.item.info.file_path, FILES[5]);
+    assert!(sequel.similarity.title_overlap > 0.8);
+    assert!(!sequel.similarity.same_brand);
+    assert!(sequel.similarity.same_folder);
+    assert!(
+        hits.windows(2)
+            .all(|w| w[0].similarity.score >= w[1].similarity.score)
+    );
+}
+
+#[test]
+fn test_related_excludes_target_and_folders() {
+    let results = related(FILES[6], 10);
+    assert!(!results.contains(&FILES[6].to_string()));
+    assert!(results.iter().all(|path| path.ends_with(".rar")));
+    assert_eq!(related(FILES[0], 1).len(), 1);
+}
+
+#[test]
+fn test_related_unknown_path_not_found() {
+    for path in ["zd/nope.rar", "zd/1001-1500"] {
+        assert!(matches!(
+            GetRelatedHandler::new().handle(&GetRelatedQuery::new(path.into(), 10), &index()),
+            Err(AppError::NotFound(_))
+        ));
+    }
+}
+
+#[test]
+fn test_related_stops_within_budget() {
+    let query = GetRelatedQuery::new(FILES[0].into(), 10);
+    let complete = GetRelatedHandler::new()
+        .with_time_budget(Duration::from_secs(60))
+        .handle(&query, &index())
+        .unwrap();
+    assert!(!complete.partial);
+    assert_eq!(complete.hits.len(), 3);
+
+    let expired = GetRelatedHandler::new()
+        .with_time_budget(Duration::ZERO)
+        .handle(&query, &index())
+        .unwrap();
+    assert!(expired.partial);
+    assert!(expired.hits.is_empty());
+
+    let cancellation = Cancellation::new();
+    cancellation.cancel();
+    let cancelled = GetRelatedHandler::new()
+        .with_cancellation(cancellation)
+        .handle(&query, &index())
+        .unwrap();
+    assert!(cancelled.partial);
+}
+
+#[test]
+fn test_related_cache_keyed_by_index_version() {
+    let cache = SearchCacheService::new("v1", SearchCacheConfig::default(), None);
+    let hits = GetRelatedHandler::new()
+        .handle(&GetRelatedQuery::new(FILES[0].into(), 10), &index())
+        .unwrap()
+        .hits;
+    cache.put_related(cache.related_key(FILES[0]), Arc::new(hits.clone()));
+
+    let cached = cache.get_related(&cache.related_key(FILES[0])).unwrap();
+    assert_eq!(*cached, hits);
+    assert!(cache.get_related(&cache.related_key(FILES[1])).is_none());
+
+    let reloaded = SearchCacheService::new("v2", SearchCacheConfig::default(), None);
+    assert_ne!(reloaded.related_key(FILES[0]), cache.related_key(FILES[0]));
+}
@@ -0,0 +1,154 @@
+use crate::application::search::dto::search_hit::SearchHit;
+use crate::application::search::handlers::search_files_handler::SearchFilesHandler;
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::application::search::services::result_grouping_service::ResultGroupingService;
+use crate::domain::search::entities::search_item::SearchList;
+use crate::domain::search::value_objects::release_key::ReleaseKey;
+use crate::tests::support;
+
+fn index() -> SearchList {
+    support::index_with(&[
+        ("zd/hulotte.part1.rar", 100, 10),
+        ("zd/hulotte.part2.rar", 100, 30),
+        ("zd/hulotte.part3.rar", 50, 20),
+        ("zd/hulotte_v1.02.rar", 10, 40),
+        ("zd/kanon.iso", 1, 5),
+    ])
+}
+
+#[test]
+fn test_release_key_strips_volumes() {
+    let key = ReleaseKey::new("zd/Title.rar");
+    for path in [
+        "zd/Title.part1.rar",
+        "zd/title.part02.rar",
+        "zd/Title.7z.001",
+        "zd/Title.zip.002",
+        "zd/Title.z01",
+        "zd/Title.r00",
+        "zd/Title.001",
+    ] {
+        assert_eq!(ReleaseKey::new(path), key, "{path}");
+    }
+}
+
+#[test]
+fn test_release_key_strips_versions_and_patches() {
+    let key = ReleaseKey::new("zd/Title.rar");
+    for path in [
+        "zd/Title_v1.02.rar",
+        "zd/Title ver2.zip",
+        "zd/Title ver.1.1.7z",
+        "zd/Title patch.exe",
+        "zd/Title_v1.1 patch.rar",
+        "zd/Title 补丁.zip",
+    ] {
+        assert_eq!(ReleaseKey::new(path), key, "{path}");
+    }
+}
+
+#[test]
+fn test_release_key_strips_date_tags() {
+    let key = ReleaseKey::new("zd/[180629][Key] Summer Pockets.rar");
+    assert_eq!(key, ReleaseKey::new("zd/[Key] Summer Pockets.rar"));
+    for path in [
+        "zd/[190925][Key] Summer Pockets_v1.02.part1.rar",
+        "zd/[20190925] [Key] Summer Pockets.7z",
+        "zd/[Key] Summer Pockets [180629].rar",
+        "zd/[Key] Summer Pockets (190925) patch.zip",
+    ] {
+        assert_eq!(ReleaseKey::new(path), key, "{path}");
+    }
+
+    // Numbers that are no date, or a date that is the whole name, stay
+    assert_ne!(
+        ReleaseKey::new("zd/[123456] Title.rar"),
+        ReleaseKey::new("zd/Title.rar")
+    );
+    assert_ne!(
+        ReleaseKey::new("zd/[180629].rar"),
+        ReleaseKey::new("zd/[190925].rar")
+    );
+}
+
+#[test]
+fn test_two_dated_versions_group_together() {
+    let index = support::index_with(&[
+        ("zd/[180629][Key] Summer Pockets.rar", 100, 10),
+        ("zd/[190925][Key] Summer Pockets.rar", 100, 20),
+        (
+            "zd/[200625][Key] Summer Pockets Reflection Blue.rar",
+            100,
+            30,
+        ),
+    ]);
+    let hits = ResultGroupingService::group(index.into_iter().map(SearchHit::from).collect());
+    assert_eq!(hits.len(), 2);
+
+    let group = hits[0].group.as_ref().unwrap();
+    assert_eq!(group.members.len(), 2);
+    assert_eq!(group.newest_timestamp, 20);
+    assert!(hits[1].group.is_none());
+}
+
+#[test]
+fn test_release_key_keeps_distinct_titles() {
+    assert_ne!(
+        ReleaseKey::new("zd/Title.rar"),
+        ReleaseKey::new("zd/Title 2.rar")
+    );
+    assert_ne!(
+        ReleaseKey::new("zd/Title.rar"),
+        ReleaseKey::new("0/Title.rar")
+    );
+    assert_ne!(
+        ReleaseKey::new("zd/Dispatch.rar"),
+        ReleaseKey::new("zd/Dis.rar")
+    );
+    assert_ne!(
+        ReleaseKey::new("zd/Wave5.rar"),
+        ReleaseKey::new("zd/Wa.rar")
+    );
+}
+
+#[test]
+fn test_group_folds_members() {
+    let hits = ResultGroupingService::group(index().into_iter().map(SearchHit::from).collect());
+    assert_eq!(hits.len(), 2);
+
+    let group = hits[0].group.as_ref().unwrap();
+    assert_eq!(hits[0].item.info.file_path.as_ref(), "zd/hulotte.part1.rar");
+    assert_eq!(group.members.len(), 4);
+    assert_eq!(group.members[0].file_path.as_ref(), "zd/hulotte.part1.rar");
+    assert_eq!(group.members[2].file_path.as_ref(), "zd/hulotte.part3.rar");
+    assert_eq!(group.total_size, 260);
+    assert_eq!(group.newest_timestamp, 40);
+
+    assert!(hits[1].group.is_none());
+}
+
+#[test]
+fn test_search_grouping_is_opt_in() {
+    let handler = SearchFilesHandler::new(support::adapter());
+
+    let flat = handler.handle(&SearchFilesQuery::new("hulotte".into(), None, 0), &index());
+    assert_eq!(flat.page.total, 4);
+    assert!(flat.page.results.iter().all(|hit| hit.group.is_none()));
+
+    let grouped = handler.handle(
+        &SearchFilesQuery::new("hulotte".into(), None, 0).with_grouping(true),
+        &index(),
+    );
+    assert_eq!(grouped.page.total, 1);
+    assert_eq!(
+        grouped.page.results[0]
+            .group
+            .as_ref()
+            .unwrap()
+            .members
+            .len(),
+        4
+    );
+    let counted: usize = grouped.facets.extensions.values().sum();
+    assert_eq!(counted, 4);
+}
@@ -0,0 +1,94 @@
+use crate::domain::search::repositories::fuzzy_search_repository::FuzzySearchRepository;
+use crate::domain::search::services::search_index_service::SearchIndexService;
+use crate::domain::search::value_objects::release_info::ReleaseInfo;
+use crate::infrastructure::adapters::search::fuse_search_adapter::{FuseConfig, FuseSearchAdapter};
+use crate::tests::support::{self, file};
+
+#[test]
+fn test_parse_release_name() {
+    let info = ReleaseInfo::parse("zd/1001-1500/[181026][hulotte] 出会って5分は俺のもの！.rar");
+    assert_eq!(info.date.as_deref(), Some("2018-10-26"));
+    assert_eq!(info.brand.as_deref(), Some("hulotte"));
+    assert_eq!(info.title, "出会って5分は俺のもの！");
+    assert_eq!(info.extension.as_deref(), Some("rar"));
+}
+
+#[test]
+fn test_parse_release_name_variants() {
+    let info = ReleaseInfo::parse("【20230115】【Key】 Summer Pockets.7Z");
+    assert_eq!(info.date.as_deref(), Some("2023-01-15"));
+    assert_eq!(info.brand.as_deref(), Some("Key"));
+    assert_eq!(info.title, "Summer Pockets");
+    assert_eq!(info.extension.as_deref(), Some("7z"));
+
+    // A non-date first tag is the brand
+    let info = ReleaseInfo::parse("0/win/[Key] Kanon.zip");
+    assert_eq!(info.date, None);
+    assert_eq!(info.brand.as_deref(), Some("Key"));
+    assert_eq!(info.title, "Kanon");
+
+    // Invalid dates are not dates
+    let info = ReleaseInfo::parse("[181399] Title.rar");
+    assert_eq!(info.date, None);
+    assert_eq!(info.brand.as_deref(), Some("181399"));
+}
+
+#[test]
+fn test_parse_plain_name() {
+    let info = ReleaseInfo::parse("0/apk/hulotte");
+    assert_eq!(info.date, None);
+    assert_eq!(info.brand, None);
+    assert_eq!(info.title, "hulotte");
+    assert_eq!(info.extension, None);
+
+    // Only tags: fall back to the whole stem
+    let info = ReleaseInfo::parse("[181026][hulotte].rar");
+    assert_eq!(info.title, "[181026][hulotte]");
+}
+
+#[test]
+fn test_index_carries_release_info() {
+    let index = SearchIndexService::new().build_index(&[vec![file(
+        "合集系列/浮士德galgame游戏合集/2019/[190125][ALICE SOFT] Rance 10.rar",
+    )]]);
+    assert_eq!(index[0].release.brand.as_deref(), Some("ALICE SOFT"));
+    assert_eq!(index[0].release.date.as_deref(), Some("2019-01-25"));
+}
+
+#[test]
+fn test_title_match_outranks_brand_match() {
+    let index = SearchIndexService::new().build_index(&[vec![
+        file("zd/[200101][Sakura] Hanabi.rar"),
+        file("zd/[200101][Hanabi] Sakura.rar"),
+    ]]);
+    let adapter = support::adapter();
+
+    // The same misspelling counts for more in the title than in the brand
+    let results = adapter.search_scored("sakuro", &index);
+    assert_eq!(results[0].item.release.title, "Sakura");
+}
+
+#[test]
+fn test_exact_match_scores_like_fuse_lib() {
+    let index = SearchIndexService::new().build_index(&[vec![file("sakura")]]);
+    let adapter = FuseSearchAdapter::new(FuseConfig {
+        file_name_only: true,
+        ..FuseConfig::default()
+    });
+
+    // fuse-lib turns an exact 0.0 of a full-weight field into 0.001
+    let results = adapter.search_scored("sakura", &index);
+    assert_eq!(results[0].score.value(), 0.001);
+    // Lighter fields keep their exact 0.0, fields are averaged
+    let results = support::adapter().search_scored("sakura", &index);
+    assert_eq!(results[0].score.value(), 0.0005);
+}
+
+#[test]
+fn test_release_info_serialization() {
+    let info = ReleaseInfo::parse("[Key] Kanon.zip");
+    let json = serde_json::to_value(&info).unwrap();
+    assert_eq!(json["brand"], "Key");
+    assert_eq!(json["title"], "Kanon");
+    assert!(json.get("date").is_none());
+}
@@ -0,0 +1,196 @@
+use crate::application::search::services::relevance_evaluator::RelevanceEvaluator;
+use crate::domain::search::entities::relevance_report::RelevanceReport;
+use crate::domain::search::services::relevance_metrics_service::RelevanceMetricsService;
+use crate::domain::search::services::search_index_service::SearchIndexService;
+use crate::infrastructure::persistence::config::golden_set_config::{
+    load_baseline, load_fixture, load_golden_set,
+};
+use crate::interfaces::cli::commands::evaluate::{self, DEFAULT_TOLERANCE, EvaluateArgs};
+use crate::tests::support;
+use std::collections::HashSet;
+
+const GOLDEN: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../eval/golden.toml");
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../eval/fixture.json");
+const BASELINE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../eval/baseline.json");
+
+fn temp_path(name: &str) -> String {
+    let file = format!("shinnku-eval-{}-{name}", std::process::id());
+    std::env::temp_dir()
+        .join(file)
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn approx(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+fn relevant(paths: &[&str]) -> Vec<String> {
+    paths.iter().map(|path| (*path).to_string()).collect()
+}
+
+#[test]
+fn test_query_metrics() {
+    let metrics = RelevanceMetricsService::new();
+    let ranked = ["x", "a", "y", "b"];
+
+    let eval = metrics.evaluate_query("q", &ranked, &relevant(&["a", "b"]));
+    assert_eq!(eval.first_relevant, Some(2));
+    assert!(approx(eval.reciprocal_rank, 0.5));
+    assert!(approx(eval.recall[&1], 0.0));
+    assert!(approx(eval.recall[&10], 1.0));
+    // Gains at ranks 2 and 4 over an ideal ranking with both at the top
+    let ideal = 1.0 + 1.0 / 3f64.log2();
+    let gain = 1.0 / 3f64.log2() + 1.0 / 5f64.log2();
+    assert!(approx(eval.ndcg, gain / ideal));
+
+    let missed = metrics.evaluate_query("q", &ranked, &relevant(&["z"]));
+    assert_eq!(missed.first_relevant, None);
+    assert!(approx(missed.reciprocal_rank, 0.0));
+    assert!(approx(missed.ndcg, 0.0));
+}
+
+#[test]
+fn test_ndcg_and_recall_cutoffs() {
+    let relevant: HashSet<&str> = ["a", "b"].into();
+    let ranked = ["a", "x", "b"];
+
+    assert!(approx(
+        RelevanceMetricsService::recall(&ranked, &relevant, 1),
+        0.5
+    ));
+    assert!(approx(
+        RelevanceMetricsService::recall(&ranked, &relevant, 3),
+        1.0
+    ));
+    assert!(approx(
+        RelevanceMetricsService::ndcg(&ranked, &relevant, 1),
+        1.0
+    ));
+    assert!(approx(
+        RelevanceMetricsService::ndcg(&["x", "a"], &relevant, 1),
+        0.0
+    ));
+}
+
+#[test]
+fn test_report_averages_queries() {
+    let metrics = RelevanceMetricsService::new();
+    let report = metrics.summarize(vec![
+        metrics.evaluate_query("hit", &["a"], &relevant(&["a"])),
+        metrics.evaluate_query("miss", &["x"], &relevant(&["a"])),
+    ]);
+
+    assert!(approx(report.mrr, 0.5));
+    assert!(approx(report.ndcg, 0.5));
+    assert!(approx(report.recall[&1], 0.5));
+}
+
+#[test]
+fn test_diff_flags_dropped_queries() {
+    let metrics = RelevanceMetricsService::new();
+    let baseline = metrics.summarize(vec![
+        metrics.evaluate_query("kept", &["a"], &relevant(&["a"])),
+        metrics.evaluate_query("dropped", &["b", "x"], &relevant(&["b"])),
+    ]);
+    let current = metrics.summarize(vec![
+        metrics.evaluate_query("kept", &["a"], &relevant(&["a"])),
+        metrics.evaluate_query("dropped", &["x", "b"], &relevant(&["b"])),
+        metrics.evaluate_query("added", &["c"], &relevant(&["c"])),
+    ]);
+
+    let diff = metrics.compare(&current, &baseline, DEFAULT_TOLERANCE);
+    assert!(diff.has_regressions());
+    assert!(
+        diff.regressions()
+            .any(|change| change.query.as_deref() == Some("dropped") && change.metric == "RR")
+    );
+    assert!(
+        diff.changes
+            .iter()
+            .all(|change| change.query.as_deref() != Some("kept"))
+    );
+    assert_eq!(diff.new_queries, ["added"]);
+
+    assert!(!metrics.compare(&baseline, &baseline, 0.0).has_regressions());
+}
+
+#[test]
+fn test_evaluate_args() {
+    let args = ["--threshold", "0.4", "--no-tokenize", "--write-baseline"].map(String::from);
+    let args = EvaluateArgs::parse(args).unwrap();
+    assert!(approx(args.fuse.threshold, 0.4));
+    assert!(!args.fuse.tokenize);
+    assert!(args.write_baseline);
+    assert_eq!(args.golden, "eval/golden.toml");
+
+    assert!(!args.force);
+    assert!(EvaluateArgs::parse(["--force".to_string()]).unwrap().force);
+
+    assert!(EvaluateArgs::parse(["--distance".to_string()]).is_err());
+    assert!(EvaluateArgs::parse(["--bogus".to_string()]).is_err());
+}
+
+#[tokio::test]
+async fn test_golden_query_without_relevant_files_rejected() {
+    let path = temp_path("golden.toml");
+    tokio::fs::write(&path, "[[query]]\nq = \"kanon\"\nrelevant = []\n")
+        .await
+        .unwrap();
+    let err = load_golden_set(&path).await.unwrap_err();
+    tokio::fs::remove_file(&path).await.unwrap();
+    assert!(err.to_string().contains("'kanon'"), "{err}");
+}
+
+#[tokio::test]
+async fn test_write_baseline_refuses_regressions() {
+    let path = temp_path("baseline.json");
+    tokio::fs::copy(BASELINE, &path).await.unwrap();
+    // Exact matches only, far worse than the baseline
+    let args = |force: bool| {
+        let mut args = vec![
+            "--golden",
+            GOLDEN,
+            "--fixture",
+            FIXTURE,
+            "--baseline",
+            &path,
+            "--write-baseline",
+            "--threshold",
+            "0.0",
+        ];
+        if force {
+            args.push("--force");
+        }
+        EvaluateArgs::parse(args.into_iter().map(String::from)).unwrap()
+    };
+
+    assert!(!evaluate::run(args(false)).await.unwrap());
+    let kept = load_baseline(&path).await.unwrap();
+    assert_eq!(kept, load_baseline(BASELINE).await.unwrap());
+
+    assert!(evaluate::run(args(true)).await.unwrap());
+    let written = load_baseline(&path).await.unwrap();
+    tokio::fs::remove_file(&path).await.unwrap();
+    assert_ne!(written, kept);
+}
+
+/// Ranking changes that hurt the golden set fail here; after an intended
+/// change, refresh the baseline with `shinnku-com-backend evaluate --write-baseline`
+#[tokio::test]
+async fn test_fuse_ranking_matches_baseline() {
+    let golden = load_golden_set(GOLDEN).await.unwrap();
+    let fixture = load_fixture(FIXTURE).await.unwrap();
+    let baseline: RelevanceReport = load_baseline(BASELINE).await.unwrap().unwrap();
+    let items = SearchIndexService::new().build_index(&[fixture]);
+
+    let evaluator = RelevanceEvaluator::new(support::adapter());
+    let report = evaluator.evaluate(&golden, &items);
+    let diff = RelevanceMetricsService::new().compare(&report, &baseline, DEFAULT_TOLERANCE);
+
+    assert!(!diff.has_regressions(), "ranking regressed:\n{diff}");
+    assert!(
+        diff.new_queries.is_empty(),
+        "baseline is missing queries:\n{diff}"
+    );
+}
@@ -0,0 +1,94 @@
+use crate::application::analytics::services::search_analytics_service::{
+    AnalyticsConfig, SearchAnalyticsService,
+};
+use crate::domain::analytics::entities::search_event::SearchEvent;
+use crate::domain::analytics::services::search_stats_service::{
+    LatencyPercentiles, QueryCount, SearchStatsService,
+};
+use std::time::Duration;
+
+fn event(query: &str, results: usize, latency_ms: u64) -> SearchEvent {
+    SearchEvent::new(0, query, "search", results, latency_ms, false)
+}
+
+fn counts(counts: &[QueryCount]) -> Vec<(&str, usize)> {
+    counts
+        .iter()
+        .map(|count| (count.query.as_str(), count.count))
+        .collect()
+}
+
+#[test]
+fn test_event_normalizes_query() {
+    let event = SearchEvent::new(1, "  Summer   Pockets ", "aisearch", 3, 12, true);
+    assert_eq!(event.query, "summer pockets");
+
+    let json = serde_json::to_string(&event).unwrap();
+    assert_eq!(serde_json::from_str::<SearchEvent>(&json).unwrap(), event);
+}
+
+#[test]
+fn test_top_queries() {
+    let events = vec![
+        event("kanon", 3, 1),
+        event("Summer Pockets", 5, 1),
+        event("summer  pockets", 5, 1),
+        event("air", 0, 1),
+        event("air", 0, 1),
+        event("", 10, 1),
+        event("clannad", 0, 1),
+    ];
+    let stats = SearchStatsService::new();
+
+    assert_eq!(
+        counts(&stats.top_queries(&events, 10)),
+        [
+            ("air", 2),
+            ("summer pockets", 2),
+            ("clannad", 1),
+            ("kanon", 1)
+        ]
+    );
+    assert_eq!(counts(&stats.top_queries(&events, 1)), [("air", 2)]);
+    assert_eq!(
+        counts(&stats.top_zero_result_queries(&events, 10)),
+        [("air", 2), ("clannad", 1)]
+    );
+}
+
+#[test]
+fn test_latency_percentiles() {
+    let stats = SearchStatsService::new();
+
+    let events: Vec<SearchEvent> = (1..=100).rev().map(|ms| event("q", 1, ms)).collect();
+    assert_eq!(
+        stats.latency_percentiles(&events),
+        LatencyPercentiles {
+            count: 100,
+            p50: 50,
+            p90: 90,
+            p99: 99,
+            max: 100,
+        }
+    );
+
+    let single = stats.latency_percentiles(&[event("q", 1, 7)]);
+    assert_eq!((single.p50, single.p99, single.max), (7, 7, 7));
+
+    assert_eq!(
+        stats.latency_percentiles(&[]),
+        LatencyPercentiles::default()
+    );
+}
+
+#[tokio::test]
+async fn test_service_without_redis() {
+    let analytics = SearchAnalyticsService::new(AnalyticsConfig::default(), None);
+    analytics.record("search", "kanon", 0, Duration::from_millis(5), false);
+
+    let window = Duration::from_secs(3600);
+    let (searches, queries) = analytics.top_queries(window, 10).await.unwrap();
+    assert_eq!(searches, 0);
+    assert!(queries.is_empty());
+    assert_eq!(analytics.latency(window).await.unwrap()["all"].count, 0);
+}
@@ -0,0 +1,105 @@
+use crate::application::search::dto::search_response::SearchResponse;
+use crate::application::search::handlers::combined_search_handler::CombinedSearchHandler;
+use crate::application::search::handlers::search_files_handler::SearchFilesHandler;
+use crate::application::search::queries::combined_search_query::CombinedSearchQuery;
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::application::search::services::search_cache_service::{
+    SearchCacheConfig, SearchCacheService,
+};
+use crate::application::search::services::search_execution_service::{
+    SearchExecutionConfig, SearchExecutionService,
+};
+use crate::domain::search::repositories::fuzzy_search_repository::FuzzySearchRepository;
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
+use crate::interfaces::http::dto::search_dto::SearchStreamEvent;
+use crate::tests::support;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+fn files_search(budget: Duration) -> SearchResponse {
+    let adapter = support::adapter().with_time_budget(budget);
+    let query = SearchFilesQuery::new("summer pockets".into(), None, 0);
+    SearchFilesHandler::new(adapter).handle(&query, &support::index(support::SUMMER_POCKETS))
+}
+
+#[test]
+fn test_search_within_budget_is_complete() {
+    let response = files_search(Duration::from_secs(60));
+    assert_eq!(response.page.total, 2);
+    assert!(!response.partial);
+
+    let adapter = support::adapter();
+    assert_eq!(
+        adapter
+            .search_scored("summer pockets", &support::index(support::SUMMER_POCKETS))
+            .len(),
+        2
+    );
+    assert!(!adapter.is_partial());
+}
+
+#[test]
+fn test_exhausted_budget_marks_response_partial() {
+    let response = files_search(Duration::ZERO);
+    assert!(response.partial);
+    assert!(response.page.total < 2);
+
+    let adapter = support::adapter().with_time_budget(Duration::ZERO);
+    let query = CombinedSearchQuery::new(
+        vec![
+            WeightedQuery::unweighted("summer"),
+            WeightedQuery::unweighted("pockets"),
+        ],
+        10,
+        0,
+    );
+    assert!(
+        CombinedSearchHandler::new(adapter)
+            .handle(&query, &support::index(support::SUMMER_POCKETS))
+            .partial
+    );
+
+    let events = SearchStreamEvent::sequence(Ok(&response));
+    assert!(matches!(events.last(), Some(SearchStreamEvent::Done(summary)) if summary.partial));
+}
+
+#[test]
+fn test_partial_flag_serialized() {
+    let complete = serde_json::to_value(files_search(Duration::from_secs(60))).unwrap();
+    assert_eq!(complete["partial"], false);
+    let partial = serde_json::to_value(files_search(Duration::ZERO)).unwrap();
+    assert_eq!(partial["partial"], true);
+}
+
+#[tokio::test]
+async fn test_partial_response_not_cached() {
+    let cache = SearchCacheService::new("v1", SearchCacheConfig::default(), None);
+    let executor = SearchExecutionService::new(Arc::new(cache), SearchExecutionConfig::default());
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    for expected in 1..=2 {
+        let counter = calls.clone();
+        let response = executor
+            .run("key".into(), move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                files_search(Duration::ZERO)
+            })
+            .await
+            .unwrap();
+        assert!(response.partial);
+        assert_eq!(calls.load(Ordering::SeqCst), expected);
+    }
+
+    executor
+        .run("key".into(), || files_search(Duration::from_secs(60)))
+        .await
+        .unwrap();
+    let cached = executor
+        .run("key".into(), || -> SearchResponse {
+            unreachable!("served from cache")
+        })
+        .await
+        .unwrap();
+    assert!(!cached.partial);
+}
@@ -0,0 +1,165 @@
+use crate::application::search::dto::search_response::SearchResponse;
+use crate::application::search::handlers::search_files_handler::SearchFilesHandler;
+use crate::application::search::queries::combined_search_query::CombinedSearchQuery;
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::application::search::services::query_parser::QueryParser;
+use crate::application::search::services::search_cache_service::{
+    SearchCacheConfig, SearchCacheService,
+};
+use crate::domain::search::services::search_index_service::SearchIndexService;
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
+use crate::infrastructure::adapters::search::fuse_search_adapter::{
+    FuseConfig, FuseOverrides, FuseSearchAdapter,
+};
+use crate::tests::support::{self, index};
+use std::sync::Arc;
+
+fn cache(capacity: usize) -> SearchCacheService {
+    let config = SearchCacheConfig {
+        capacity,
+        ..SearchCacheConfig::default()
+    };
+    SearchCacheService::new("v1", config, None)
+}
+
+fn response(paths: &[&str]) -> Arc<SearchResponse> {
+    let handler = SearchFilesHandler::new(support::adapter());
+    Arc::new(handler.handle(&SearchFilesQuery::new("zd".into(), None, 0), &index(paths)))
+}
+
+#[test]
+fn test_files_key_normalization() {
+    let cache = cache(8);
+    let key = |q: &str| {
+        let parsed = QueryParser::parse(q).unwrap();
+        cache.files_key(
+            &SearchFilesQuery::new(parsed.text, Some(10), 0).with_filters(parsed.filters),
+        )
+    };
+
+    assert_eq!(key("Summer  Pockets"), key(" summer pockets "));
+    assert_ne!(key("summer pockets"), key("summer pockets ext:rar"));
+    assert_ne!(key("summer pockets ext:rar"), key("summer pockets ext:7z"));
+
+    // Pages are cut from one cached match set
+    let base = SearchFilesQuery::new("kanon".into(), Some(10), 0);
+    assert_eq!(
+        cache.files_key(&base),
+        cache.files_key(&SearchFilesQuery::new("kanon".into(), Some(10), 10))
+    );
+    assert_eq!(
+        cache.files_key(&base),
+        cache.files_key(&SearchFilesQuery::new("kanon".into(), None, 0))
+    );
+    assert_ne!(
+        cache.files_key(&base),
+        cache.files_key(&base.clone().with_grouping(true))
+    );
+}
+
+#[test]
+fn test_combined_key_normalization() {
+    let cache = cache(8);
+    let key =
+        |queries: Vec<WeightedQuery>| cache.combined_key(&CombinedSearchQuery::new(queries, 10, 0));
+
+    assert_eq!(
+        key(vec![WeightedQuery::unweighted("Kanon ")]),
+        key(vec![WeightedQuery::unweighted("kanon")])
+    );
+    assert_ne!(
+        key(vec![WeightedQuery::unweighted("kanon")]),
+        key(vec![WeightedQuery::new("kanon", 2.0)])
+    );
+    assert_eq!(
+        cache.combined_key(&CombinedSearchQuery::new(
+            vec![WeightedQuery::unweighted("kanon")],
+            10,
+            0
+        )),
+        cache.combined_key(&CombinedSearchQuery::new(
+            vec![WeightedQuery::unweighted("kanon")],
+            20,
+            40
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_case_sensitive_tuning_keeps_case_in_key() {
+    let cache = cache(8);
+    let fuse = FuseOverrides {
+        is_case_sensitive: Some(true),
+        ..FuseOverrides::default()
+    }
+    .apply(FuseConfig::default())
+    .unwrap();
+    let handler = SearchFilesHandler::new(FuseSearchAdapter::new(fuse.clone()));
+    let index = index(&["zd/Kanon.rar", "zd/kanon.rar"]);
+    let query = |q: &str| SearchFilesQuery::new(q.into(), None, 0).with_tuning(fuse.label());
+
+    let upper = query("Kanon");
+    cache
+        .put(
+            cache.files_key(&upper),
+            Arc::new(handler.handle(&upper, &index)),
+        )
+        .await;
+    let lower = query("kanon");
+    assert_ne!(cache.files_key(&upper), cache.files_key(&lower));
+    assert!(cache.get(&cache.files_key(&lower)).await.is_none());
+    assert!(cache.get(&cache.files_key(&upper)).await.is_some());
+
+    // Without tuning the default, case-insensitive matching still shares one entry
+    assert_eq!(
+        cache.files_key(&SearchFilesQuery::new("Kanon".into(), None, 0)),
+        cache.files_key(&SearchFilesQuery::new("kanon".into(), None, 0))
+    );
+}
+
+#[test]
+fn test_index_version_in_key() {
+    let query = SearchFilesQuery::new("kanon".into(), None, 0);
+    let v1 = SearchCacheService::new("v1", SearchCacheConfig::default(), None);
+    let v2 = SearchCacheService::new("v2", SearchCacheConfig::default(), None);
+    assert_ne!(v1.files_key(&query), v2.files_key(&query));
+}
+
+#[test]
+fn test_fingerprint_tracks_data() {
+    let service = SearchIndexService::new();
+    let a = service.fingerprint(&index(&["zd/a.rar", "zd/b.rar"]));
+    assert_eq!(a, service.fingerprint(&index(&["zd/a.rar", "zd/b.rar"])));
+    assert_ne!(a, service.fingerprint(&index(&["zd/a.rar"])));
+    assert_ne!(a, service.fingerprint(&index(&["zd/a.rar", "zd/c.rar"])));
+}
+
+#[tokio::test]
+async fn test_get_put_and_eviction() {
+    let cache = cache(2);
+    assert!(cache.get("a").await.is_none());
+
+    cache.put("a".into(), response(&["zd/a.rar"])).await;
+    cache.put("b".into(), response(&["zd/b.rar"])).await;
+    assert_eq!(cache.get("a").await.unwrap().page.total, 1);
+
+    // `b` is now least recently used and makes room for `c`
+    cache.put("c".into(), response(&["zd/c.rar"])).await;
+    assert!(cache.get("b").await.is_none());
+    assert!(cache.get("a").await.is_some());
+    assert!(cache.get("c").await.is_some());
+}
+
+#[test]
+fn test_response_round_trip() {
+    // The Redis tier stores responses as JSON
+    let response = response(&[
+        "zd/[181026][hulotte] a.part1.rar",
+        "zd/[181026][hulotte] a.part2.rar",
+    ]);
+    let json = serde_json::to_string(response.as_ref()).unwrap();
+    let restored: SearchResponse = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored.page, response.page);
+    assert_eq!(restored.facets, response.facets);
+}
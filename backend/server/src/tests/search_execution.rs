@@ -0,0 +1,177 @@
+use crate::application::search::dto::search_response::SearchResponse;
+use crate::application::search::services::search_cache_service::{
+    SearchCacheConfig, SearchCacheService,
+};
+use crate::application::search::services::search_execution_service::{
+    SearchExecutionConfig, SearchExecutionService,
+};
+use crate::error::AppError;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+fn executor(config: SearchExecutionConfig) -> Arc<SearchExecutionService> {
+    let cache = SearchCacheService::new("v1", SearchCacheConfig::default(), None);
+    Arc::new(SearchExecutionService::new(Arc::new(cache), config))
+}
+
+/// A search taking `delay` that counts how often it ran
+fn slow_search(
+    calls: &Arc<AtomicUsize>,
+    delay: Duration,
+) -> impl FnOnce() -> SearchResponse + Send + 'static {
+    let calls = calls.clone();
+    move || {
+        calls.fetch_add(1, Ordering::SeqCst);
+        std::thread::sleep(delay);
+        SearchResponse::from_results(Vec::new(), 0, None, false)
+    }
+}
+
+#[tokio::test]
+async fn test_identical_searches_coalesce() {
+    let executor = executor(SearchExecutionConfig::default());
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let tasks: Vec<_> = (0..20)
+        .map(|_| {
+            let executor = executor.clone();
+            let search = slow_search(&calls, Duration::from_millis(100));
+            tokio::spawn(async move { executor.run("same".into(), search).await })
+        })
+        .collect();
+
+    let mut responses = Vec::new();
+    for task in tasks {
+        responses.push(task.await.unwrap().unwrap());
+    }
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(responses.iter().all(|r| Arc::ptr_eq(r, &responses[0])));
+}
+
+#[tokio::test]
+async fn test_finished_search_served_from_cache() {
+    let executor = executor(SearchExecutionConfig::default());
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    executor
+        .run("key".into(), slow_search(&calls, Duration::ZERO))
+        .await
+        .unwrap();
+    executor
+        .run("key".into(), slow_search(&calls, Duration::ZERO))
+        .await
+        .unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    executor
+        .run("other".into(), slow_search(&calls, Duration::ZERO))
+        .await
+        .unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_rejects_when_queue_full() {
+    let executor = executor(SearchExecutionConfig {
+        max_concurrent: 1,
+        max_queued: 0,
+        queue_timeout: Duration::from_secs(5),
+        ..SearchExecutionConfig::default()
+    });
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let busy = {
+        let executor = executor.clone();
+        let search = slow_search(&calls, Duration::from_millis(200));
+        tokio::spawn(async move { executor.run("a".into(), search).await })
+    };
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let rejected = executor
+        .run("b".into(), slow_search(&calls, Duration::ZERO))
+        .await;
+    assert!(matches!(rejected, Err(AppError::Unavailable(_))));
+
+    busy.await.unwrap().unwrap();
+    executor
+        .run("b".into(), slow_search(&calls, Duration::ZERO))
+        .await
+        .unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_queued_search_waits_or_times_out() {
+    let executor = executor(SearchExecutionConfig {
+        max_concurrent: 1,
+        max_queued: 1,
+        queue_timeout: Duration::from_millis(50),
+        ..SearchExecutionConfig::default()
+    });
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let busy = {
+        let executor = executor.clone();
+        let search = slow_search(&calls, Duration::from_millis(300));
+        tokio::spawn(async move { executor.run("a".into(), search).await })
+    };
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let timed_out = executor
+        .run("b".into(), slow_search(&calls, Duration::ZERO))
+        .await;
+    assert!(matches!(timed_out, Err(AppError::Unavailable(_))));
+    busy.await.unwrap().unwrap();
+
+    // With a longer wait the queued search gets the slot once it frees up
+    let executor = self::executor(SearchExecutionConfig {
+        max_concurrent: 1,
+        max_queued: 1,
+        queue_timeout: Duration::from_secs(5),
+        ..SearchExecutionConfig::default()
+    });
+    let busy = {
+        let executor = executor.clone();
+        let search = slow_search(&calls, Duration::from_millis(100));
+        tokio::spawn(async move { executor.run("a".into(), search).await })
+    };
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    executor
+        .run("b".into(), slow_search(&calls, Duration::ZERO))
+        .await
+        .unwrap();
+    busy.await.unwrap().unwrap();
+}
+
+#[test]
+fn test_unavailable_status() {
+    let response = AppError::Unavailable("busy".into()).into_response();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_blocking_work_shares_search_slots() {
+    let executor = executor(SearchExecutionConfig {
+        max_concurrent: 1,
+        max_queued: 0,
+        ..SearchExecutionConfig::default()
+    });
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let busy = {
+        let executor = executor.clone();
+        let search = slow_search(&calls, Duration::from_millis(200));
+        tokio::spawn(async move { executor.run("a".into(), search).await })
+    };
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let rejected = executor.run_blocking(|| 1).await;
+    assert!(matches!(rejected, Err(AppError::Unavailable(_))));
+
+    busy.await.unwrap().unwrap();
+    assert_eq!(executor.run_blocking(|| 1).await.unwrap(), 1);
+}
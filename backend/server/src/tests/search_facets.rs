@@ -0,0 +1,77 @@
+use crate::application::files::services::file_tree_service::FileTreeService;
+use crate::application::search::handlers::search_files_handler::SearchFilesHandler;
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::application::search::services::facet_service::FacetService;
+use crate::domain::search::entities::search_item::{SearchItem, SearchList};
+use crate::tests::support;
+
+fn index() -> SearchList {
+    support::index_with(&[
+        // 2018-10-26
+        (
+            "zd/1001-1500/[181026][hulotte] 出会って5分は俺のもの！.rar",
+            1,
+            1_540_512_000_000,
+        ),
+        // 2023-01-01
+        ("0/apk/hulotte.apk", 1, 1_672_531_200_000),
+        // 2022-12-31
+        (
+            "合集系列/浮士德galgame游戏合集/2019/hulotte.7z",
+            1,
+            1_672_444_800_000,
+        ),
+    ])
+}
+
+#[test]
+fn test_tree_path() {
+    assert_eq!(FileTreeService::tree_path("zd/a.rar"), "shinnku/zd/a.rar");
+    assert_eq!(
+        FileTreeService::tree_path("合集系列/浮士德galgame游戏合集/2019/a.7z"),
+        "galgame0/2019/a.7z"
+    );
+}
+
+#[test]
+fn test_year_from_millis() {
+    assert_eq!(FacetService::year_from_millis(0), 1970);
+    assert_eq!(FacetService::year_from_millis(1_672_444_800_000), 2022);
+    assert_eq!(FacetService::year_from_millis(1_672_531_200_000), 2023);
+    // 2020-02-29, a leap day
+    assert_eq!(FacetService::year_from_millis(1_582_934_400_000), 2020);
+}
+
+#[test]
+fn test_facet_counts() {
+    let items: Vec<SearchItem> = index();
+    let facets = FacetService::count(&items);
+
+    assert_eq!(facets.buckets["shinnku"], 2);
+    assert_eq!(facets.buckets["galgame0"], 1);
+    assert_eq!(facets.categories["shinnku/zd"], 1);
+    assert_eq!(facets.categories["shinnku/0"], 1);
+    assert_eq!(facets.categories["galgame0/2019"], 1);
+    assert_eq!(facets.extensions["rar"], 1);
+    assert_eq!(facets.extensions["apk"], 1);
+    assert_eq!(facets.extensions["7z"], 1);
+    assert_eq!(facets.game_types["熟肉"], 1);
+    assert_eq!(facets.game_types["手机"], 1);
+    assert_eq!(facets.game_types["生肉"], 1);
+    assert_eq!(facets.years[&2018], 1);
+    assert_eq!(facets.years[&2022], 1);
+    assert_eq!(facets.years[&2023], 1);
+}
+
+#[test]
+fn test_facets_cover_full_match_set() {
+    let handler = SearchFilesHandler::new(support::adapter());
+    let response = handler.handle(
+        &SearchFilesQuery::new("hulotte".into(), Some(1), 0),
+        &index(),
+    );
+
+    assert_eq!(response.page.results.len(), 1);
+    let counted: usize = response.facets.buckets.values().sum();
+    assert_eq!(counted, response.page.total);
+}
@@ -2,7 +2,11 @@ use crate::domain::files::entities::file_info::FileInfo;
 use crate::domain::search::entities::search_item::SearchItem;
 use crate::domain::search::repositories::fuzzy_search_repository::FuzzySearchRepository;
 use crate::domain::search::services::search_index_service::SearchIndexService;
-use crate::infrastructure::adapters::search::fuse_search_adapter::FuseSearchAdapter;
+use crate::domain::search::value_objects::fusion_strategy::FusionStrategy;
+use crate::domain::search::value_objects::item_kind::ItemKind;
+use crate::domain::search::value_objects::release_info::ReleaseInfo;
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
+use crate::tests::support;
 
 #[test]
 fn test_search_index_builder() {
@@ -35,6 +39,9 @@ fn test_runsearch() {
                 upload_timestamp: 0,
                 file_size: 1,
             },
+            release: ReleaseInfo::default(),
+            kind: ItemKind::File,
+            file_count: None,
         },
         SearchItem {
             id: "bar.txt".into(),
@@ -43,13 +50,16 @@ fn test_runsearch() {
                 upload_timestamp: 0,
                 file_size: 1,
             },
+            release: ReleaseInfo::default(),
+            kind: ItemKind::File,
+            file_count: None,
         },
     ];
 
-    let adapter = FuseSearchAdapter::with_default_config();
-    let res = adapter.search("foo", &files);
+    let adapter = support::adapter();
+    let res = adapter.search_scored("foo", &files);
     assert!(!res.is_empty());
-    assert_eq!(res[0].id, "foo.txt");
+    assert_eq!(res[0].item.id, "foo.txt");
 }
 
 #[test]
@@ -62,6 +72,9 @@ fn test_combine_search() {
                 upload_timestamp: 0,
                 file_size: 1,
             },
+            release: ReleaseInfo::default(),
+            kind: ItemKind::File,
+            file_count: None,
         },
         SearchItem {
             id: "bar.txt".into(),
@@ -70,18 +83,37 @@ fn test_combine_search() {
                 upload_timestamp: 0,
                 file_size: 1,
             },
+            release: ReleaseInfo::default(),
+            kind: ItemKind::File,
+            file_count: None,
         },
     ];
 
-    let adapter = FuseSearchAdapter::with_default_config();
-    let res = adapter.combined_search("foo", "bar", 10, &files);
+    let adapter = support::adapter();
+    let res = adapter.fused_search(
+        &[
+            WeightedQuery::unweighted("foo"),
+            WeightedQuery::unweighted("bar"),
+        ],
+        FusionStrategy::Average,
+        10,
+        &files,
+    );
     assert_eq!(res.len(), 2);
-    assert!(res.iter().any(|i| i.id == "foo.txt"));
-    assert!(res.iter().any(|i| i.id == "bar.txt"));
+    assert!(res.iter().any(|r| r.item.id == "foo.txt"));
+    assert!(res.iter().any(|r| r.item.id == "bar.txt"));
 
-    let res2 = adapter.combined_search("foo", "foo", 10, &files);
+    let res2 = adapter.fused_search(
+        &[
+            WeightedQuery::unweighted("foo"),
+            WeightedQuery::unweighted("foo"),
+        ],
+        FusionStrategy::Average,
+        10,
+        &files,
+    );
     assert_eq!(res2.len(), 1);
-    assert_eq!(res2[0].id, "foo.txt");
+    assert_eq!(res2[0].item.id, "foo.txt");
 }
 
 #[test]
@@ -94,13 +126,16 @@ fn test_long_search_query() {
             upload_timestamp: 0,
             file_size: 1,
         },
+        release: ReleaseInfo::default(),
+        kind: ItemKind::File,
+        file_count: None,
     }];
 
-    let adapter = FuseSearchAdapter::with_default_config();
+    let adapter = support::adapter();
 
     // Test with original problematic query - should not panic
     let long_query = "出会った5分は俺のもの！時間停止と不可避な運命";
-    let res = adapter.search(long_query, &files);
+    let res = adapter.search_scored(long_query, &files);
     // The main goal is that this doesn't panic due to shift overflow
     // The result might be empty due to pattern truncation, which is acceptable
     println!(
@@ -110,7 +145,7 @@ fn test_long_search_query() {
 
     // Test with a shorter query that should match
     let short_query = "出会った";
-    let res2 = adapter.search(short_query, &files);
+    let res2 = adapter.search_scored(short_query, &files);
     assert!(!res2.is_empty(), "Short query should find matches");
 }
 
@@ -125,16 +160,19 @@ fn test_char_boundary_panic() {
             upload_timestamp: 0,
             file_size: 1,
         },
+        release: ReleaseInfo::default(),
+        kind: ItemKind::File,
+        file_count: None,
     }];
 
-    let adapter = FuseSearchAdapter::with_default_config();
+    let adapter = support::adapter();
 
     // This is the URL-decoded query from the error:
     // %E5%87%BA%E4%BC%9A%E3%81%A3%E3%81%A65%E5%88%86%E3%81%AF%E4%BF%BA%E3%81%AE%E3%82%82%E3%81%AE%EF%BC%81%E6%99%82%E9%96%93%E5%81%9C%E6%AD%A2%E3%81%A8%E4%B8%8D%E5%8F%AF%E9%81%BF%E3%81%AA%E9%81%8B%E5%91%BD
     let problematic_query = "出会って5分は俺のもの！時間停止と不可避な運命";
 
     // This should reproduce the panic about char boundary at byte index 63
-    let res = adapter.search(problematic_query, &files);
+    let res = adapter.search_scored(problematic_query, &files);
 
     // If we get here without panicking, the bug is fixed
     println!("Search completed successfully. Result count: {}", res.len());
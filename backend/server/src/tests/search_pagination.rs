@@ -0,0 +1,106 @@
+use crate::application::search::handlers::combined_search_handler::CombinedSearchHandler;
+use crate::application::search::handlers::search_files_handler::SearchFilesHandler;
+use crate::application::search::queries::combined_search_query::CombinedSearchQuery;
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::application::shared::dto::common::{Page, decode_cursor, resolve_offset};
+use crate::domain::search::value_objects::weighted_query::WeightedQuery;
+use crate::tests::support;
+
+#[test]
+fn test_paginate_walks_all_pages() {
+    let page = Page::paginate((0..5).collect(), 0, Some(2));
+    assert_eq!(page.total, 5);
+    assert_eq!(page.results, vec![0, 1]);
+
+    let offset = decode_cursor(page.next_cursor.as_deref().unwrap()).unwrap();
+    let page = Page::paginate((0..5).collect(), offset, Some(2));
+    assert_eq!(page.results, vec![2, 3]);
+
+    let offset = decode_cursor(page.next_cursor.as_deref().unwrap()).unwrap();
+    let page = Page::paginate((0..5).collect(), offset, Some(2));
+    assert_eq!(page.results, vec![4]);
+    assert_eq!(page.next_cursor, None);
+}
+
+#[test]
+fn test_paginate_without_limit_returns_rest() {
+    let page = Page::paginate((0..5).collect::<Vec<_>>(), 3, None);
+    assert_eq!(page.total, 5);
+    assert_eq!(page.results, vec![3, 4]);
+    assert_eq!(page.next_cursor, None);
+
+    let page = Page::paginate((0..5).collect::<Vec<_>>(), 10, Some(2));
+    assert!(page.results.is_empty());
+    assert_eq!(page.next_cursor, None);
+}
+
+#[test]
+fn test_resolve_offset() {
+    assert_eq!(resolve_offset(None, None).unwrap(), 0);
+    assert_eq!(resolve_offset(Some(7), None).unwrap(), 7);
+    assert!(resolve_offset(Some(1), Some("o1")).is_err());
+    assert!(resolve_offset(None, Some("garbage")).is_err());
+}
+
+#[test]
+fn test_search_handler_pages() {
+    let index = support::index(&["foo1.txt", "foo2.txt", "foo3.txt", "bar.txt"]);
+    let handler = SearchFilesHandler::new(support::adapter());
+
+    let first = handler
+        .handle(&SearchFilesQuery::new("foo".into(), Some(2), 0), &index)
+        .page;
+    let second = handler
+        .handle(&SearchFilesQuery::new("foo".into(), Some(2), 2), &index)
+        .page;
+    assert_eq!(first.total, second.total);
+    assert_eq!(first.results.len(), 2);
+    assert!(
+        second
+            .results
+            .iter()
+            .all(|item| !first.results.contains(item))
+    );
+}
+
+#[test]
+fn test_combined_handler_reports_full_total() {
+    let index = support::index(&["foo.txt", "bar.txt"]);
+    let handler = CombinedSearchHandler::new(support::adapter());
+
+    let page = handler
+        .handle(
+            &CombinedSearchQuery::new(
+                vec![
+                    WeightedQuery::unweighted("foo"),
+                    WeightedQuery::unweighted("bar"),
+                ],
+                1,
+                0,
+            ),
+            &index,
+        )
+        .page;
+    assert_eq!(page.total, 2);
+    assert_eq!(page.results.len(), 1);
+    assert!(page.next_cursor.is_some());
+}
+
+#[test]
+fn test_pages_cut_from_full_response() {
+    let index = support::index(&["foo1.txt", "foo2.txt", "foo3.txt", "foo4.txt", "bar.txt"]);
+    let handler = SearchFilesHandler::new(support::adapter());
+    let query = SearchFilesQuery::new("foo".into(), Some(2), 0);
+    let full = handler.handle(&query.clone().unpaged(), &index);
+    assert_eq!(full.page.results.len(), full.page.total);
+    assert_eq!(full.page.next_cursor, None);
+
+    for offset in [0, 2, 4, 10] {
+        let mut paged = query.clone();
+        paged.offset = offset;
+        let expected = handler.handle(&paged, &index);
+        let page = full.page(offset, Some(2));
+        assert_eq!(page.page, expected.page);
+        assert_eq!(page.facets, expected.facets);
+    }
+}
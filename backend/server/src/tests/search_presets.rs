@@ -0,0 +1,135 @@
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::application::search::services::preset_service::PresetService;
+use crate::application::search::services::search_cache_service::{
+    SearchCacheConfig, SearchCacheService,
+};
+use crate::domain::search::repositories::fuzzy_search_repository::FuzzySearchRepository;
+use crate::domain::search::services::search_index_service::SearchIndexService;
+use crate::error::AppError;
+use crate::infrastructure::adapters::search::fuse_search_adapter::{
+    FuseConfig, FuseOverrides, FuseSearchAdapter,
+};
+use crate::infrastructure::persistence::config::preset_config::load_preset_file;
+use crate::tests::support;
+use std::collections::BTreeMap;
+
+const PRESETS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../presets.toml");
+
+fn code(err: AppError) -> &'static str {
+    match err {
+        AppError::InvalidInput { code, .. } => code,
+        other => panic!("expected an input error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_preset_file_keeps_defaults_for_missing_fields() {
+    let presets = load_preset_file(PRESETS).await.unwrap();
+    assert!(presets.contains_key("strict"));
+    assert!(presets.contains_key("loose"));
+
+    let file_name_only = &presets["filename-only"];
+    assert!(file_name_only.file_name_only);
+    assert_eq!(
+        *file_name_only,
+        FuseConfig {
+            file_name_only: true,
+            ..FuseConfig::default()
+        }
+    );
+    assert!(load_preset_file("missing.toml").await.unwrap().is_empty());
+}
+
+#[test]
+fn test_resolve_presets() {
+    let strict = FuseConfig {
+        threshold: 0.3,
+        ..FuseConfig::default()
+    };
+    let broken = FuseConfig {
+        threshold: 2.0,
+        ..FuseConfig::default()
+    };
+    let service = PresetService::new(BTreeMap::from([
+        ("strict".to_string(), strict.clone()),
+        ("broken".to_string(), broken),
+    ]));
+
+    assert_eq!(service.resolve(None).unwrap(), FuseConfig::default());
+    assert_eq!(service.resolve(Some("strict")).unwrap(), strict);
+    assert_eq!(
+        code(service.resolve(Some("broken")).unwrap_err()),
+        "unknown_preset"
+    );
+    assert_eq!(
+        code(service.resolve(Some("nope")).unwrap_err()),
+        "unknown_preset"
+    );
+}
+
+#[test]
+fn test_overrides_are_bounds_checked() {
+    let overrides = FuseOverrides {
+        distance: Some(50),
+        tokenize: Some(false),
+        ..FuseOverrides::default()
+    };
+    let config = overrides.apply(FuseConfig::default()).unwrap();
+    assert_eq!(config.distance, 50);
+    assert!(!config.tokenize);
+    assert!((config.threshold - 0.6).abs() < f64::EPSILON);
+
+    for overrides in [
+        FuseOverrides {
+            threshold: Some(-0.1),
+            ..FuseOverrides::default()
+        },
+        FuseOverrides {
+            distance: Some(0),
+            ..FuseOverrides::default()
+        },
+        FuseOverrides {
+            max_pattern_length: Some(65),
+            ..FuseOverrides::default()
+        },
+    ] {
+        let err = overrides.apply(FuseConfig::default()).unwrap_err();
+        assert_eq!(code(err.into()), "tuning_out_of_range");
+    }
+}
+
+#[test]
+fn test_file_name_only_ignores_folders() {
+    let files = support::files(&["zd/summer pockets/setup.exe", "zd/misc/summer pockets.rar"]);
+    let items = SearchIndexService::new().build_index(&[files]);
+    let paths = |config: FuseConfig| -> Vec<String> {
+        FuseSearchAdapter::new(config)
+            .search_scored("summer pockets", &items)
+            .into_iter()
+            .map(|result| result.item.info.file_path.to_string())
+            .collect()
+    };
+
+    assert_eq!(paths(FuseConfig::default()).len(), 2);
+    let config = FuseConfig {
+        file_name_only: true,
+        ..FuseConfig::default()
+    };
+    assert_eq!(paths(config), ["zd/misc/summer pockets.rar"]);
+}
+
+#[test]
+fn test_tuning_separates_cache_entries() {
+    let cache = SearchCacheService::new("v1", SearchCacheConfig::default(), None);
+    let strict = FuseConfig {
+        threshold: 0.3,
+        ..FuseConfig::default()
+    };
+    assert_eq!(FuseConfig::default().label(), None);
+
+    let query = SearchFilesQuery::new("kanon".into(), None, 0);
+    let default_key = cache.files_key(&query.clone().with_tuning(FuseConfig::default().label()));
+    let strict_key = cache.files_key(&query.clone().with_tuning(strict.label()));
+    assert_eq!(default_key, cache.files_key(&query));
+    assert_ne!(default_key, strict_key);
+}
@@ -0,0 +1,106 @@
+use crate::application::files::services::file_tree_service::FileTreeService;
+use crate::application::search::handlers::search_files_handler::SearchFilesHandler;
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::application::search::queries::search_filters::SearchFilters;
+use crate::domain::files::entities::tree_node::TreeNode;
+use crate::domain::search::entities::search_item::SearchList;
+use crate::error::AppError;
+use crate::tests::support::{self, files};
+
+const SHINNKU: &[&str] = &[
+    "zd/1001-1500/summer pockets.rar",
+    "zd/1001-1500/kanon.rar",
+    "zd/1001-15000/summer pockets.7z",
+    "zd/1501-2000/summer pockets reflection blue.rar",
+];
+const GALGAME0: &[&str] = &["合集系列/浮士德galgame游戏合集/2020/summer pockets.rar"];
+
+fn tree() -> TreeNode {
+    let shinnku = TreeNode::from(files(SHINNKU).as_slice());
+    let galgame0 = TreeNode::from(files(GALGAME0).as_slice());
+    FileTreeService::build_combined_frontend_tree(&shinnku, &galgame0)
+}
+
+fn index() -> SearchList {
+    support::bucket_index(files(SHINNKU), files(GALGAME0))
+}
+
+fn search(query: &str, scope: Option<String>) -> Vec<String> {
+    let handler = SearchFilesHandler::new(support::adapter());
+    let query = SearchFilesQuery::new(query.into(), None, 0).with_filters(SearchFilters {
+        scope,
+        ..SearchFilters::default()
+    });
+    handler
+        .handle(&query, &index())
+        .page
+        .results
+        .into_iter()
+        .map(|hit| hit.item.info.file_path.to_string())
+        .collect()
+}
+
+#[test]
+fn test_resolve_scope() {
+    let tree = tree();
+    assert_eq!(
+        FileTreeService::resolve_scope(&tree, "/shinnku/zd/1001-1500/").unwrap(),
+        Some("shinnku/zd/1001-1500".into())
+    );
+    assert_eq!(
+        FileTreeService::resolve_scope(&tree, "galgame0/2020").unwrap(),
+        Some("galgame0/2020".into())
+    );
+    assert!(
+        FileTreeService::resolve_scope(&tree, "%E5%90%88%E9%9B%86").is_err(),
+        "galgame0 bucket prefix is not part of the combined tree"
+    );
+    assert_eq!(FileTreeService::resolve_scope(&tree, "/").unwrap(), None);
+}
+
+#[test]
+fn test_resolve_scope_percent_decoded() {
+    let shinnku = TreeNode::from(files(&["游戏/a.rar"]).as_slice());
+    let tree = FileTreeService::build_combined_frontend_tree(&shinnku, &TreeNode::new());
+    assert_eq!(
+        FileTreeService::resolve_scope(&tree, "shinnku/%E6%B8%B8%E6%88%8F").unwrap(),
+        Some("shinnku/游戏".into())
+    );
+}
+
+#[test]
+fn test_bad_scope_not_found() {
+    let tree = tree();
+    for scope in ["shinnku/zd/9999", "nope", "shinnku/zd/1001-1500/kanon.rar"] {
+        assert!(matches!(
+            FileTreeService::resolve_scope(&tree, scope),
+            Err(AppError::NotFound(_))
+        ));
+    }
+}
+
+#[test]
+fn test_scoped_search() {
+    assert_eq!(
+        search("summer pockets", Some("shinnku/zd/1001-1500".into())),
+        ["zd/1001-1500/summer pockets.rar"]
+    );
+
+    let galgame0 = search("summer pockets", Some("galgame0".into()));
+    assert_eq!(
+        galgame0,
+        ["合集系列/浮士德galgame游戏合集/2020/summer pockets.rar"]
+    );
+
+    assert_eq!(search("summer pockets", None).len(), 4);
+}
+
+#[test]
+fn test_scope_without_query_lists_folder() {
+    let mut listed = search("", Some("shinnku/zd/1001-1500".into()));
+    listed.sort();
+    assert_eq!(
+        listed,
+        ["zd/1001-1500/kanon.rar", "zd/1001-1500/summer pockets.rar"]
+    );
+}
@@ -0,0 +1,114 @@
+use crate::application::search::dto::search_response::SearchResponse;
+use crate::application::search::handlers::search_files_handler::SearchFilesHandler;
+use crate::application::search::queries::search_files_query::SearchFilesQuery;
+use crate::domain::search::repositories::fuzzy_search_repository::FuzzySearchRepository;
+use crate::domain::search::value_objects::cancellation::Cancellation;
+use crate::error::AppError;
+use crate::interfaces::http::controllers::search_controller::spawn_search_events;
+use crate::interfaces::http::dto::search_dto::SearchStreamEvent;
+use crate::tests::support;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+fn response(query: &str, limit: Option<usize>) -> Arc<SearchResponse> {
+    let handler = SearchFilesHandler::new(support::adapter());
+    let query = SearchFilesQuery::new(query.into(), limit, 0);
+    Arc::new(handler.handle(&query, &support::index(support::SUMMER_POCKETS)))
+}
+
+#[test]
+fn test_cancelled_scan_finds_nothing() {
+    let cancellation = Cancellation::new();
+    let adapter = support::adapter().with_cancellation(cancellation.clone());
+    assert_eq!(
+        adapter
+            .search_scored("summer pockets", &support::index(support::SUMMER_POCKETS))
+            .len(),
+        2
+    );
+
+    cancellation.cancel();
+    assert!(
+        adapter
+            .search_scored("summer pockets", &support::index(support::SUMMER_POCKETS))
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_events_list_hits_in_rank_order_then_done() {
+    let response = response("summer pockets", Some(1));
+    let events = SearchStreamEvent::sequence(Ok(&response));
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(
+        events[0],
+        SearchStreamEvent::Hit(response.page.results[0].clone())
+    );
+    let SearchStreamEvent::Done(summary) = &events[1] else {
+        panic!("expected a done event, got {:?}", events[1]);
+    };
+    assert_eq!(summary.total, 2);
+    assert!(summary.next_cursor.is_some());
+}
+
+#[test]
+fn test_ndjson_events_are_keyed_by_name() {
+    let events = SearchStreamEvent::sequence(Ok(&response("kanon", None)));
+    let lines: Vec<serde_json::Value> = events
+        .iter()
+        .map(|event| serde_json::to_value(event).unwrap())
+        .collect();
+
+    assert_eq!(lines[0]["hit"]["id"], "kanon.rar");
+    assert_eq!(lines[1]["done"]["total"], 1);
+
+    let error = AppError::Unavailable("busy".into());
+    let events = SearchStreamEvent::sequence(Err(&error));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].name(), "error");
+}
+
+#[tokio::test]
+async fn test_stream_delivers_search_events() {
+    let response = response("summer pockets", None);
+    let mut events = spawn_search_events(Cancellation::new(), async move { Ok(response) });
+
+    let mut names = Vec::new();
+    while let Some(event) = events.recv().await {
+        names.push(event.name());
+    }
+    assert_eq!(names, ["hit", "hit", "done"]);
+}
+
+#[tokio::test]
+async fn test_disconnect_cancels_search() {
+    let cancellation = Cancellation::new();
+    let cancelled = cancellation.flag();
+    let events = spawn_search_events(cancellation, std::future::pending());
+
+    drop(events);
+    tokio::time::timeout(Duration::from_secs(1), async {
+        while !cancelled.load(Ordering::Relaxed) {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_dropped_request_cancels_search() {
+    let cancellation = Cancellation::new();
+    let cancelled = cancellation.flag();
+    // A JSON request whose client disconnects before the search is done
+    let request = async move {
+        let _cancel = cancellation.cancel_on_drop();
+        std::future::pending::<()>().await;
+    };
+
+    let timed_out = tokio::time::timeout(Duration::from_millis(10), request).await;
+    assert!(timed_out.is_err());
+    assert!(cancelled.load(Ordering::Relaxed));
+}
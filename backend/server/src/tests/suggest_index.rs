@@ -0,0 +1,82 @@
+use crate::domain::search::entities::suggest_index::{SuggestIndex, SuggestionKind};
+use crate::tests::support::tree;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_suggest_titles_and_folders() {
+    let index = SuggestIndex::build(&tree(&[
+        "shinnku/zd/1001-1500/[181026][hulotte] 出会って5分は俺のもの！.rar",
+        "shinnku/0/win/サノバウィッチ/サノバウィッチ.part1.rar",
+        "shinnku/0/win/サノバウィッチ/readme.txt",
+    ]));
+
+    let res = index.suggest("出会", 10);
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].text, "出会って5分は俺のもの！");
+    assert_eq!(res[0].kind, SuggestionKind::File);
+
+    // Tags such as the brand are searchable but the title is what is shown
+    let res = index.suggest("HULOTTE", 10);
+    assert_eq!(res[0].text, "出会って5分は俺のもの！");
+
+    let res = index.suggest("サノバ", 10);
+    assert_eq!(res[0].text, "サノバウィッチ");
+    assert_eq!(res[0].kind, SuggestionKind::Folder);
+    assert_eq!(res[0].path, "shinnku/0/win/サノバウィッチ");
+
+    // Numeric range folders are not offered
+    assert!(index.suggest("1001", 10).is_empty());
+    assert!(index.suggest("", 10).is_empty());
+}
+
+#[test]
+fn test_suggest_ranks_title_prefix_first() {
+    let index = SuggestIndex::build(&tree(&[
+        "a/sweet home/x.rar",
+        "a/my sweet days/x.rar",
+        "a/sweet/x.rar",
+    ]));
+
+    let res: Vec<String> = index
+        .suggest("sweet", 10)
+        .into_iter()
+        .map(|s| s.text)
+        .collect();
+    assert_eq!(res, vec!["sweet", "sweet home", "my sweet days"]);
+    assert_eq!(index.suggest("sweet", 1).len(), 1);
+}
+
+#[test]
+fn test_suggest_dedupe_keeps_first_path_by_name() {
+    let paths = [
+        "shinnku/zd/b/[Key] Kanon.rar",
+        "shinnku/zd/a/[Key] Kanon.7z",
+        "shinnku/zd/c/Kanon.zip",
+    ];
+    for _ in 0..5 {
+        let res = SuggestIndex::build(&tree(&paths)).suggest("kanon", 10);
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].path, "shinnku/zd/a/[Key] Kanon.7z");
+    }
+}
+
+#[test]
+fn test_suggest_is_fast() {
+    let paths: Vec<String> = (0..20_000)
+        .map(|i| {
+            format!(
+                "shinnku/zd/{}/[{i}][brand{}] title number {i}.rar",
+                i / 500,
+                i % 97
+            )
+        })
+        .collect();
+    let refs: Vec<&str> = paths.iter().map(String::as_str).collect();
+    let index = SuggestIndex::build(&tree(&refs));
+
+    let start = Instant::now();
+    for _ in 0..100 {
+        assert!(!index.suggest("ti", 10).is_empty());
+    }
+    assert!(start.elapsed() / 100 < Duration::from_millis(10));
+}
@@ -0,0 +1,78 @@
+use crate::application::files::services::file_tree_service::FileTreeService;
+use crate::domain::files::entities::file_info::FileInfo;
+use crate::domain::files::entities::tree_node::TreeNode;
+use crate::domain::search::entities::search_item::SearchList;
+use crate::domain::search::services::search_index_service::SearchIndexService;
+use crate::infrastructure::adapters::search::fuse_search_adapter::{FuseConfig, FuseSearchAdapter};
+
+/// Two hulotte releases next to two unrelated games, for query rewriting tests
+pub const CATALOG: &[&str] = &[
+    "zd/[181026][hulotte] 出会って5分は俺のもの！.rar",
+    "zd/[190531][hulotte] 抜きゲーみたいな島に住んでる.rar",
+    "0/win/sabbat of the witch.7z",
+    "0/win/summer pockets.7z",
+];
+
+/// Two archives of one game and an unrelated one, for budget and streaming tests
+pub const SUMMER_POCKETS: &[&str] = &["summer pockets.rar", "summer pockets.7z", "kanon.rar"];
+
+/// File of one byte uploaded at the epoch
+pub fn file(path: &str) -> FileInfo {
+    file_with(path, 1, 0)
+}
+
+pub fn file_with(path: &str, file_size: u64, upload_timestamp: u64) -> FileInfo {
+    FileInfo {
+        file_path: path.into(),
+        upload_timestamp,
+        file_size,
+    }
+}
+
+pub fn files(paths: &[&str]) -> Vec<FileInfo> {
+    paths.iter().map(|path| file(path)).collect()
+}
+
+/// Files of `(path, size, upload timestamp)` entries
+pub fn files_with(entries: &[(&str, u64, u64)]) -> Vec<FileInfo> {
+    entries
+        .iter()
+        .map(|(path, size, timestamp)| file_with(path, *size, *timestamp))
+        .collect()
+}
+
+/// Search index of a single bucket holding `paths`
+pub fn index(paths: &[&str]) -> SearchList {
+    SearchIndexService::new().build_index(&[files(paths)])
+}
+
+/// Search index of a single bucket of `(path, size, upload timestamp)` entries
+pub fn index_with(entries: &[(&str, u64, u64)]) -> SearchList {
+    SearchIndexService::new().build_index(&[files_with(entries)])
+}
+
+/// Search index of the shinnku and galgame0 buckets, files only
+pub fn bucket_index(shinnku: Vec<FileInfo>, galgame0: Vec<FileInfo>) -> SearchList {
+    SearchIndexService::new().build_index(&[shinnku, galgame0])
+}
+
+/// Search index of the shinnku and galgame0 buckets followed by their folders,
+/// built the way the server builds it
+pub fn folder_index(shinnku: Vec<FileInfo>, galgame0: Vec<FileInfo>) -> SearchList {
+    let tree = FileTreeService::build_combined_frontend_tree(
+        &TreeNode::from(shinnku.as_slice()),
+        &TreeNode::from(galgame0.as_slice()),
+    );
+    let service = SearchIndexService::new();
+    let mut index = service.build_index(&[shinnku, galgame0]);
+    index.extend(service.build_folder_index(&FileTreeService::bucket_roots(&tree)));
+    index
+}
+
+pub fn tree(paths: &[&str]) -> TreeNode {
+    TreeNode::from(files(paths).as_slice())
+}
+
+pub fn adapter() -> FuseSearchAdapter {
+    FuseSearchAdapter::new(FuseConfig::default())
+}
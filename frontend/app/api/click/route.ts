@@ -0,0 +1,19 @@
+import { type NextRequest, NextResponse } from 'next/server'
+
+export async function POST(req: NextRequest) {
+  const serviceUrl = process.env.BACKEND_URL || 'http://localhost:2999'
+  const body = await req.text()
+  const headers: Record<string, string> = { 'Content-Type': 'application/json' }
+  // The backend counts repeated clicks of one client only once, so always
+  // pass the client address on; without it clicks are not deduplicated
+  const client =
+    req.headers.get('x-forwarded-for') ?? req.headers.get('x-real-ip')
+  if (client) headers['X-Forwarded-For'] = client
+  const res = await fetch(`${serviceUrl}/click`, {
+    method: 'POST',
+    headers,
+    body,
+  }).catch(() => null)
+
+  return new NextResponse(null, { status: res?.status ?? 502 })
+}
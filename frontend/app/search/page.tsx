@@ -1,9 +1,11 @@
+import Link from 'next/link'
 import { notFound } from 'next/navigation'
 
 import Search from '@/components/search'
 import { SearchAnswer } from '@/components/search/SearchAnswer'
 import { SearchIntro } from '@/components/search/SearchIntro'
 import { Separator } from '@/components/ui/separator'
+import { t } from '@/i18n'
 import { ai_search } from '@/lib/search'
 
 export default async function SearchPage({
@@ -11,8 +13,10 @@ export default async function SearchPage({
 }: {
   searchParams: Promise<{ [key: string]: string | string[] | undefined }>
 }) {
-  const q = (await searchParams).q as string
-  const answer = await ai_search(q, 200)
+  const params = await searchParams
+  const q = params.q as string
+  const cursor = params.cursor as string | undefined
+  const { results: answer, next_cursor } = await ai_search(q, 200, cursor)
 
   if (q) {
     return (
@@ -26,7 +30,15 @@ export default async function SearchPage({
           </div>
           <div className='grid grid-cols-1 gap-4 md:grid-cols-[2fr_1px_1fr]'>
             <div className='md:pr-6'>
-              <SearchAnswer answer={answer} />
+              <SearchAnswer answer={answer} query={q} />
+              {next_cursor && (
+                <Link
+                  className='block p-2 text-blue-600 hover:underline'
+                  href={`/search?q=${encodeURIComponent(q)}&cursor=${encodeURIComponent(next_cursor)}`}
+                >
+                  {t('searchNextPage')}
+                </Link>
+              )}
             </div>
             <Separator className='hidden md:block' orientation='vertical' />
             <div className='hidden md:block md:pl-2'>
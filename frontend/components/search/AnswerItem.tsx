@@ -4,14 +4,25 @@ import Link from 'next/link'
 
 import { get_game_type } from '@/lib/url'
 import { num2size } from '@/lib/utils'
-import type { FileInfo } from '@/types'
+import type { FileInfo, ReleaseInfo } from '@/types'
 import { Card, CardContent, CardHeader } from '@ui/card'
 
 interface AnswerItemProps {
   info: FileInfo
+  release?: ReleaseInfo
+  type?: 'file' | 'folder'
+  fileCount?: number
+  query?: string
 }
 
-export const AnswerItem: React.FC<AnswerItemProps> = ({ info }) => {
+export const AnswerItem: React.FC<AnswerItemProps> = ({
+  info,
+  release,
+  type,
+  fileCount,
+  query,
+}) => {
+  const isFolder = type === 'folder'
   let parts = info.file_path.split('/')
   const fileName = parts[parts.length - 1]
   // Fix the href by adding the appropriate routing prefix
@@ -20,19 +31,36 @@ export const AnswerItem: React.FC<AnswerItemProps> = ({ info }) => {
     parts = parts.slice(2) // Remove the first part if it starts with '合集系列/浮士德galgame游戏合集/'
   }
   const href = `/files/${prefix}/${parts.map(encodeURIComponent).join('/')}`
+  // Opened files feed the popularity ranking of the search backend
+  const trackClick = () => {
+    if (isFolder) return
+    navigator.sendBeacon(
+      '/api/click',
+      new Blob([JSON.stringify({ path: info.file_path, q: query })], {
+        type: 'application/json',
+      }),
+    )
+  }
 
   return (
     <Card className='transition-shadow hover:shadow-md'>
       <CardHeader className='pb-0'>
-        <Link className='text-lg text-blue-600 hover:underline' href={href}>
-          {fileName}
+        <Link
+          className='text-lg text-blue-600 hover:underline'
+          href={href}
+          onClick={trackClick}
+        >
+          {isFolder ? `${fileName}/` : fileName}
         </Link>
         <p className='text-muted-foreground text-sm break-all'>
-          文件路径：{info.file_path}
+          {isFolder ? '文件夹路径' : '文件路径'}：{info.file_path}
         </p>
       </CardHeader>
       <CardContent className='text-muted-foreground pt-0 text-sm'>
         <span className='pr-2'>{get_game_type(info.file_path)}</span>
+        {isFolder && <span className='pr-2'>{fileCount ?? 0} 个文件</span>}
+        {release?.brand && <span className='pr-2'>{release.brand}</span>}
+        {release?.date && <span className='pr-2'>{release.date}</span>}
         {num2size(info.file_size)}
       </CardContent>
     </Card>
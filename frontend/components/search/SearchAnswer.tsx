@@ -1,25 +1,38 @@
 'use client'
 
 import { ScrollArea } from '@/components/ui/scroll-area'
-import type { FileInfo } from '@/types'
+import type { FileInfo, ReleaseInfo } from '@/types'
 import { AnswerItem } from './AnswerItem'
 
 export type SearchList = {
   id: string
   info: FileInfo
+  release?: ReleaseInfo
+  type?: 'file' | 'folder'
+  file_count?: number
 }[]
 
 interface SearchAnswerProps {
   answer: SearchList
+  query?: string
 }
 
-export const SearchAnswer: React.FC<SearchAnswerProps> = ({ answer }) => {
+export const SearchAnswer: React.FC<SearchAnswerProps> = ({
+  answer,
+  query,
+}) => {
   return (
     <ScrollArea className='h-400'>
       <div className='flex flex-col'>
         {answer.map((v) => (
-          <div key={v.id} className='p-2'>
-            <AnswerItem info={v.info} />
+          <div key={`${v.type ?? 'file'}:${v.id}`} className='p-2'>
+            <AnswerItem
+              info={v.info}
+              release={v.release}
+              type={v.type}
+              fileCount={v.file_count}
+              query={query}
+            />
           </div>
         ))}
       </div>
@@ -25,6 +25,7 @@ const messages = {
   aboutUnderConstruction: 'Under construction',
   searchPlaceholder: 'Search game name or keyword',
   searchIntroFromGemini: 'Intro powered by gemini 2.5 pro',
+  searchNextPage: 'Next page',
   navMenuDirectory: 'Directory',
   pageWelcomeDescription:
     'Shinnku (formerly Lost Station) is a visual novel resource site collecting most translated visual novels, raw resources, krkr resources, and more.',
@@ -25,6 +25,7 @@ const messages = {
   aboutUnderConstruction: '仍在施工中',
   searchPlaceholder: '请搜索游戏名称或关键词',
   searchIntroFromGemini: '简介来自gemini 2.5 pro的支持',
+  searchNextPage: '下一页',
   navMenuDirectory: '目录',
   pageWelcomeDescription:
     '真红小站（原 失落小站）一个galgame资源站, 收录了大部分的汉化galgame, 大部分的生肉galgame资源，krkr资源，visual novel，等等。',
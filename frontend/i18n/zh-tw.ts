@@ -25,6 +25,7 @@ const messages = {
   aboutUnderConstruction: '仍在施工中',
   searchPlaceholder: '請搜索遊戲名稱或關鍵詞',
   searchIntroFromGemini: '簡介來自gemini 2.5 pro的支持',
+  searchNextPage: '下一頁',
   navMenuDirectory: '目錄',
   pageWelcomeDescription:
     '真紅小站（原 失落小站）一個galgame資源站, 收錄了大部分的漢化galgame, 大部分的生肉galgame資源，krkr資源，visual novel，等等。',
@@ -1,36 +1,45 @@
-import { type SearchItem, SearchItemSchema } from './validation'
+import { type SearchPage, SearchPageSchema } from './validation'
 
-export async function ai_search(q: string, n: number): Promise<SearchItem[]> {
+const EMPTY_PAGE: SearchPage = { total: 0, next_cursor: null, results: [] }
+
+export async function ai_search(
+  q: string,
+  n: number,
+  cursor?: string,
+): Promise<SearchPage> {
   const serviceUrl = process.env.BACKEND_URL || 'http://localhost:2999'
+  const page = cursor ? `&cursor=${encodeURIComponent(cursor)}` : ''
 
   const raw = await fetch(
-    `${serviceUrl}/aisearch?q=${encodeURIComponent(q)}&n=${n}`,
+    `${serviceUrl}/aisearch?q=${encodeURIComponent(q)}&n=${n}${page}`,
   )
     .then((res) => res.json())
-    .catch(() => [])
+    .catch(() => EMPTY_PAGE)
 
   try {
-    return SearchItemSchema.array().parse(raw)
+    return SearchPageSchema.parse(raw)
   } catch {
-    return []
+    return EMPTY_PAGE
   }
 }
 
 export async function default_search(
   q: string,
   n: number,
-): Promise<SearchItem[]> {
+  cursor?: string,
+): Promise<SearchPage> {
   const serviceUrl = process.env.BACKEND_URL || 'http://localhost:2999'
+  const page = cursor ? `&cursor=${encodeURIComponent(cursor)}` : ''
 
   const raw = await fetch(
-    `${serviceUrl}/search?q=${encodeURIComponent(q)}&n=${n}`,
+    `${serviceUrl}/search?q=${encodeURIComponent(q)}&n=${n}${page}`,
   )
     .then((res) => res.json())
-    .catch(() => [])
+    .catch(() => EMPTY_PAGE)
 
   try {
-    return SearchItemSchema.array().parse(raw)
+    return SearchPageSchema.parse(raw)
   } catch {
-    return []
+    return EMPTY_PAGE
   }
 }
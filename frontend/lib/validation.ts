@@ -6,13 +6,30 @@ export const FileInfoSchema = z.object({
   file_size: z.number(),
 })
 export type FileInfo = z.infer<typeof FileInfoSchema>
+export const ReleaseInfoSchema = z.object({
+  date: z.string().optional(),
+  brand: z.string().optional(),
+  title: z.string(),
+  extension: z.string().optional(),
+})
+export type ReleaseInfo = z.infer<typeof ReleaseInfoSchema>
 export const SearchItemSchema = z.object({
   id: z.string(),
   info: FileInfoSchema,
+  release: ReleaseInfoSchema.optional(),
+  matched_query: z.string().optional(),
+  type: z.enum(['file', 'folder']).optional(),
+  file_count: z.number().optional(),
 })
 export type SearchItem = z.infer<typeof SearchItemSchema>
 export const SearchListSchema = z.array(SearchItemSchema)
 export type SearchList = z.infer<typeof SearchListSchema>
+export const SearchPageSchema = z.object({
+  total: z.number(),
+  next_cursor: z.string().nullable(),
+  results: SearchListSchema,
+})
+export type SearchPage = z.infer<typeof SearchPageSchema>
 export const BlogFrontmatterSchema = z.object({
   title: z.string(),
   banner: z.string(),
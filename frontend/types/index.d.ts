@@ -1,8 +1,9 @@
-import { FileInfo } from '@/lib/validation'
+import { FileInfo, ReleaseInfo } from '@/lib/validation'
 
 export type FileInfo = FileInfo
+export type ReleaseInfo = ReleaseInfo
 export type FileOrFolder =
-  | { type: 'file'; name: string; info: FileInfo }
+  | { type: 'file'; name: string; info: FileInfo; release?: ReleaseInfo }
   | { type: 'folder'; name: string }
 export type GameType = '熟肉' | '生肉' | '手机'
 export type WikipediaAnswer = {